@@ -30,10 +30,6 @@ const navUpdateSchema = z.object({
     assetId: z.string().min(32, 'Invalid asset ID'),
     currentValue: z.string().min(1, 'Value is required'),
   })).min(1, 'At least one asset valuation is required'),
-  netDailyPnl: z.string().refine(
-    (val) => parseUserInput(val) !== null,
-    'Invalid P&L amount'
-  ),
   notes: z.string().optional(),
 });
 
@@ -54,7 +50,6 @@ const NavUpdatePanel: React.FC = () => {
     resolver: zodResolver(navUpdateSchema),
     defaultValues: {
       assetValuations: [{ assetId: '', currentValue: '' }],
-      netDailyPnl: '0',
       notes: '',
     },
     mode: 'onChange',
@@ -69,7 +64,7 @@ const NavUpdatePanel: React.FC = () => {
 
   // Calculate new NAV preview
   const navPreview = useMemo(() => {
-    if (!fundState || !watchedValues.assetValuations?.length || !watchedValues.netDailyPnl) {
+    if (!fundState || !watchedValues.assetValuations?.length) {
       return null;
     }
 
@@ -79,11 +74,12 @@ const NavUpdatePanel: React.FC = () => {
         return sum + (value || 0);
       }, 0);
 
-      const netPnl = parseUserInput(watchedValues.netDailyPnl) || 0;
+      const cashReserves = bnToNumber(fundState.cashReserves, 6);
       const currentTotalAssets = bnToNumber(fundState.totalAssets, 8);
       const totalShares = bnToNumber(fundState.totalShares, 8);
       
-      const newTotalAssets = totalAssetValue + netPnl;
+      // Total assets are cash plus every held asset, as computed on-chain
+      const newTotalAssets = cashReserves + totalAssetValue;
       const newNavPerShare = totalShares > 0 ? newTotalAssets / totalShares : 1.0;
       const currentNavPerShare = bnToNumber(fundState.navPerShare, 8);
       
@@ -114,11 +110,8 @@ const NavUpdatePanel: React.FC = () => {
         currentValue: new BN(Math.floor((parseUserInput(asset.currentValue) || 0) * 100_000_000)),
       }));
 
-      const netDailyPnl = parseUserInput(data.netDailyPnl) || 0;
-
       await updateNavMutation.mutateAsync({
         newAssetValuations: assetValuations,
-        netDailyPnl,
       });
 
       toast.success('NAV updated successfully!');
@@ -253,25 +246,6 @@ const NavUpdatePanel: React.FC = () => {
             </div>
           </div>
 
-          {/* Net Daily P&L */}
-          <div>
-            <label className="block text-sm font-medium text-gray-700 mb-2">
-              Net Daily P&L (USD)
-            </label>
-            <input
-              {...register('netDailyPnl')}
-              type="text"
-              placeholder="0.00 (positive for profit, negative for loss)"
-              className="w-full px-3 py-2 border border-gray-300 rounded-md focus:ring-primary-500 focus:border-primary-500"
-            />
-            {errors.netDailyPnl && (
-              <p className="text-sm text-danger-600 mt-1">{errors.netDailyPnl.message}</p>
-            )}
-            <p className="text-sm text-gray-500 mt-1">
-              Enter positive values for gains, negative for losses
-            </p>
-          </div>
-
           {/* Notes */}
           <div>
             <label className="block text-sm font-medium text-gray-700 mb-2">
@@ -119,9 +119,8 @@ export const useUpdateNav = () => {
   const queryClient = useQueryClient();
 
   return useMutation(
-    async ({ newAssetValuations, netDailyPnl }: {
+    async ({ newAssetValuations }: {
       newAssetValuations: AssetValuation[];
-      netDailyPnl: number;
     }) => {
       if (!program || !getFundStatePDA) {
         throw new Error('Program not connected');
@@ -132,15 +131,19 @@ export const useUpdateNav = () => {
         throw new Error('Cannot find fund state PDA');
       }
 
-      // Convert netDailyPnl to BN with 8 decimals
-      const netDailyPnlBN = new BN(Math.floor(netDailyPnl * 100_000_000));
-
+      // Every held asset account is passed in valuation order so the
+      // program can check completeness and price each one exactly once
       const tx = await program.methods
-        .updateNav(newAssetValuations, netDailyPnlBN)
+        .updateNav(newAssetValuations)
         .accounts({
           admin: program.provider.publicKey!,
           fundState: fundStatePDA,
         })
+        .remainingAccounts(newAssetValuations.map(valuation => ({
+          pubkey: valuation.assetId,
+          isWritable: true,
+          isSigner: false,
+        })))
         .rpc();
 
       return tx;
@@ -1,6 +1,6 @@
 import { useMemo } from 'react';
 import { Connection, PublicKey } from '@solana/web3.js';
-import { Program, AnchorProvider, Idl } from '@coral-xyz/anchor';
+import { Program, AnchorProvider, Idl, BN } from '@coral-xyz/anchor';
 import { useConnection, useWallet } from '@solana/wallet-adapter-react';
 import { PROGRAM_CONFIG } from '../types';
 
@@ -45,8 +45,7 @@ const IDL: Idl = {
         { name: "fundState", isMut: true, isSigner: false },
       ],
       args: [
-        { name: "newAssetValuations", type: { vec: { defined: "AssetValuation" } } },
-        { name: "netDailyPnl", type: "i64" }
+        { name: "newAssetValuations", type: { vec: { defined: "AssetValuation" } } }
       ]
     }
   ],
@@ -141,12 +140,12 @@ export const useProgram = () => {
     []
   );
 
-  // Helper function to get fund state PDA
+  // Helper function to get fund state PDA (funds are keyed by registry id)
   const getFundStatePDA = useMemo(() => {
-    return () => {
+    return (fundId: number = PROGRAM_CONFIG.FUND_ID) => {
       if (!program) return null;
       return PublicKey.findProgramAddressSync(
-        [Buffer.from('fund_state')],
+        [Buffer.from('fund_state'), new BN(fundId).toArrayLike(Buffer, 'le', 8)],
         program.programId
       )[0];
     };
@@ -154,14 +153,15 @@ export const useProgram = () => {
 
   // Helper function to get user fund account PDA
   const getUserFundAccountPDA = useMemo(() => {
-    return (userPubkey: PublicKey) => {
-      if (!program) return null;
+    return (userPubkey: PublicKey, fundId: number = PROGRAM_CONFIG.FUND_ID) => {
+      const fundStatePDA = getFundStatePDA(fundId);
+      if (!program || !fundStatePDA) return null;
       return PublicKey.findProgramAddressSync(
-        [Buffer.from('user_fund'), userPubkey.toBuffer()],
+        [Buffer.from('user_account'), fundStatePDA.toBuffer(), userPubkey.toBuffer()],
         program.programId
       )[0];
     };
-  }, [program]);
+  }, [program, getFundStatePDA]);
 
   return {
     program,
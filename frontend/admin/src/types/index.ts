@@ -136,7 +136,6 @@ export interface ChartDataPoint {
 // Form Types
 export interface NavUpdateForm {
   newAssetValuations: AssetValuation[];
-  netDailyPnl: string;
   notes?: string;
 }
 
@@ -194,6 +193,7 @@ export const DECIMAL_PLACES = {
 
 export const PROGRAM_CONFIG = {
   PROGRAM_ID: '2gtiJ4B3Fv6oF6ZEYJcXdoNTGVC4jG5bNQjXs9ELWrhx',
+  FUND_ID: 0,
   NETWORK: 'localnet',
   RPC_URL: 'http://localhost:8899',
 } as const; 
\ No newline at end of file
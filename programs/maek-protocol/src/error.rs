@@ -154,4 +154,113 @@ pub enum ErrorCode {
     
     #[msg("Duration risk exceeds acceptable threshold")]
     DurationRiskExceeded,
-} 
\ No newline at end of file
+    
+    // Asset Lifecycle Errors
+    #[msg("Asset has not reached its maturity date")]
+    AssetNotMatured,
+    
+    #[msg("Asset is not active")]
+    AssetNotActive,
+    
+    // Credit Event Errors
+    #[msg("Asset is not flagged as in default")]
+    AssetNotInDefault,
+    
+    #[msg("Write-down must be between 0 and 10000 basis points")]
+    InvalidWriteDown,
+    
+    #[msg("Asset write-down history is full")]
+    WriteDownHistoryFull,
+    
+    // NAV Valuation Errors
+    #[msg("Every held asset must be valued exactly once")]
+    IncompleteAssetValuations,
+    
+    #[msg("Asset valuation does not match the supplied asset account")]
+    AssetValuationMismatch,
+    
+    #[msg("Asset valued more than once")]
+    DuplicateAssetValuation,
+    
+    // Pricing Errors
+    #[msg("Purchase price is inconsistent with face value and yield")]
+    PriceYieldMismatch,
+    
+    #[msg("Invalid coupon rate or frequency for asset type")]
+    InvalidCouponTerms,
+    
+    // Portfolio Maturity Errors
+    #[msg("Weighted-average maturity exceeds fund limit")]
+    WeightedAverageMaturityExceeded,
+    
+    #[msg("Weighted-average life exceeds fund limit")]
+    WeightedAverageLifeExceeded,
+    
+    // Fee Errors
+    #[msg("No accrued fees to claim")]
+    NoFeesToClaim,
+    
+    // Redemption Queue Errors
+    #[msg("Redemption requests must be processed in queue order")]
+    RedemptionQueueOrder,
+    
+    #[msg("Withdrawals must wait behind queued redemptions")]
+    RedemptionsQueued,
+    
+    // Forward Pricing Errors
+    #[msg("Fund uses forward pricing; place an order instead")]
+    ForwardPricingActive,
+    
+    #[msg("Fund prices at the last published NAV; deposit or withdraw directly")]
+    ForwardPricingInactive,
+    
+    #[msg("Order cannot settle before the next NAV update")]
+    OrderAwaitingNAV,
+    
+    // Redemption Gate Errors
+    #[msg("Redemption exceeds the remaining gate capacity for this NAV period")]
+    RedemptionGateExceeded,
+    
+    // Custodian Attestation Errors
+    #[msg("NAV update requires an attestation from the fund's custodian")]
+    MissingAttestation,
+    
+    #[msg("Custodian attestation is too old")]
+    StaleAttestation,
+    
+    #[msg("Attested par does not cover the par of held assets within tolerance")]
+    AttestationMismatch,
+    
+    #[msg("Holdings statement is not signed by the custodian")]
+    InvalidAttestationSignature,
+    
+    #[msg("Holdings statement is not newer than the recorded attestation")]
+    AttestationOutdated,
+    
+    // Oracle Errors
+    #[msg("Price feed account is missing, malformed or not the asset's feed")]
+    InvalidPriceFeed,
+    
+    #[msg("Oracle confidence interval is too wide")]
+    OracleConfidenceTooWide,
+    
+    // Price Deviation Errors
+    #[msg("Valuation moved beyond the asset type's deviation limit without a price override")]
+    PriceDeviationExceeded,
+    
+    // Defaulted Asset Valuation Errors
+    #[msg("Defaulted assets are revalued only through write-downs and recoveries")]
+    DefaultedAssetRevalued,
+    
+    // Order Settlement Errors
+    #[msg("Orders struck at the current NAV must settle before the next NAV update")]
+    OrdersAwaitingSettlement,
+    
+    // Treasury Settlement Errors
+    #[msg("Treasury vault does not hold the proceeds being booked")]
+    ProceedsNotReceived,
+    
+    // Default Recovery Errors
+    #[msg("Recoveries cannot exceed the defaulted asset's par value")]
+    RecoveryExceedsPar,
+}
@@ -3,6 +3,7 @@ use anchor_lang::prelude::*;
 #[event]
 pub struct FundInitialized {
     pub fund_state: Pubkey,
+    pub fund_id: u64,
     pub admin: Pubkey,
     pub inception_date: i64,
 }
@@ -13,6 +14,8 @@ pub struct DepositMade {
     pub amount_usdc: u64,
     pub fund_tokens_minted: u64,
     pub nav_per_share: u64,
+    pub swing_factor_bps: u16,
+    pub swing_levy: u64,
     pub timestamp: i64,
 }
 
@@ -22,6 +25,8 @@ pub struct WithdrawalMade {
     pub fund_tokens_burned: u64,
     pub amount_usdc: u64,
     pub nav_per_share: u64,
+    pub swing_factor_bps: u16,
+    pub swing_levy: u64,
     pub timestamp: i64,
 }
 
@@ -35,6 +40,15 @@ pub struct NAVUpdated {
     pub cash_reserves: u64,
     pub daily_pnl: i64,
     pub management_fee: u64,
+    pub performance_fee: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PerformanceFeeCrystallized {
+    pub amount: u64,
+    pub high_water_mark: u64,
+    pub period_start: i64,
     pub timestamp: i64,
 }
 
@@ -63,6 +77,139 @@ pub struct AssetMatured {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct AssetSold {
+    pub asset_id: Pubkey,
+    pub sale_price: u64,
+    pub cost_basis: u64,
+    pub realized_pnl: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetRevalued {
+    pub asset_id: Pubkey,
+    pub price_feed: Pubkey,
+    pub previous_value: u64,
+    pub new_value: u64,
+    pub publish_time: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PriceOverridden {
+    pub asset_id: Pubkey,
+    pub authority: Pubkey,
+    pub previous_value: u64,
+    pub new_value: u64,
+    pub deviation_bps: u64,
+    pub max_deviation_bps: u32,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct CouponReceived {
+    pub asset_id: Pubkey,
+    pub amount: u64,
+    pub next_coupon_date: i64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct InterestAccrued {
+    pub asset_id: Pubkey,
+    pub days: u32,
+    pub interest: u64,
+    pub accrued_interest: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AssetWrittenDown {
+    pub asset_id: Pubkey,
+    pub write_down_bps: u16,
+    pub write_down_amount: u64,
+    pub new_value: u64,
+    pub nav_per_share: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DefaultRecoveryRecorded {
+    pub asset_id: Pubkey,
+    pub recovery_amount: u64,
+    pub remaining_value: u64,
+    pub written_off: u64,
+    pub nav_per_share: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct FeesClaimed {
+    pub fee_recipient: Pubkey,
+    pub amount_usdc: u64,
+    pub remaining_payable: u64,
+    pub cash_reserves: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedemptionQueued {
+    pub request: Pubkey,
+    pub owner: Pubkey,
+    pub sequence: u64,
+    pub fund_tokens: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RedemptionProcessed {
+    pub request: Pubkey,
+    pub owner: Pubkey,
+    pub sequence: u64,
+    pub fund_tokens_burned: u64,
+    pub amount_usdc: u64,
+    pub nav_per_share: u64,
+    pub swing_factor_bps: u16,
+    pub swing_levy: u64,
+    pub remaining_tokens: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OrderPlaced {
+    pub order: Pubkey,
+    pub owner: Pubkey,
+    pub kind: u8,
+    pub amount: u64,
+    pub sequence: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct OrderSettled {
+    pub order: Pubkey,
+    pub owner: Pubkey,
+    pub kind: u8,
+    pub fund_tokens: u64,
+    pub amount_usdc: u64,
+    pub nav_per_share: u64,
+    pub swing_factor_bps: u16,
+    pub swing_levy: u64,
+    pub remaining_amount: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct AttestationRecorded {
+    pub fund: Pubkey,
+    pub custodian: Pubkey,
+    pub statement_date: i64,
+    pub total_par: u64,
+    pub positions_hash: [u8; 32],
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct LiquidityManaged {
     pub action: String,
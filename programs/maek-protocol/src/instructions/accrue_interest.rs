@@ -0,0 +1,162 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::{CouponReceived, InterestAccrued};
+use crate::utils::calculations::*;
+
+#[derive(Accounts)]
+pub struct AccrueInterest<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fixed_income_asset", fund_state.key().as_ref(), fixed_income_asset.asset_id.as_ref()],
+        bump = fixed_income_asset.bump
+    )]
+    pub fixed_income_asset: Box<Account<'info, FixedIncomeAsset>>,
+}
+
+/// Permissionless crank advancing an asset's accrued interest to now
+pub fn accrue_interest(ctx: Context<AccrueInterest>) -> Result<()> {
+    let asset_key = ctx.accounts.fixed_income_asset.key();
+    
+    // Defaulted assets are non-accrual
+    require!(ctx.accounts.fixed_income_asset.status == AssetStatus::Active, ErrorCode::AssetNotActive);
+    
+    accrue_asset_interest(&mut ctx.accounts.fund_state, &mut ctx.accounts.fixed_income_asset, asset_key)
+}
+
+/// Advance an active asset's accrued interest and coupons to now, by whole days
+pub(crate) fn accrue_asset_interest(fund_state: &mut FundState, asset: &mut FixedIncomeAsset, asset_key: Pubkey) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    // Interest stops accruing at maturity
+    let accrue_until = std::cmp::min(clock.unix_timestamp, asset.maturity_date);
+    let elapsed_days = (accrue_until - asset.last_interest_calculation) / 86400;
+    if elapsed_days <= 0 {
+        msg!("No full day to accrue for asset {}", asset_key);
+        return Ok(());
+    }
+    
+    // Advance by whole days so partial days carry into the next crank
+    let period_end = asset.last_interest_calculation + elapsed_days * 86400;
+    
+    let mut interest = 0u64;
+    let mut coupons_received = 0u64;
+    if asset.coupon_frequency > 0 {
+        // Coupon instruments accrue their coupon on par, period by period
+        let mut accrued = asset.accrued_interest;
+        let mut accrued_from = asset.last_interest_calculation;
+        let mut purchased_accrued = 0u64;
+        
+        // Coupons falling due before maturity are paid out; the final coupon settles with the principal
+        while asset.next_coupon_date != 0 && asset.next_coupon_date < asset.maturity_date && asset.next_coupon_date <= period_end {
+            let coupon_date = asset.next_coupon_date;
+            let earned = calculate_coupon_accrual(
+                asset.face_value,
+                asset.coupon_rate,
+                asset.coupon_frequency,
+                asset.day_count_convention,
+                accrued_from,
+                coupon_date,
+                asset.maturity_date,
+            )?;
+            interest = interest.checked_add(earned).ok_or(ErrorCode::MathOverflow)?;
+            accrued = accrued.checked_add(earned).ok_or(ErrorCode::MathOverflow)?;
+            
+            // The part of the coupon accrued before purchase was paid for in the purchase price
+            let coupon = calculate_coupon_payment(asset.face_value, asset.coupon_rate, asset.coupon_frequency)?;
+            purchased_accrued = purchased_accrued.checked_add(coupon.saturating_sub(accrued)).ok_or(ErrorCode::MathOverflow)?;
+            coupons_received = coupons_received.checked_add(coupon).ok_or(ErrorCode::MathOverflow)?;
+            accrued = 0;
+            
+            accrued_from = coupon_date;
+            asset.next_coupon_date = coupon_period(asset.maturity_date, asset.coupon_frequency, coupon_date)?.1;
+            
+            // A floating rate resets on the coupon date, moving the holding's WAM date to the next reset
+            if asset.floating_rate {
+                fund_state.reset_weighted_sum = fund_state.reset_weighted_sum
+                    .checked_add(asset.purchase_price as u128 * (asset.next_coupon_date - coupon_date) as u128)
+                    .ok_or(ErrorCode::MathOverflow)?;
+            }
+        }
+        
+        let earned = calculate_coupon_accrual(
+            asset.face_value,
+            asset.coupon_rate,
+            asset.coupon_frequency,
+            asset.day_count_convention,
+            accrued_from,
+            period_end,
+            asset.maturity_date,
+        )?;
+        interest = interest.checked_add(earned).ok_or(ErrorCode::MathOverflow)?;
+        asset.accrued_interest = accrued.checked_add(earned).ok_or(ErrorCode::MathOverflow)?;
+        asset.current_value = asset.current_value.checked_sub(purchased_accrued).ok_or(ErrorCode::MathOverflow)?;
+    } else if is_discount_instrument(asset.asset_type) && asset.price_feed != Pubkey::default() {
+        // An oracle-priced discount instrument's market price already includes its accretion,
+        // so only the accrual clock advances
+    } else {
+        // Discount instruments accrete on the amount invested, interest-at-maturity instruments accrue on par
+        let discount_instrument = is_discount_instrument(asset.asset_type);
+        let principal = if discount_instrument { asset.purchase_price } else { asset.face_value };
+        
+        interest = calculate_accrued_interest(
+            principal,
+            asset.yield_rate,
+            asset.day_count_convention,
+            asset.last_interest_calculation,
+            period_end,
+        )?;
+        
+        // Accretion never takes a discount instrument's carrying value above par
+        if discount_instrument {
+            let remaining_discount = asset.face_value
+                .saturating_sub(asset.current_value)
+                .saturating_sub(asset.accrued_interest);
+            interest = std::cmp::min(interest, remaining_discount);
+        }
+        
+        asset.accrued_interest = asset.accrued_interest.checked_add(interest).ok_or(ErrorCode::MathOverflow)?;
+    }
+    
+    asset.last_interest_calculation = period_end;
+    asset.days_to_maturity = ((asset.maturity_date - period_end).max(0) / 86400) as u16;
+    
+    // Accrued interest is part of the holding's carrying value
+    fund_state.fixed_income_value = fund_state.fixed_income_value.checked_add(interest).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.total_assets = fund_state.total_assets.checked_add(interest).ok_or(ErrorCode::MathOverflow)?;
+    
+    // Coupons are paid by the custodian into the treasury vault, leaving the holding's carrying value
+    if coupons_received > 0 {
+        let coupon_usdc = coupons_received / 100; // Convert 8 to 6 decimals
+        let unsettled = coupons_received - coupon_usdc * 100;
+        fund_state.treasury_cash = fund_state.treasury_cash.checked_add(coupon_usdc).ok_or(ErrorCode::MathOverflow)?;
+        fund_state.fixed_income_value = fund_state.fixed_income_value.checked_sub(unsettled).ok_or(ErrorCode::MathOverflow)?;
+        fund_state.total_assets = fund_state.total_assets.checked_sub(unsettled).ok_or(ErrorCode::MathOverflow)?;
+        
+        emit!(CouponReceived {
+            asset_id: asset_key,
+            amount: coupon_usdc,
+            next_coupon_date: asset.next_coupon_date,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    
+    emit!(InterestAccrued {
+        asset_id: asset_key,
+        days: elapsed_days as u32,
+        interest,
+        accrued_interest: asset.accrued_interest,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Accrued {} interest on asset {} over {} days", interest, asset_key, elapsed_days);
+    
+    Ok(())
+}
@@ -1,24 +1,31 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::ErrorCode;
+use crate::events::{EmergencyPause, FundUnpaused};
 
 #[derive(Accounts)]
 pub struct AdminPause<'info> {
     #[account(
         mut,
-        seeds = [b"fund_state"],
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
         has_one = admin_authority
     )]
-    pub fund_state: Account<'info, FundState>,
+    pub fund_state: Box<Account<'info, FundState>>,
     
     pub admin_authority: Signer<'info>,
 }
 
-pub fn pause_fund(ctx: Context<AdminPause>) -> Result<()> {
+pub fn pause_fund(ctx: Context<AdminPause>, reason: String) -> Result<()> {
     let fund_state = &mut ctx.accounts.fund_state;
     fund_state.is_paused = true;
     
+    emit!(EmergencyPause {
+        reason,
+        paused_by: ctx.accounts.admin_authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
     msg!("Fund has been paused");
     Ok(())
 }
@@ -27,6 +34,221 @@ pub fn unpause_fund(ctx: Context<AdminPause>) -> Result<()> {
     let fund_state = &mut ctx.accounts.fund_state;
     fund_state.is_paused = false;
     
+    emit!(FundUnpaused {
+        unpaused_by: ctx.accounts.admin_authority.key(),
+        timestamp: Clock::get()?.unix_timestamp,
+    });
+    
     msg!("Fund has been unpaused");
     Ok(())
-} 
\ No newline at end of file
+}
+
+#[derive(Accounts)]
+pub struct AdminConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = admin_authority
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    pub admin_authority: Signer<'info>,
+}
+
+pub fn set_max_portfolio_duration(ctx: Context<AdminConfig>, max_portfolio_duration: u32) -> Result<()> {
+    // Between one day and 30 years (10_000 = 1 year)
+    require!((28..=300_000).contains(&max_portfolio_duration), ErrorCode::InvalidAmount);
+    
+    let fund_state = &mut ctx.accounts.fund_state;
+    fund_state.max_portfolio_duration = max_portfolio_duration;
+    
+    msg!("Maximum portfolio duration set to {}", max_portfolio_duration);
+    Ok(())
+}
+
+pub fn set_maturity_limits(ctx: Context<AdminConfig>, max_wam_days: u16, max_wal_days: u16) -> Result<()> {
+    // WAM can never exceed WAL; both are capped at the 397-day single-holding limit
+    require!(max_wam_days >= 1 && max_wam_days <= max_wal_days && max_wal_days <= 397, ErrorCode::InvalidAmount);
+    
+    let fund_state = &mut ctx.accounts.fund_state;
+    fund_state.max_wam_days = max_wam_days;
+    fund_state.max_wal_days = max_wal_days;
+    
+    msg!("Maturity limits set to WAM {} days, WAL {} days", max_wam_days, max_wal_days);
+    Ok(())
+}
+
+pub fn set_fee_recipient(ctx: Context<AdminConfig>, fee_recipient: Pubkey) -> Result<()> {
+    require!(fee_recipient != Pubkey::default(), ErrorCode::InvalidAccountData);
+    
+    let fund_state = &mut ctx.accounts.fund_state;
+    fund_state.fee_recipient = fee_recipient;
+    
+    msg!("Fee recipient set to {}", fee_recipient);
+    Ok(())
+}
+
+pub fn set_performance_fee(
+    ctx: Context<AdminConfig>,
+    performance_fee_bps: u16,
+    hurdle_rate_bps: u16,
+    crystallization_period: i64,
+) -> Result<()> {
+    require!(performance_fee_bps <= 3000, ErrorCode::FeeTooHigh); // Max 30% of gains
+    require!(hurdle_rate_bps <= 2000, ErrorCode::InvalidAmount); // Max 20% annual hurdle
+    if performance_fee_bps > 0 {
+        // Between one day and one year
+        require!((86400..=366 * 86400).contains(&crystallization_period), ErrorCode::InvalidAmount);
+    }
+    
+    let fund_state = &mut ctx.accounts.fund_state;
+    
+    // Switching the fee on starts a fresh period, charging only gains made from here
+    if fund_state.performance_fee_bps == 0 && performance_fee_bps > 0 {
+        fund_state.high_water_mark = fund_state.high_water_mark.max(fund_state.nav_per_share);
+        fund_state.performance_period_start = Clock::get()?.unix_timestamp;
+    }
+    
+    fund_state.performance_fee_bps = performance_fee_bps;
+    fund_state.hurdle_rate_bps = hurdle_rate_bps;
+    fund_state.crystallization_period = crystallization_period;
+    
+    msg!(
+        "Performance fee set to {} bps over a {} bps hurdle, crystallizing every {} seconds",
+        performance_fee_bps, hurdle_rate_bps, crystallization_period
+    );
+    Ok(())
+}
+
+pub fn set_swing_pricing(
+    ctx: Context<AdminConfig>,
+    inflow_swing_threshold_bps: u16,
+    outflow_swing_threshold_bps: u16,
+    inflow_swing_factor_bps: u16,
+    outflow_swing_factor_bps: u16,
+) -> Result<()> {
+    // Thresholds up to 100% of total assets, swing factors up to 2%
+    require!(inflow_swing_threshold_bps <= 10_000 && outflow_swing_threshold_bps <= 10_000, ErrorCode::InvalidAmount);
+    require!(inflow_swing_factor_bps <= 200 && outflow_swing_factor_bps <= 200, ErrorCode::InvalidAmount);
+    
+    let fund_state = &mut ctx.accounts.fund_state;
+    fund_state.inflow_swing_threshold_bps = inflow_swing_threshold_bps;
+    fund_state.outflow_swing_threshold_bps = outflow_swing_threshold_bps;
+    fund_state.inflow_swing_factor_bps = inflow_swing_factor_bps;
+    fund_state.outflow_swing_factor_bps = outflow_swing_factor_bps;
+    
+    msg!(
+        "Swing pricing set to {}/{} bps above {}/{} bps net flows",
+        inflow_swing_factor_bps, outflow_swing_factor_bps, inflow_swing_threshold_bps, outflow_swing_threshold_bps
+    );
+    Ok(())
+}
+
+pub fn set_redemption_gate(ctx: Context<AdminConfig>, redemption_gate_bps: u16) -> Result<()> {
+    require!(redemption_gate_bps <= 10_000, ErrorCode::InvalidAmount);
+    
+    let fund_state = &mut ctx.accounts.fund_state;
+    
+    // A newly enabled gate measures against current assets until the next NAV update
+    if fund_state.redemption_gate_bps == 0 {
+        fund_state.gate_period_assets = fund_state.total_assets;
+    }
+    fund_state.redemption_gate_bps = redemption_gate_bps;
+    
+    msg!("Redemption gate set to {} bps of period assets", redemption_gate_bps);
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RegistryConfig<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    #[account(
+        seeds = [b"fund_registry"],
+        bump = fund_registry.bump,
+        has_one = authority @ ErrorCode::UnauthorizedAccess
+    )]
+    pub fund_registry: Box<Account<'info, FundRegistry>>,
+    
+    pub authority: Signer<'info>,
+}
+
+/// Appointed by the registry authority so the fund admin cannot override its own valuations
+pub fn set_price_override_authority(ctx: Context<RegistryConfig>, price_override_authority: Pubkey) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    require_keys_neq!(price_override_authority, fund_state.admin_authority, ErrorCode::InvalidAccountData);
+    
+    fund_state.price_override_authority = price_override_authority;
+    
+    msg!("Price override authority set to {}", price_override_authority);
+    Ok(())
+}
+
+/// Appointed by the registry authority so the fund admin cannot redirect purchase settlements
+pub fn set_settlement_account(ctx: Context<RegistryConfig>, settlement_account: Pubkey) -> Result<()> {
+    require!(settlement_account != Pubkey::default(), ErrorCode::InvalidAccountData);
+    
+    let fund_state = &mut ctx.accounts.fund_state;
+    fund_state.settlement_account = settlement_account;
+    
+    msg!("Settlement account set to {}", settlement_account);
+    Ok(())
+}
+
+/// Set by the registry authority so the fund admin cannot loosen the limits on its own valuations
+pub fn set_price_deviation_limit(
+    ctx: Context<RegistryConfig>,
+    asset_type: FixedIncomeAssetType,
+    max_deviation_bps: u16,
+) -> Result<()> {
+    require!(max_deviation_bps > 0 && max_deviation_bps <= 5_000, ErrorCode::InvalidAmount);
+    
+    let fund_state = &mut ctx.accounts.fund_state;
+    fund_state.price_deviation_limits_bps[asset_type as usize] = max_deviation_bps;
+    
+    msg!("Price deviation limit for asset type {} set to {} bps", asset_type as u8, max_deviation_bps);
+    Ok(())
+}
+
+/// Appointed by the registry authority so the fund admin cannot attest to its own holdings
+pub fn set_custodian(
+    ctx: Context<RegistryConfig>,
+    custodian: Pubkey,
+    max_attestation_age: i64,
+    attestation_tolerance_bps: u16,
+) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    
+    // Once appointed, a custodian can be replaced but never removed
+    require!(custodian != Pubkey::default(), ErrorCode::InvalidAccountData);
+    require_keys_neq!(custodian, fund_state.admin_authority, ErrorCode::InvalidAccountData);
+    
+    // Attestations at most a week old within a 10% tolerance
+    require!(max_attestation_age > 0 && max_attestation_age <= 7 * 86400, ErrorCode::InvalidAmount);
+    require!(attestation_tolerance_bps <= 1_000, ErrorCode::InvalidAmount);
+    
+    fund_state.custodian = custodian;
+    fund_state.max_attestation_age = max_attestation_age;
+    fund_state.attestation_tolerance_bps = attestation_tolerance_bps;
+    
+    msg!(
+        "Custodian set to {} (attestations within {} s and {} bps)",
+        custodian, max_attestation_age, attestation_tolerance_bps
+    );
+    Ok(())
+}
+
+pub fn set_forward_pricing(ctx: Context<AdminConfig>, enabled: bool) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    fund_state.forward_pricing = enabled;
+    
+    // Orders already placed still settle at the next NAV after switching back
+    msg!("Forward pricing {}", if enabled { "enabled" } else { "disabled" });
+    Ok(())
+}
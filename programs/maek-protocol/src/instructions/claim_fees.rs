@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::FeesClaimed;
+use crate::utils::calculations::calculate_excess_liquidity;
+
+#[derive(Accounts)]
+pub struct ClaimFees<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = admin_authority,
+        has_one = usdc_vault
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    pub admin_authority: Signer<'info>,
+    
+    /// Fund's USDC vault
+    #[account(mut)]
+    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// Fee recipient's USDC token account
+    #[account(
+        mut,
+        constraint = fee_recipient_usdc_account.mint == fund_state.usdc_mint,
+        constraint = fee_recipient_usdc_account.owner == fund_state.fee_recipient @ ErrorCode::UnauthorizedAccess
+    )]
+    pub fee_recipient_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    let clock = Clock::get()?;
+    
+    require!(!fund_state.is_paused, ErrorCode::FundPaused);
+    
+    // Fees are paid in whole USDC units; sub-unit dust stays payable
+    let fees_due = fund_state.accrued_fees_payable / 100; // Convert 8 to 6 decimals
+    require!(fees_due > 0, ErrorCode::NoFeesToClaim);
+    
+    // Only cash above the target liquidity ratio can be paid out
+    let available = calculate_excess_liquidity(
+        fund_state.cash_reserves,
+        fund_state.total_assets,
+        fund_state.target_liquidity_ratio,
+    )?;
+    let amount = fees_due.min(available);
+    require!(amount > 0, ErrorCode::LiquidityRatioBelowMinimum);
+    
+    // Transfer USDC from vault to the fee recipient
+    let fund_id_bytes = fund_state.fund_id.to_le_bytes();
+    let seeds = &[b"fund_state".as_ref(), fund_id_bytes.as_ref(), &[fund_state.bump]];
+    let signer = &[&seeds[..]];
+    
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.usdc_vault.to_account_info(),
+            to: ctx.accounts.fee_recipient_usdc_account.to_account_info(),
+            authority: fund_state.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(transfer_ctx, amount)?;
+    
+    // Settle the liability (total assets are unchanged: they were already net of fees payable)
+    let settled = amount.checked_mul(100).ok_or(ErrorCode::MathOverflow)?; // Convert 6 to 8 decimals
+    fund_state.cash_reserves = fund_state.cash_reserves.checked_sub(amount).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.accrued_fees_payable = fund_state.accrued_fees_payable.checked_sub(settled).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.total_fees_paid = fund_state.total_fees_paid.checked_add(settled).ok_or(ErrorCode::MathOverflow)?;
+    
+    emit!(FeesClaimed {
+        fee_recipient: fund_state.fee_recipient,
+        amount_usdc: amount,
+        remaining_payable: fund_state.accrued_fees_payable,
+        cash_reserves: fund_state.cash_reserves,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Paid {} USDC of management fees to {}", amount, fund_state.fee_recipient);
+    
+    Ok(())
+}
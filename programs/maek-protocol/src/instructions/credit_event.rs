@@ -0,0 +1,258 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::{AssetWrittenDown, DefaultRecoveryRecorded};
+use crate::instructions::treasury::settle_treasury_proceeds;
+use crate::utils::calculations::*;
+
+#[derive(Accounts)]
+pub struct MarkAssetDefault<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = admin_authority
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fixed_income_asset", fund_state.key().as_ref(), fixed_income_asset.asset_id.as_ref()],
+        bump = fixed_income_asset.bump
+    )]
+    pub fixed_income_asset: Box<Account<'info, FixedIncomeAsset>>,
+    
+    /// Audit trail of write-downs and recoveries for the asset
+    #[account(
+        init_if_needed,
+        payer = admin_authority,
+        space = WriteDownHistory::LEN,
+        seeds = [b"write_down_history", fixed_income_asset.key().as_ref()],
+        bump
+    )]
+    pub write_down_history: Box<Account<'info, WriteDownHistory>>,
+    
+    #[account(mut)]
+    pub admin_authority: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RecordDefaultRecovery<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = admin_authority,
+        has_one = usdc_vault,
+        has_one = treasury_vault
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fixed_income_asset", fund_state.key().as_ref(), fixed_income_asset.asset_id.as_ref()],
+        bump = fixed_income_asset.bump
+    )]
+    pub fixed_income_asset: Box<Account<'info, FixedIncomeAsset>>,
+    
+    #[account(
+        mut,
+        seeds = [b"write_down_history", fixed_income_asset.key().as_ref()],
+        bump = write_down_history.bump
+    )]
+    pub write_down_history: Box<Account<'info, WriteDownHistory>>,
+    
+    #[account(
+        mut,
+        seeds = [b"issuer_exposure", fund_state.key().as_ref(), fixed_income_asset.issuer.as_ref()],
+        bump = issuer_exposure.bump
+    )]
+    pub issuer_exposure: Box<Account<'info, IssuerExposure>>,
+    
+    /// Fund's USDC vault, receiving the recovery as cash reserves
+    #[account(mut)]
+    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// Fund's treasury vault, into which the recovery is paid
+    #[account(mut)]
+    pub treasury_vault: Box<Account<'info, TokenAccount>>,
+    
+    pub admin_authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+/// Flag an asset as in default and write down its current value
+pub fn mark_asset_default(ctx: Context<MarkAssetDefault>, write_down_bps: u16) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    let asset = &mut ctx.accounts.fixed_income_asset;
+    let history = &mut ctx.accounts.write_down_history;
+    let clock = Clock::get()?;
+    
+    // Further write-downs may be applied while the asset stays in default
+    require!(
+        asset.status == AssetStatus::Active || asset.status == AssetStatus::DefaultRisk,
+        ErrorCode::AssetNotActive
+    );
+    require!(write_down_bps <= 10000, ErrorCode::InvalidWriteDown); // At most 100%
+    require!(history.events.len() < WriteDownHistory::MAX_EVENTS, ErrorCode::WriteDownHistoryFull);
+    
+    if history.asset == Pubkey::default() {
+        history.fund = fund_state.key();
+        history.asset = asset.key();
+        history.bump = ctx.bumps.write_down_history;
+    }
+    
+    // Reduce the carrying value; the loss flows straight into total assets
+    let write_down = calculate_write_down(asset.current_value, write_down_bps)?;
+    asset.current_value = asset.current_value.checked_sub(write_down).ok_or(ErrorCode::MathOverflow)?;
+    asset.status = AssetStatus::DefaultRisk;
+    
+    fund_state.fixed_income_value = fund_state.fixed_income_value.checked_sub(write_down).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.total_assets = fund_state.total_assets.checked_sub(write_down).ok_or(ErrorCode::MathOverflow)?;
+    refresh_nav(fund_state, clock.unix_timestamp)?;
+    
+    history.total_written_down = history.total_written_down.checked_add(write_down).ok_or(ErrorCode::MathOverflow)?;
+    history.events.push(CreditEvent {
+        kind: CreditEventKind::WriteDown,
+        write_down_bps,
+        amount: write_down,
+        value_after: asset.current_value,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    emit!(AssetWrittenDown {
+        asset_id: asset.key(),
+        write_down_bps,
+        write_down_amount: write_down,
+        new_value: asset.current_value,
+        nav_per_share: fund_state.nav_per_share,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Asset {} written down by {} to {}", asset.key(), write_down, asset.current_value);
+    
+    Ok(())
+}
+
+/// Book cash recovered on a defaulted asset, optionally closing out the position
+pub fn record_default_recovery(
+    ctx: Context<RecordDefaultRecovery>,
+    recovery_amount: u64,
+    close_position: bool,
+) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    let asset = &mut ctx.accounts.fixed_income_asset;
+    let history = &mut ctx.accounts.write_down_history;
+    let issuer_exposure = &mut ctx.accounts.issuer_exposure;
+    let clock = Clock::get()?;
+    
+    require!(asset.status == AssetStatus::DefaultRisk, ErrorCode::AssetNotInDefault);
+    require!(recovery_amount / 100 * 100 == recovery_amount, ErrorCode::InvalidAmount); // Settles in USDC
+    
+    // Nothing beyond the par claim can be recovered, which bounds any gain over the written-down value
+    let total_recovered = history.total_recovered.checked_add(recovery_amount).ok_or(ErrorCode::MathOverflow)?;
+    require!(total_recovered <= asset.face_value, ErrorCode::RecoveryExceedsPar);
+    
+    let events_needed = if close_position { 2 } else { 1 };
+    require!(
+        history.events.len() + events_needed <= WriteDownHistory::MAX_EVENTS,
+        ErrorCode::WriteDownHistoryFull
+    );
+    
+    // Recovered cash first reduces the written-down value; anything above it is a gain
+    let applied = std::cmp::min(recovery_amount, asset.current_value);
+    let excess = recovery_amount - applied;
+    asset.current_value -= applied;
+    
+    fund_state.fixed_income_value = fund_state.fixed_income_value.checked_sub(applied).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.total_assets = fund_state.total_assets.checked_add(excess).ok_or(ErrorCode::MathOverflow)?;
+    
+    // Recoveries are paid into the treasury vault and return to cash reserves, like maturity and sale proceeds
+    settle_treasury_proceeds(
+        fund_state,
+        &ctx.accounts.treasury_vault,
+        &ctx.accounts.usdc_vault,
+        &ctx.accounts.token_program,
+        recovery_amount / 100, // Convert 8 to 6 decimals
+    )?;
+    
+    history.total_recovered = total_recovered;
+    history.events.push(CreditEvent {
+        kind: CreditEventKind::Recovery,
+        write_down_bps: 0,
+        amount: recovery_amount,
+        value_after: asset.current_value,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    // Closing the workout writes off whatever was not recovered
+    let mut written_off = 0;
+    if close_position {
+        written_off = asset.current_value.checked_add(asset.accrued_interest).ok_or(ErrorCode::MathOverflow)?;
+        
+        fund_state.fixed_income_value = fund_state.fixed_income_value.checked_sub(written_off).ok_or(ErrorCode::MathOverflow)?;
+        fund_state.total_assets = fund_state.total_assets.checked_sub(written_off).ok_or(ErrorCode::MathOverflow)?;
+        fund_state.active_asset_count = fund_state.active_asset_count.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+        
+        // Remove the holding from the portfolio risk aggregates
+        let duration_weight = asset.modified_duration as u128 * asset.purchase_price as u128;
+        let maturity_weight = asset.purchase_price as u128 * asset.maturity_date as u128;
+        let reset_weight = asset.purchase_price as u128 * asset.interest_reset_date() as u128;
+        fund_state.weighted_duration_sum = fund_state.weighted_duration_sum.checked_sub(duration_weight).ok_or(ErrorCode::MathOverflow)?;
+        fund_state.maturity_weighted_sum = fund_state.maturity_weighted_sum.checked_sub(maturity_weight).ok_or(ErrorCode::MathOverflow)?;
+        fund_state.reset_weighted_sum = fund_state.reset_weighted_sum.checked_sub(reset_weight).ok_or(ErrorCode::MathOverflow)?;
+        fund_state.held_book_value = fund_state.held_book_value.checked_sub(asset.purchase_price).ok_or(ErrorCode::MathOverflow)?;
+        
+        // Realized loss is the cost basis less everything recovered
+        let realized_pnl = calculate_realized_pnl(history.total_recovered, asset.purchase_price, asset.accrued_interest)?;
+        fund_state.realized_pnl = fund_state.realized_pnl.checked_add(realized_pnl).ok_or(ErrorCode::MathOverflow)?;
+        
+        issuer_exposure.total_value = issuer_exposure.total_value.checked_sub(asset.purchase_price).ok_or(ErrorCode::MathOverflow)?;
+        issuer_exposure.asset_count = issuer_exposure.asset_count.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+        
+        asset.current_value = 0;
+        asset.accrued_interest = 0;
+        asset.status = AssetStatus::WrittenOff;
+        
+        history.events.push(CreditEvent {
+            kind: CreditEventKind::WriteOff,
+            write_down_bps: 0,
+            amount: written_off,
+            value_after: 0,
+            timestamp: clock.unix_timestamp,
+        });
+    }
+    
+    refresh_nav(fund_state, clock.unix_timestamp)?;
+    
+    emit!(DefaultRecoveryRecorded {
+        asset_id: asset.key(),
+        recovery_amount,
+        remaining_value: asset.current_value,
+        written_off,
+        nav_per_share: fund_state.nav_per_share,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Recovered {} on asset {}", recovery_amount, asset.key());
+    
+    Ok(())
+}
+
+/// Reprice NAV per share and portfolio risk after a change in total assets
+fn refresh_nav(fund_state: &mut FundState, now: i64) -> Result<()> {
+    refresh_portfolio_risk(fund_state, now)?;
+    
+    // total_assets (8 decimals) * 10^8 / total_shares (8 decimals) = NAV (8 decimals)
+    if fund_state.total_shares > 0 {
+        let nav = (fund_state.total_assets as u128) * 100_000_000 / (fund_state.total_shares as u128);
+        require!(nav <= u64::MAX as u128, ErrorCode::MathOverflow);
+        fund_state.nav_per_share = nav as u64;
+    }
+    
+    Ok(())
+}
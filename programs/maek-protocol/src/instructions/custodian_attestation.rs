@@ -0,0 +1,80 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::AttestationRecorded;
+use crate::utils::validation::validate_ed25519_signature;
+
+#[derive(Accounts)]
+pub struct SubmitAttestation<'info> {
+    #[account(
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    /// Latest custodian attestation for the fund
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = CustodianAttestation::LEN,
+        seeds = [b"custodian_attestation", fund_state.key().as_ref()],
+        bump
+    )]
+    pub custodian_attestation: Box<Account<'info, CustodianAttestation>>,
+    
+    /// Anyone may relay a statement; the custodian's signature is what is checked
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    /// CHECK: Instructions sysvar, used to read the preceding ed25519 verify instruction
+    #[account(address = anchor_lang::solana_program::sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+/// Record a custodian holdings statement verified by the ed25519 instruction placed just before this one
+pub fn submit_attestation(ctx: Context<SubmitAttestation>, statement: HoldingsStatement) -> Result<()> {
+    let fund_state = &ctx.accounts.fund_state;
+    let attestation = &mut ctx.accounts.custodian_attestation;
+    let clock = Clock::get()?;
+    
+    require!(fund_state.custodian != Pubkey::default(), ErrorCode::MissingAttestation);
+    require_keys_eq!(statement.fund, fund_state.key(), ErrorCode::InvalidAccountData);
+    require!(statement.statement_date <= clock.unix_timestamp, ErrorCode::InvalidAmount);
+    
+    // Statements only move forward in time, unless the custodian has been replaced
+    require!(
+        statement.statement_date > attestation.statement_date || attestation.custodian != fund_state.custodian,
+        ErrorCode::AttestationOutdated
+    );
+    
+    // The custodian's signature over the encoded statement is checked by the ed25519 precompile
+    let instructions = ctx.accounts.instructions.to_account_info();
+    let current_index = load_current_index_checked(&instructions)?;
+    require!(current_index > 0, ErrorCode::InvalidAttestationSignature);
+    let verify_instruction = load_instruction_at_checked(current_index as usize - 1, &instructions)?;
+    validate_ed25519_signature(&verify_instruction, &fund_state.custodian, &statement.try_to_vec()?)?;
+    
+    attestation.fund = fund_state.key();
+    attestation.custodian = fund_state.custodian;
+    attestation.statement_date = statement.statement_date;
+    attestation.total_par = statement.total_par;
+    attestation.positions_hash = statement.positions_hash;
+    attestation.recorded_at = clock.unix_timestamp;
+    attestation.bump = ctx.bumps.custodian_attestation;
+    
+    emit!(AttestationRecorded {
+        fund: attestation.fund,
+        custodian: attestation.custodian,
+        statement_date: attestation.statement_date,
+        total_par: attestation.total_par,
+        positions_hash: attestation.positions_hash,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Custodian attested {} par as of {}", attestation.total_par, attestation.statement_date);
+    
+    Ok(())
+}
@@ -3,13 +3,14 @@ use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer, MintTo};
 use anchor_spl::associated_token::AssociatedToken;
 use crate::state::*;
 use crate::error::ErrorCode;
+use crate::events::DepositMade;
 use crate::utils::calculations::*;
 
 #[derive(Accounts)]
 pub struct Deposit<'info> {
     #[account(
         mut,
-        seeds = [b"fund_state"],
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump
     )]
     pub fund_state: Account<'info, FundState>,
@@ -18,7 +19,7 @@ pub struct Deposit<'info> {
         init_if_needed,
         payer = user,
         space = UserFundAccount::LEN,
-        seeds = [b"user_account", user.key().as_ref()],
+        seeds = [b"user_account", fund_state.key().as_ref(), user.key().as_ref()],
         bump
     )]
     pub user_account: Account<'info, UserFundAccount>,
@@ -69,13 +70,31 @@ pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
     
     // Validate fund is not paused
     require!(!fund_state.is_paused, ErrorCode::FundPaused);
+    require!(!fund_state.forward_pricing, ErrorCode::ForwardPricingActive);
     
     // Validate deposit amount
     require!(amount >= 1_000_000, ErrorCode::DepositTooSmall); // Min $1 USDC
     require!(amount <= 1_000_000_000_000, ErrorCode::DepositTooLarge); // Max $1M USDC
     
-    // Calculate fund tokens to mint
-    let fund_tokens = calculate_fund_tokens(amount, fund_state.nav_per_share)?;
+    // Swing the price up if net inflows since the last NAV update exceed the threshold
+    let amount_scaled = amount.checked_mul(100).ok_or(ErrorCode::MathOverflow)?; // Convert to 8 decimals
+    let net_flows = fund_state.net_flows
+        .checked_add(i64::try_from(amount_scaled).map_err(|_| ErrorCode::MathOverflow)?)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let swing_factor_bps = calculate_swing_factor(
+        net_flows.max(0) as u64,
+        fund_state.total_assets,
+        fund_state.inflow_swing_threshold_bps,
+        fund_state.inflow_swing_factor_bps,
+    );
+    
+    // Calculate fund tokens to mint; the levy is the deposit not matched by token value at NAV
+    let fund_tokens = calculate_fund_tokens(amount, fund_state.nav_per_share, swing_factor_bps)?;
+    let swing_levy = if swing_factor_bps > 0 {
+        amount.saturating_sub(calculate_withdrawal_amount(fund_tokens, fund_state.nav_per_share, 0)?)
+    } else {
+        0
+    };
     
     // Transfer USDC from user to vault
     let transfer_ctx = CpiContext::new(
@@ -89,7 +108,8 @@ pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
     token::transfer(transfer_ctx, amount)?;
     
     // Mint fund tokens to user
-    let seeds = &[b"fund_state".as_ref(), &[fund_state.bump]];
+    let fund_id_bytes = fund_state.fund_id.to_le_bytes();
+    let seeds = &[b"fund_state".as_ref(), fund_id_bytes.as_ref(), &[fund_state.bump]];
     let signer = &[&seeds[..]];
     
     let mint_ctx = CpiContext::new_with_signer(
@@ -104,32 +124,33 @@ pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
     token::mint_to(mint_ctx, fund_tokens)?;
     
     // Update fund state
-    fund_state.total_assets = fund_state.total_assets.checked_add(amount * 100).ok_or(ErrorCode::MathOverflow)?; // Convert 6 to 8 decimals
+    fund_state.total_assets = fund_state.total_assets.checked_add(amount_scaled).ok_or(ErrorCode::MathOverflow)?;
     fund_state.total_shares = fund_state.total_shares.checked_add(fund_tokens).ok_or(ErrorCode::MathOverflow)?;
     fund_state.cash_reserves = fund_state.cash_reserves.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.net_flows = net_flows;
+    fund_state.total_swing_levies = fund_state.total_swing_levies.checked_add(swing_levy.checked_mul(100).ok_or(ErrorCode::MathOverflow)?).ok_or(ErrorCode::MathOverflow)?; // Retained by the fund
     
     // Initialize user account if needed
     if user_account.owner == Pubkey::default() {
         user_account.owner = ctx.accounts.user.key();
+        user_account.fund = fund_state.key();
         user_account.created_at = clock.unix_timestamp;
         user_account.bump = ctx.bumps.user_account;
         fund_state.total_depositors = fund_state.total_depositors.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
     }
     
     // Update user account
-    user_account.fund_tokens = user_account.fund_tokens.checked_add(fund_tokens).ok_or(ErrorCode::MathOverflow)?;
-    user_account.total_deposited = user_account.total_deposited.checked_add(amount * 100).ok_or(ErrorCode::MathOverflow)?; // Convert to 8 decimals
-    user_account.last_deposit_time = clock.unix_timestamp;
-    user_account.last_deposit_nav = fund_state.nav_per_share;
-    user_account.deposit_count = user_account.deposit_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
-    
-    // Update average cost basis
-    let total_cost = user_account.avg_cost_basis
-        .checked_mul(user_account.fund_tokens.checked_sub(fund_tokens).ok_or(ErrorCode::MathOverflow)?)
-        .ok_or(ErrorCode::MathOverflow)?
-        .checked_add(fund_state.nav_per_share.checked_mul(fund_tokens).ok_or(ErrorCode::MathOverflow)?)
-        .ok_or(ErrorCode::MathOverflow)?;
-    user_account.avg_cost_basis = total_cost.checked_div(user_account.fund_tokens).ok_or(ErrorCode::MathOverflow)?;
+    user_account.record_deposit(fund_tokens, amount, fund_state.nav_per_share, clock.unix_timestamp)?;
+    
+    emit!(DepositMade {
+        user: ctx.accounts.user.key(),
+        amount_usdc: amount,
+        fund_tokens_minted: fund_tokens,
+        nav_per_share: fund_state.nav_per_share,
+        swing_factor_bps,
+        swing_levy,
+        timestamp: clock.unix_timestamp,
+    });
     
     msg!("Deposit successful: {} USDC for {} fund tokens", amount, fund_tokens);
     
@@ -0,0 +1,475 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::associated_token::{get_associated_token_address, AssociatedToken};
+use anchor_spl::token::{self, Burn, Mint, MintTo, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::{OrderPlaced, OrderSettled};
+use crate::utils::calculations::*;
+
+#[derive(Accounts)]
+pub struct PlaceSubscriptionOrder<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = UserFundAccount::LEN,
+        seeds = [b"user_account", fund_state.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_account: Box<Account<'info, UserFundAccount>>,
+    
+    /// Order settled at the next NAV
+    #[account(
+        init,
+        payer = user,
+        space = PendingOrder::LEN,
+        seeds = [b"pending_order", fund_state.key().as_ref(), fund_state.order_sequence.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pending_order: Box<Account<'info, PendingOrder>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// User's USDC token account
+    #[account(
+        mut,
+        constraint = user_usdc_account.mint == fund_state.usdc_mint,
+        constraint = user_usdc_account.owner == user.key()
+    )]
+    pub user_usdc_account: Box<Account<'info, TokenAccount>>,
+    
+    /// User's fund token account, credited at settlement
+    #[account(
+        init_if_needed,
+        payer = user,
+        associated_token::mint = fund_token_mint,
+        associated_token::authority = user
+    )]
+    pub user_fund_token_account: Box<Account<'info, TokenAccount>>,
+    
+    /// Fund's USDC vault
+    #[account(
+        mut,
+        address = fund_state.usdc_vault
+    )]
+    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// Fund token mint
+    #[account(address = fund_state.fund_token_mint)]
+    pub fund_token_mint: Box<Account<'info, Mint>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct PlaceRedemptionOrder<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_account", fund_state.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Box<Account<'info, UserFundAccount>>,
+    
+    /// Order settled at the next NAV
+    #[account(
+        init,
+        payer = user,
+        space = PendingOrder::LEN,
+        seeds = [b"pending_order", fund_state.key().as_ref(), fund_state.order_sequence.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub pending_order: Box<Account<'info, PendingOrder>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// User's fund token account
+    #[account(
+        mut,
+        token::mint = fund_state.fund_token_mint,
+        token::authority = user
+    )]
+    pub user_fund_token_account: Box<Account<'info, TokenAccount>>,
+    
+    /// Fund tokens escrowed by unsettled redemption orders
+    #[account(
+        init_if_needed,
+        payer = user,
+        token::mint = fund_token_mint,
+        token::authority = fund_state,
+        seeds = [b"order_escrow", fund_state.key().as_ref()],
+        bump
+    )]
+    pub order_escrow: Box<Account<'info, TokenAccount>>,
+    
+    /// Fund token mint
+    #[account(address = fund_state.fund_token_mint)]
+    pub fund_token_mint: Box<Account<'info, Mint>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SettleOrders<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = usdc_vault,
+        has_one = fund_token_mint
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    /// Fund's USDC vault
+    #[account(mut)]
+    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// Fund token mint
+    #[account(mut)]
+    pub fund_token_mint: Box<Account<'info, Mint>>,
+    
+    /// Fund tokens escrowed by unsettled redemption orders
+    #[account(
+        mut,
+        seeds = [b"order_escrow", fund_state.key().as_ref()],
+        bump
+    )]
+    pub order_escrow: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    // remaining_accounts: for each order, the PendingOrder (writable), the owner's token account
+    // (fund token ATA for subscriptions, USDC account for redemptions), the owner's
+    // UserFundAccount and the owner, all writable
+}
+
+pub fn place_subscription_order(ctx: Context<PlaceSubscriptionOrder>, amount: u64) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    let user_account = &mut ctx.accounts.user_account;
+    let order = &mut ctx.accounts.pending_order;
+    let clock = Clock::get()?;
+    
+    require!(!fund_state.is_paused, ErrorCode::FundPaused);
+    require!(fund_state.forward_pricing, ErrorCode::ForwardPricingInactive);
+    
+    // Same limits as a direct deposit
+    require!(amount >= 1_000_000, ErrorCode::DepositTooSmall); // Min $1 USDC
+    require!(amount <= 1_000_000_000_000, ErrorCode::DepositTooLarge); // Max $1M USDC
+    
+    // USDC moves into the vault now but only joins the fund at settlement
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_usdc_account.to_account_info(),
+            to: ctx.accounts.usdc_vault.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, amount)?;
+    
+    // Initialize user account if needed
+    if user_account.owner == Pubkey::default() {
+        user_account.owner = ctx.accounts.user.key();
+        user_account.fund = fund_state.key();
+        user_account.created_at = clock.unix_timestamp;
+        user_account.bump = ctx.bumps.user_account;
+        fund_state.total_depositors = fund_state.total_depositors.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    }
+    
+    fund_state.pending_subscriptions = fund_state.pending_subscriptions.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    record_order(fund_state, order, ctx.accounts.user.key(), OrderKind::Subscription, amount, ctx.bumps.pending_order)
+}
+
+pub fn place_redemption_order(ctx: Context<PlaceRedemptionOrder>, fund_tokens: u64) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    let user_account = &mut ctx.accounts.user_account;
+    let order = &mut ctx.accounts.pending_order;
+    
+    require!(!fund_state.is_paused, ErrorCode::FundPaused);
+    require!(fund_state.forward_pricing, ErrorCode::ForwardPricingInactive);
+    require!(fund_tokens > 0, ErrorCode::WithdrawAmountZero);
+    require!(user_account.fund_tokens >= fund_tokens, ErrorCode::InsufficientFundTokens);
+    
+    // Escrow the tokens until they are burned at settlement
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_fund_token_account.to_account_info(),
+            to: ctx.accounts.order_escrow.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, fund_tokens)?;
+    
+    user_account.fund_tokens = user_account.fund_tokens.checked_sub(fund_tokens).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.pending_redemption_tokens = fund_state.pending_redemption_tokens
+        .checked_add(fund_tokens)
+        .ok_or(ErrorCode::MathOverflow)?;
+    record_order(fund_state, order, ctx.accounts.user.key(), OrderKind::Redemption, fund_tokens, ctx.bumps.pending_order)
+}
+
+/// Fill in a new order and advance the fund's order sequence
+fn record_order(
+    fund_state: &mut Account<FundState>,
+    order: &mut Account<PendingOrder>,
+    owner: Pubkey,
+    kind: OrderKind,
+    amount: u64,
+    bump: u8,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    order.fund = fund_state.key();
+    order.owner = owner;
+    order.kind = kind;
+    order.amount = amount;
+    order.sequence = fund_state.order_sequence;
+    order.submitted_at = clock.unix_timestamp;
+    order.nav_sequence = fund_state.nav_sequence;
+    order.bump = bump;
+    fund_state.order_sequence = fund_state.order_sequence.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.orders_awaiting_nav = fund_state.orders_awaiting_nav.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    
+    emit!(OrderPlaced {
+        order: order.key(),
+        owner,
+        kind: kind as u8,
+        amount,
+        sequence: order.sequence,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Placed order {} for {}", order.sequence, amount);
+    
+    Ok(())
+}
+
+/// Settle orders at the NAV struck after they were placed, carrying redemptions the cash or gate cannot cover to the next NAV
+pub fn settle_orders<'info>(ctx: Context<'_, '_, '_, 'info, SettleOrders<'info>>) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    let clock = Clock::get()?;
+    
+    require!(!fund_state.is_paused, ErrorCode::FundPaused);
+    let order_count = ctx.remaining_accounts.len() / 4;
+    require!(order_count * 4 == ctx.remaining_accounts.len(), ErrorCode::InvalidAccountData);
+    
+    let fund_key = fund_state.key();
+    let fund_id_bytes = fund_state.fund_id.to_le_bytes();
+    let bump = fund_state.bump;
+    let seeds = &[b"fund_state".as_ref(), fund_id_bytes.as_ref(), &[bump]];
+    let signer = &[&seeds[..]];
+    let nav_per_share = fund_state.nav_per_share;
+    
+    for accounts in ctx.remaining_accounts.chunks(4) {
+        let (order_info, token_account_info, user_account_info, owner_info) =
+            (&accounts[0], &accounts[1], &accounts[2], &accounts[3]);
+        
+        // Load the order; it strikes at the first NAV published after it was placed
+        require_keys_eq!(*order_info.owner, crate::ID, ErrorCode::InvalidAccountData);
+        require!(order_info.is_writable, ErrorCode::InvalidAccountData);
+        let mut order = PendingOrder::try_deserialize(&mut &order_info.try_borrow_data()?[..])?;
+        require_keys_eq!(order.fund, fund_key, ErrorCode::InvalidAccountData);
+        require!(
+            order.nav_sequence.checked_add(1).ok_or(ErrorCode::MathOverflow)? == fund_state.nav_sequence,
+            ErrorCode::OrderAwaitingNAV
+        );
+        fund_state.orders_awaiting_settlement = fund_state.orders_awaiting_settlement
+            .checked_sub(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require_keys_eq!(owner_info.key(), order.owner, ErrorCode::InvalidAccountData);
+        
+        // Load the owner's fund account
+        require_keys_eq!(*user_account_info.owner, crate::ID, ErrorCode::InvalidAccountData);
+        let (user_account_key, _) = Pubkey::find_program_address(
+            &[b"user_account", fund_key.as_ref(), order.owner.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(user_account_info.key(), user_account_key, ErrorCode::InvalidAccountData);
+        require!(user_account_info.is_writable && owner_info.is_writable, ErrorCode::InvalidAccountData);
+        let mut user_account = UserFundAccount::try_deserialize(&mut &user_account_info.try_borrow_data()?[..])?;
+        
+        let (fund_tokens, amount_usdc, swing_factor_bps, swing_levy) = match order.kind {
+            OrderKind::Subscription => {
+                // Mint to the owner's fund token account at the new NAV
+                let fund_token_account = get_associated_token_address(&order.owner, &fund_state.fund_token_mint);
+                require_keys_eq!(token_account_info.key(), fund_token_account, ErrorCode::InvalidAccountData);
+                
+                // Swing the price up if net inflows since the NAV update exceed the threshold, as for deposits
+                let amount_scaled = order.amount.checked_mul(100).ok_or(ErrorCode::MathOverflow)?; // Convert to 8 decimals
+                let net_flows = fund_state.net_flows
+                    .checked_add(i64::try_from(amount_scaled).map_err(|_| ErrorCode::MathOverflow)?)
+                    .ok_or(ErrorCode::MathOverflow)?;
+                let swing_factor_bps = calculate_swing_factor(
+                    net_flows.max(0) as u64,
+                    fund_state.total_assets,
+                    fund_state.inflow_swing_threshold_bps,
+                    fund_state.inflow_swing_factor_bps,
+                );
+                let fund_tokens = calculate_fund_tokens(order.amount, nav_per_share, swing_factor_bps)?;
+                let swing_levy = if swing_factor_bps > 0 {
+                    order.amount.saturating_sub(calculate_withdrawal_amount(fund_tokens, nav_per_share, 0)?)
+                } else {
+                    0
+                };
+                
+                let mint_ctx = CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.fund_token_mint.to_account_info(),
+                        to: token_account_info.clone(),
+                        authority: fund_state.to_account_info(),
+                    },
+                    signer,
+                );
+                token::mint_to(mint_ctx, fund_tokens)?;
+                
+                // The USDC already in the vault joins the fund
+                fund_state.pending_subscriptions = fund_state.pending_subscriptions
+                    .checked_sub(order.amount)
+                    .ok_or(ErrorCode::MathOverflow)?;
+                fund_state.total_assets = fund_state.total_assets.checked_add(amount_scaled).ok_or(ErrorCode::MathOverflow)?;
+                fund_state.total_shares = fund_state.total_shares.checked_add(fund_tokens).ok_or(ErrorCode::MathOverflow)?;
+                fund_state.cash_reserves = fund_state.cash_reserves.checked_add(order.amount).ok_or(ErrorCode::MathOverflow)?;
+                fund_state.net_flows = net_flows;
+                fund_state.total_swing_levies = fund_state.total_swing_levies
+                    .checked_add(swing_levy.checked_mul(100).ok_or(ErrorCode::MathOverflow)?) // Retained by the fund
+                    .ok_or(ErrorCode::MathOverflow)?;
+                
+                user_account.record_deposit(fund_tokens, order.amount, nav_per_share, clock.unix_timestamp)?;
+                (fund_tokens, order.amount, swing_factor_bps, swing_levy)
+            }
+            OrderKind::Redemption => {
+                // Pay into the owner's USDC account at the new NAV
+                require_keys_eq!(*token_account_info.owner, token::ID, ErrorCode::InvalidAccountData);
+                let owner_usdc = TokenAccount::try_deserialize(&mut &token_account_info.try_borrow_data()?[..])?;
+                require_keys_eq!(owner_usdc.mint, fund_state.usdc_mint, ErrorCode::InvalidAccountData);
+                require_keys_eq!(owner_usdc.owner, order.owner, ErrorCode::InvalidAccountData);
+                
+                // Partially fill the order if cash or the redemption gate runs short
+                let available = fund_state.cash_reserves.min(fund_state.remaining_redemption_capacity()? / 100); // Convert 8 to 6 decimals
+                let mut tokens_paid = order.amount;
+                let mut nav_value = calculate_withdrawal_amount(tokens_paid, nav_per_share, 0)?;
+                if nav_value > available {
+                    tokens_paid = calculate_fund_tokens(available, nav_per_share, 0)?.min(order.amount);
+                    nav_value = calculate_withdrawal_amount(tokens_paid, nav_per_share, 0)?;
+                }
+                
+                // Swing the price down if net outflows since the NAV update exceed the threshold, as for withdrawals
+                let nav_value_scaled = nav_value.checked_mul(100).ok_or(ErrorCode::MathOverflow)?; // Convert to 8 decimals
+                let net_flows = fund_state.net_flows
+                    .checked_sub(i64::try_from(nav_value_scaled).map_err(|_| ErrorCode::MathOverflow)?)
+                    .ok_or(ErrorCode::MathOverflow)?;
+                let swing_factor_bps = calculate_swing_factor(
+                    net_flows.min(0).unsigned_abs(),
+                    fund_state.total_assets,
+                    fund_state.outflow_swing_threshold_bps,
+                    fund_state.outflow_swing_factor_bps,
+                );
+                let amount_usdc = calculate_withdrawal_amount(tokens_paid, nav_per_share, swing_factor_bps)?;
+                let swing_levy = nav_value.checked_sub(amount_usdc).ok_or(ErrorCode::MathOverflow)?;
+                
+                if tokens_paid > 0 {
+                    let burn_ctx = CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Burn {
+                            mint: ctx.accounts.fund_token_mint.to_account_info(),
+                            from: ctx.accounts.order_escrow.to_account_info(),
+                            authority: fund_state.to_account_info(),
+                        },
+                        signer,
+                    );
+                    token::burn(burn_ctx, tokens_paid)?;
+                    
+                    let transfer_ctx = CpiContext::new_with_signer(
+                        ctx.accounts.token_program.to_account_info(),
+                        Transfer {
+                            from: ctx.accounts.usdc_vault.to_account_info(),
+                            to: token_account_info.clone(),
+                            authority: fund_state.to_account_info(),
+                        },
+                        signer,
+                    );
+                    token::transfer(transfer_ctx, amount_usdc)?;
+                    
+                    fund_state.pending_redemption_tokens = fund_state.pending_redemption_tokens
+                        .checked_sub(tokens_paid)
+                        .ok_or(ErrorCode::MathOverflow)?;
+                    let amount_usdc_scaled = amount_usdc.checked_mul(100).ok_or(ErrorCode::MathOverflow)?; // Convert to 8 decimals
+                    fund_state.total_assets = fund_state.total_assets.checked_sub(amount_usdc_scaled).ok_or(ErrorCode::MathOverflow)?;
+                    fund_state.total_shares = fund_state.total_shares.checked_sub(tokens_paid).ok_or(ErrorCode::MathOverflow)?;
+                    fund_state.cash_reserves = fund_state.cash_reserves.checked_sub(amount_usdc).ok_or(ErrorCode::MathOverflow)?;
+                    fund_state.net_flows = net_flows;
+                    fund_state.period_redemptions = fund_state.period_redemptions
+                        .checked_add(nav_value_scaled) // The gate counts redemptions at NAV
+                        .ok_or(ErrorCode::MathOverflow)?;
+                    fund_state.total_swing_levies = fund_state.total_swing_levies
+                        .checked_add(swing_levy.checked_mul(100).ok_or(ErrorCode::MathOverflow)?) // Retained by the fund
+                        .ok_or(ErrorCode::MathOverflow)?;
+                    
+                    user_account.total_withdrawn = user_account.total_withdrawn
+                        .checked_add(amount_usdc_scaled)
+                        .ok_or(ErrorCode::MathOverflow)?;
+                    user_account.last_withdrawal_time = clock.unix_timestamp;
+                    user_account.withdrawal_count = user_account.withdrawal_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+                }
+                (tokens_paid, amount_usdc, swing_factor_bps, swing_levy)
+            }
+        };
+        user_account.try_serialize(&mut &mut user_account_info.try_borrow_mut_data()?[..])?;
+        let unfilled = match order.kind {
+            OrderKind::Subscription => 0,
+            OrderKind::Redemption => order.amount.checked_sub(fund_tokens).ok_or(ErrorCode::MathOverflow)?,
+        };
+        
+        emit!(OrderSettled {
+            order: order_info.key(),
+            owner: order.owner,
+            kind: order.kind as u8,
+            fund_tokens,
+            amount_usdc,
+            nav_per_share,
+            swing_factor_bps,
+            swing_levy,
+            remaining_amount: unfilled,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        // An unfilled remainder keeps its escrow and strikes at the next NAV
+        if unfilled > 0 {
+            order.amount = unfilled;
+            order.nav_sequence = fund_state.nav_sequence;
+            order.try_serialize(&mut &mut order_info.try_borrow_mut_data()?[..])?;
+            fund_state.orders_awaiting_nav = fund_state.orders_awaiting_nav.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+            continue;
+        }
+        
+        // Close the order and return its rent to the owner
+        let rent = order_info.lamports();
+        **owner_info.try_borrow_mut_lamports()? = owner_info.lamports().checked_add(rent).ok_or(ErrorCode::MathOverflow)?;
+        **order_info.try_borrow_mut_lamports()? = 0;
+        order_info.assign(&system_program::ID);
+        order_info.realloc(0, false)?;
+    }
+    
+    msg!("Settled {} orders at NAV {}", order_count, nav_per_share);
+    
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+
+#[derive(Accounts)]
+pub struct FundStatus<'info> {
+    #[account(
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+}
+
+/// Value that can still be redeemed this NAV period under the redemption gate (8 decimals)
+/// Read-only; clients simulate the instruction and read the return data
+pub fn get_redemption_capacity(ctx: Context<FundStatus>) -> Result<u64> {
+    let fund_state = &ctx.accounts.fund_state;
+    let capacity = fund_state.remaining_redemption_capacity()?;
+    
+    msg!(
+        "Redemption capacity: {} of {} bps gate, {} redeemed this period",
+        capacity, fund_state.redemption_gate_bps, fund_state.period_redemptions
+    );
+    
+    Ok(capacity)
+}
+
+/// Cash position against the target liquidity ratio and the action needed to restore it
+/// Read-only; clients simulate the instruction and read the return data
+pub fn get_liquidity_status(ctx: Context<FundStatus>) -> Result<LiquidityStatus> {
+    let status = ctx.accounts.fund_state.liquidity_status()?;
+    
+    msg!("Liquidity at {}% against {}% target", status.liquidity_ratio, status.target_liquidity_ratio);
+    
+    Ok(status)
+}
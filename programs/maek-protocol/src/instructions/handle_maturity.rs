@@ -1,34 +1,113 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
 use crate::state::*;
 use crate::error::ErrorCode;
+use crate::events::AssetMatured;
+use crate::instructions::treasury::settle_treasury_proceeds;
+use crate::utils::calculations::*;
 
 #[derive(Accounts)]
 pub struct HandleMaturity<'info> {
     #[account(
         mut,
-        seeds = [b"fund_state"],
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
-        has_one = admin_authority
+        has_one = admin_authority,
+        has_one = usdc_vault,
+        has_one = treasury_vault
     )]
-    pub fund_state: Account<'info, FundState>,
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fixed_income_asset", fund_state.key().as_ref(), fixed_income_asset.asset_id.as_ref()],
+        bump = fixed_income_asset.bump
+    )]
+    pub fixed_income_asset: Box<Account<'info, FixedIncomeAsset>>,
+    
+    #[account(
+        mut,
+        seeds = [b"issuer_exposure", fund_state.key().as_ref(), fixed_income_asset.issuer.as_ref()],
+        bump = issuer_exposure.bump
+    )]
+    pub issuer_exposure: Box<Account<'info, IssuerExposure>>,
+    
+    /// Fund's USDC vault, receiving the proceeds as cash reserves
+    #[account(mut)]
+    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// Fund's treasury vault, into which the custodian pays the proceeds
+    #[account(mut)]
+    pub treasury_vault: Box<Account<'info, TokenAccount>>,
     
     pub admin_authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
 }
 
-pub fn handle_asset_maturity(
-    ctx: Context<HandleMaturity>,
-    asset_id: Pubkey,
-) -> Result<()> {
+pub fn handle_asset_maturity(ctx: Context<HandleMaturity>) -> Result<()> {
     let fund_state = &mut ctx.accounts.fund_state;
+    let asset = &mut ctx.accounts.fixed_income_asset;
+    let issuer_exposure = &mut ctx.accounts.issuer_exposure;
+    let clock = Clock::get()?;
     
-    // This is a simplified implementation
-    // In a real implementation, you would:
-    // 1. Look up the asset details
-    // 2. Calculate maturity proceeds
-    // 3. Update cash reserves
-    // 4. Remove asset from portfolio
+    // Validate the asset is held and has matured
+    require!(asset.status == AssetStatus::Active, ErrorCode::AssetNotActive);
+    require!(clock.unix_timestamp >= asset.maturity_date, ErrorCode::AssetNotMatured);
     
-    msg!("Handling maturity for asset: {}", asset_id);
+    // Calculate maturity proceeds, settled in USDC (6 decimals)
+    let proceeds = calculate_maturity_proceeds(asset.asset_type, asset.face_value, asset.accrued_interest)?;
+    let proceeds_usdc = proceeds / 100; // Convert 8 to 6 decimals
+    let settled_proceeds = proceeds_usdc * 100;
+    
+    // Carrying value of the holding on the fund's books
+    let carrying_value = asset.current_value.checked_add(asset.accrued_interest).ok_or(ErrorCode::MathOverflow)?;
+    
+    // The holding leaves the books; any gain or loss at redemption is recognized now
+    fund_state.fixed_income_value = fund_state.fixed_income_value.checked_sub(carrying_value).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.total_assets = fund_state.total_assets
+        .checked_sub(carrying_value)
+        .and_then(|total| total.checked_add(settled_proceeds))
+        .ok_or(ErrorCode::MathOverflow)?;
+    fund_state.active_asset_count = fund_state.active_asset_count.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+    
+    // Proceeds paid by the custodian into the treasury vault return to cash reserves
+    settle_treasury_proceeds(
+        fund_state,
+        &ctx.accounts.treasury_vault,
+        &ctx.accounts.usdc_vault,
+        &ctx.accounts.token_program,
+        proceeds_usdc,
+    )?;
+    
+    // Remove the holding from the portfolio risk aggregates
+    let duration_weight = asset.modified_duration as u128 * asset.purchase_price as u128;
+    let maturity_weight = asset.purchase_price as u128 * asset.maturity_date as u128;
+    let reset_weight = asset.purchase_price as u128 * asset.interest_reset_date() as u128;
+    fund_state.weighted_duration_sum = fund_state.weighted_duration_sum.checked_sub(duration_weight).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.maturity_weighted_sum = fund_state.maturity_weighted_sum.checked_sub(maturity_weight).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.reset_weighted_sum = fund_state.reset_weighted_sum.checked_sub(reset_weight).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.held_book_value = fund_state.held_book_value.checked_sub(asset.purchase_price).ok_or(ErrorCode::MathOverflow)?;
+    refresh_portfolio_risk(fund_state, clock.unix_timestamp)?;
+    
+    // Release issuer exposure taken at purchase
+    issuer_exposure.total_value = issuer_exposure.total_value.checked_sub(asset.purchase_price).ok_or(ErrorCode::MathOverflow)?;
+    issuer_exposure.asset_count = issuer_exposure.asset_count.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+    
+    // Mark the asset as matured
+    asset.status = AssetStatus::Matured;
+    asset.days_to_maturity = 0;
+    
+    let interest_earned = settled_proceeds.saturating_sub(asset.purchase_price);
+    
+    emit!(AssetMatured {
+        asset_id: asset.key(),
+        maturity_proceeds: settled_proceeds,
+        interest_earned,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Asset {} matured for {} USDC", asset.key(), proceeds_usdc);
     
     Ok(())
-} 
\ No newline at end of file
+}
@@ -2,23 +2,36 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{Mint, Token, TokenAccount};
 use crate::state::*;
 use crate::error::ErrorCode;
+use crate::events::FundInitialized;
 
 #[derive(Accounts)]
 pub struct InitializeFund<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_registry"],
+        bump = fund_registry.bump,
+        has_one = authority @ ErrorCode::UnauthorizedAccess
+    )]
+    pub fund_registry: Box<Account<'info, FundRegistry>>,
+    
     #[account(
         init,
         payer = admin,
         space = FundState::LEN,
-        seeds = [b"fund_state"],
+        seeds = [b"fund_state", fund_registry.fund_count.to_le_bytes().as_ref()],
         bump
     )]
-    pub fund_state: Account<'info, FundState>,
+    pub fund_state: Box<Account<'info, FundState>>,
     
+    /// Registry authority approving the new fund
+    pub authority: Signer<'info>,
+    
+    /// Administrative authority of the new fund
     #[account(mut)]
     pub admin: Signer<'info>,
     
     /// USDC mint (6 decimals)
-    pub usdc_mint: Account<'info, Mint>,
+    pub usdc_mint: Box<Account<'info, Mint>>,
     
     /// Fund token mint (8 decimals)
     #[account(
@@ -26,10 +39,10 @@ pub struct InitializeFund<'info> {
         payer = admin,
         mint::decimals = 8,
         mint::authority = fund_state,
-        seeds = [b"fund_token_mint"],
+        seeds = [b"fund_token_mint", fund_state.key().as_ref()],
         bump
     )]
-    pub fund_token_mint: Account<'info, Mint>,
+    pub fund_token_mint: Box<Account<'info, Mint>>,
     
     /// USDC vault for deposits/withdrawals
     #[account(
@@ -37,10 +50,10 @@ pub struct InitializeFund<'info> {
         payer = admin,
         token::mint = usdc_mint,
         token::authority = fund_state,
-        seeds = [b"usdc_vault"],
+        seeds = [b"usdc_vault", fund_state.key().as_ref()],
         bump
     )]
-    pub usdc_vault: Account<'info, TokenAccount>,
+    pub usdc_vault: Box<Account<'info, TokenAccount>>,
     
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
@@ -51,28 +64,46 @@ pub fn initialize_fund(
     management_fee_bps: u16,
     target_liquidity_ratio: u8,
 ) -> Result<()> {
+    let fund_registry = &mut ctx.accounts.fund_registry;
     let fund_state = &mut ctx.accounts.fund_state;
     let clock = Clock::get()?;
     
     // Validate parameters
     require!(management_fee_bps <= 100, ErrorCode::FeeTooHigh); // Max 1% annually
-    require!(target_liquidity_ratio >= 10 && target_liquidity_ratio <= 50, ErrorCode::InvalidLiquidityRatio);
+    require!((10..=50).contains(&target_liquidity_ratio), ErrorCode::InvalidLiquidityRatio);
     
     // Initialize fund state
+    fund_state.fund_id = fund_registry.fund_count;
     fund_state.admin_authority = ctx.accounts.admin.key();
     fund_state.fund_token_mint = ctx.accounts.fund_token_mint.key();
     fund_state.usdc_mint = ctx.accounts.usdc_mint.key();
     fund_state.usdc_vault = ctx.accounts.usdc_vault.key();
-    fund_state.treasury_vault = Pubkey::default(); // To be set later
+    fund_state.treasury_vault = Pubkey::default(); // Created by initialize_treasury_vault
     fund_state.management_fee_bps = management_fee_bps;
+    fund_state.fee_recipient = ctx.accounts.admin.key(); // Until reassigned by the admin
     fund_state.target_liquidity_ratio = target_liquidity_ratio;
+    fund_state.max_portfolio_duration = DEFAULT_MAX_PORTFOLIO_DURATION;
+    fund_state.max_wam_days = DEFAULT_MAX_WAM_DAYS;
+    fund_state.max_wal_days = DEFAULT_MAX_WAL_DAYS;
+    fund_state.price_deviation_limits_bps = default_price_deviation_limits();
     fund_state.inception_date = clock.unix_timestamp;
     fund_state.last_nav_update = clock.unix_timestamp;
     fund_state.nav_per_share = 100_000_000; // $1.00 initial NAV
+    fund_state.high_water_mark = 100_000_000;
     fund_state.bump = ctx.bumps.fund_state;
     
-    msg!("MAEK Fund initialized with management fee: {} bps, target liquidity: {}%", 
-         management_fee_bps, target_liquidity_ratio);
+    // Reserve the next fund id
+    fund_registry.fund_count = fund_registry.fund_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    
+    emit!(FundInitialized {
+        fund_state: fund_state.key(),
+        fund_id: fund_state.fund_id,
+        admin: fund_state.admin_authority,
+        inception_date: fund_state.inception_date,
+    });
+    
+    msg!("MAEK Fund {} initialized with management fee: {} bps, target liquidity: {}%", 
+         fund_state.fund_id, management_fee_bps, target_liquidity_ratio);
     
     Ok(())
 } 
\ No newline at end of file
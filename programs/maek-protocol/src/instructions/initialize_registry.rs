@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use crate::state::*;
+use crate::error::ErrorCode;
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = FundRegistry::LEN,
+        seeds = [b"fund_registry"],
+        bump
+    )]
+    pub fund_registry: Account<'info, FundRegistry>,
+    
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    
+    /// Program data account; only the program's upgrade authority may create the registry
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(authority.key()) @ ErrorCode::UnauthorizedAccess
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+pub fn initialize_registry(ctx: Context<InitializeRegistry>) -> Result<()> {
+    let fund_registry = &mut ctx.accounts.fund_registry;
+    
+    fund_registry.authority = ctx.accounts.authority.key();
+    fund_registry.fund_count = 0;
+    fund_registry.bump = ctx.bumps.fund_registry;
+    
+    msg!("MAEK fund registry initialized with authority: {}", fund_registry.authority);
+    
+    Ok(())
+}
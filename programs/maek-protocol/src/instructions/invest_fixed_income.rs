@@ -1,53 +1,163 @@
 use anchor_lang::prelude::*;
+use anchor_lang::system_program::{self, Allocate, Assign, CreateAccount};
+use anchor_spl::token::{self, Token, TokenAccount};
 use crate::state::*;
 use crate::error::ErrorCode;
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct AssetPurchase {
-    pub asset_type: u8, // 1=Treasury Bill, 2=Corporate Bond, etc.
-    pub amount: u64,
-    pub maturity_date: i64,
-    pub yield_rate: u16, // Basis points
-}
+use crate::instructions::register_asset::record_purchase;
 
 #[derive(Accounts)]
 pub struct InvestFixedIncome<'info> {
     #[account(
         mut,
-        seeds = [b"fund_state"],
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
-        has_one = admin_authority
+        has_one = admin_authority,
+        has_one = treasury_vault
     )]
-    pub fund_state: Account<'info, FundState>,
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    /// Fund's treasury vault, paying for the purchases
+    #[account(mut)]
+    pub treasury_vault: Box<Account<'info, TokenAccount>>,
     
+    /// Custodian USDC account the purchases settle into
+    #[account(
+        mut,
+        address = fund_state.settlement_account @ ErrorCode::InvalidAccountData,
+        constraint = settlement_account.mint == fund_state.usdc_mint @ ErrorCode::InvalidAccountData
+    )]
+    pub settlement_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
     pub admin_authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    
+    // remaining_accounts: for each purchase, in order, its FixedIncomeAsset PDA (writable, not yet
+    // created) and its issuer's IssuerExposure PDA (writable, created on the issuer's first purchase)
 }
 
-pub fn invest_in_fixed_income(
-    ctx: Context<InvestFixedIncome>,
+/// Register a batch of purchases, creating the same holding and issuer exposure accounts as register_asset
+pub fn invest_in_fixed_income<'info>(
+    ctx: Context<'_, '_, '_, 'info, InvestFixedIncome<'info>>,
     assets: Vec<AssetPurchase>,
 ) -> Result<()> {
+    let payer = ctx.accounts.admin_authority.to_account_info();
+    let system_program = ctx.accounts.system_program.to_account_info();
+    let fund_key = ctx.accounts.fund_state.key();
     let fund_state = &mut ctx.accounts.fund_state;
     
-    let mut total_investment = 0u64;
+    require!(!fund_state.is_paused, ErrorCode::FundPaused);
+    require!(ctx.remaining_accounts.len() == assets.len() * 2, ErrorCode::InvalidAccountData);
     
-    for asset in assets {
-        // Validate asset parameters
-        require!(asset.amount > 0, ErrorCode::InvalidAmount);
-        require!(asset.maturity_date > Clock::get()?.unix_timestamp, ErrorCode::InvalidMaturityDate);
-        require!(asset.yield_rate <= 2000, ErrorCode::InvalidYieldRate); // Max 20% yield
+    let mut total_cost = 0u64;
+    for (purchase, accounts) in assets.iter().zip(ctx.remaining_accounts.chunks(2)) {
+        let (asset_info, exposure_info) = (&accounts[0], &accounts[1]);
+        
+        // Each holding gets a new account at its register_asset address
+        let (asset_key, asset_bump) = Pubkey::find_program_address(
+            &[b"fixed_income_asset", fund_key.as_ref(), purchase.asset_id.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(asset_info.key(), asset_key, ErrorCode::InvalidAccountData);
+        create_pda_account(
+            &payer,
+            asset_info,
+            &system_program,
+            FixedIncomeAsset::LEN,
+            &[b"fixed_income_asset", fund_key.as_ref(), purchase.asset_id.as_ref(), &[asset_bump]],
+        )?;
+        let mut asset = FixedIncomeAsset { bump: asset_bump, ..Default::default() };
+        
+        // Issuer exposure carries over from earlier purchases, including those earlier in the batch
+        let (exposure_key, exposure_bump) = Pubkey::find_program_address(
+            &[b"issuer_exposure", fund_key.as_ref(), purchase.issuer.as_ref()],
+            &crate::ID,
+        );
+        require_keys_eq!(exposure_info.key(), exposure_key, ErrorCode::InvalidAccountData);
+        let mut issuer_exposure = if *exposure_info.owner == system_program::ID {
+            create_pda_account(
+                &payer,
+                exposure_info,
+                &system_program,
+                IssuerExposure::LEN,
+                &[b"issuer_exposure", fund_key.as_ref(), purchase.issuer.as_ref(), &[exposure_bump]],
+            )?;
+            IssuerExposure::default()
+        } else {
+            require_keys_eq!(*exposure_info.owner, crate::ID, ErrorCode::InvalidAccountData);
+            IssuerExposure::try_deserialize(&mut &exposure_info.try_borrow_data()?[..])?
+        };
+        issuer_exposure.bump = exposure_bump;
         
-        total_investment = total_investment.checked_add(asset.amount).ok_or(ErrorCode::MathOverflow)?;
+        let cash_cost = record_purchase(fund_state, fund_key, &mut asset, asset_key, &mut issuer_exposure, purchase)?;
+        asset.try_serialize(&mut &mut asset_info.try_borrow_mut_data()?[..])?;
+        issuer_exposure.try_serialize(&mut &mut exposure_info.try_borrow_mut_data()?[..])?;
+        
+        total_cost = total_cost.checked_add(cash_cost).ok_or(ErrorCode::MathOverflow)?;
     }
     
-    // Check if we have enough cash reserves
-    require!(fund_state.cash_reserves >= total_investment, ErrorCode::InsufficientLiquidity);
-    
-    // Update fund state
-    fund_state.cash_reserves = fund_state.cash_reserves.checked_sub(total_investment).ok_or(ErrorCode::MathOverflow)?;
-    fund_state.fixed_income_value = fund_state.fixed_income_value.checked_add(total_investment * 100).ok_or(ErrorCode::MathOverflow)?; // Convert to 8 decimals
+    // Pay for the batch out of the treasury vault
+    if total_cost > 0 {
+        let fund_id_bytes = fund_state.fund_id.to_le_bytes();
+        let seeds = &[b"fund_state".as_ref(), fund_id_bytes.as_ref(), &[fund_state.bump]];
+        let signer = &[&seeds[..]];
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            token::Transfer {
+                from: ctx.accounts.treasury_vault.to_account_info(),
+                to: ctx.accounts.settlement_account.to_account_info(),
+                authority: fund_state.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(transfer_ctx, total_cost)?;
+    }
     
-    msg!("Invested {} USDC in fixed income assets", total_investment);
+    msg!("Invested {} USDC in {} fixed income assets", total_cost, assets.len());
     
     Ok(())
-} 
\ No newline at end of file
+}
+
+/// Create a rent-exempt program account at a PDA, topping up any lamports already sent to the address
+fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    space: usize,
+    seeds: &[&[u8]],
+) -> Result<()> {
+    let signer = &[seeds];
+    let rent = Rent::get()?.minimum_balance(space);
+    
+    if account.lamports() == 0 {
+        let create_ctx = CpiContext::new_with_signer(
+            system_program.clone(),
+            CreateAccount { from: payer.clone(), to: account.clone() },
+            signer,
+        );
+        return system_program::create_account(create_ctx, rent, space as u64, &crate::ID);
+    }
+    
+    let top_up = rent.saturating_sub(account.lamports());
+    if top_up > 0 {
+        let transfer_ctx = CpiContext::new(
+            system_program.clone(),
+            system_program::Transfer { from: payer.clone(), to: account.clone() },
+        );
+        system_program::transfer(transfer_ctx, top_up)?;
+    }
+    let allocate_ctx = CpiContext::new_with_signer(
+        system_program.clone(),
+        Allocate { account_to_allocate: account.clone() },
+        signer,
+    );
+    system_program::allocate(allocate_ctx, space as u64)?;
+    let assign_ctx = CpiContext::new_with_signer(
+        system_program.clone(),
+        Assign { account_to_assign: account.clone() },
+        signer,
+    );
+    system_program::assign(assign_ctx, &crate::ID)
+}
@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::LiquidityManaged;
+
+#[derive(Accounts)]
+pub struct ManageLiquidity<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = admin_authority
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    pub admin_authority: Signer<'info>,
+}
+
+/// Review cash against the target liquidity ratio and record the action needed to restore it
+pub fn manage_liquidity(ctx: Context<ManageLiquidity>) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    let clock = Clock::get()?;
+    
+    require!(!fund_state.is_paused, ErrorCode::FundPaused);
+    
+    let status = fund_state.liquidity_status()?;
+    fund_state.last_liquidity_action = status.action;
+    fund_state.last_liquidity_amount = status.amount;
+    fund_state.last_liquidity_review = clock.unix_timestamp;
+    
+    let action = match status.action {
+        LiquidityAction::Hold => "hold",
+        LiquidityAction::RaiseCash => "raise_cash",
+        LiquidityAction::DeployCash => "deploy_cash",
+    };
+    
+    emit!(LiquidityManaged {
+        action: action.to_string(),
+        amount: status.amount,
+        new_liquidity_ratio: status.liquidity_ratio,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!(
+        "Liquidity at {}% against {}% target: {} {} USDC",
+        status.liquidity_ratio, status.target_liquidity_ratio, action, status.amount
+    );
+    
+    Ok(())
+}
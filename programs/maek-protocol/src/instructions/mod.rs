@@ -1,15 +1,41 @@
-// pub mod initialize_fund;
+pub mod initialize_registry;
+pub mod initialize_fund;
 pub mod deposit;
 pub mod withdraw;
 pub mod update_nav;
-// pub mod invest_fixed_income;
-// pub mod handle_maturity;
-// pub mod admin;
+pub mod invest_fixed_income;
+pub mod register_asset;
+pub mod handle_maturity;
+pub mod sell_asset;
+pub mod credit_event;
+pub mod accrue_interest;
+pub mod claim_fees;
+pub mod redemption_queue;
+pub mod forward_pricing;
+pub mod fund_status;
+pub mod manage_liquidity;
+pub mod treasury;
+pub mod custodian_attestation;
+pub mod oracle_price;
+pub mod admin;
 
-// pub use initialize_fund::*;
+pub use initialize_registry::*;
+pub use initialize_fund::*;
 pub use deposit::*;
 pub use withdraw::*;
 pub use update_nav::*;
-// pub use invest_fixed_income::*;
-// pub use handle_maturity::*;
-// pub use admin::*; 
\ No newline at end of file
+pub use invest_fixed_income::*;
+pub use register_asset::*;
+pub use handle_maturity::*;
+pub use sell_asset::*;
+pub use credit_event::*;
+pub use accrue_interest::*;
+pub use claim_fees::*;
+pub use redemption_queue::*;
+pub use forward_pricing::*;
+pub use fund_status::*;
+pub use manage_liquidity::*;
+pub use treasury::*;
+pub use custodian_attestation::*;
+pub use oracle_price::*;
+pub use admin::*;
@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::{AssetRevalued, PriceOverridden};
+use crate::instructions::accrue_interest::accrue_asset_interest;
+use crate::instructions::update_nav::price_override_signer;
+use crate::utils::calculations::*;
+use crate::utils::oracle::*;
+use crate::utils::validation::validate_price_deviation;
+
+#[derive(Accounts)]
+pub struct SetAssetPriceFeed<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = admin_authority
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    pub admin_authority: Signer<'info>,
+    
+    #[account(
+        mut,
+        seeds = [b"fixed_income_asset", fund_state.key().as_ref(), fixed_income_asset.asset_id.as_ref()],
+        bump = fixed_income_asset.bump
+    )]
+    pub fixed_income_asset: Box<Account<'info, FixedIncomeAsset>>,
+}
+
+#[derive(Accounts)]
+pub struct RefreshAssetPrice<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fixed_income_asset", fund_state.key().as_ref(), fixed_income_asset.asset_id.as_ref()],
+        bump = fixed_income_asset.bump,
+        has_one = price_feed @ ErrorCode::InvalidPriceFeed
+    )]
+    pub fixed_income_asset: Box<Account<'info, FixedIncomeAsset>>,
+    
+    /// CHECK: Pyth price account, assigned to the asset by the admin and owned by the Pyth oracle program
+    #[account(owner = PYTH_ORACLE_PROGRAM_ID @ ErrorCode::InvalidPriceFeed)]
+    pub price_feed: UncheckedAccount<'info>,
+    
+    /// Price override authority, co-signing prices beyond the deviation limit
+    pub price_override_authority: Option<Signer<'info>>,
+}
+
+/// Assign the price feed valuing a holding; the default key returns it to manual valuation
+pub fn set_asset_price_feed(ctx: Context<SetAssetPriceFeed>, price_feed: Pubkey) -> Result<()> {
+    let asset_key = ctx.accounts.fixed_income_asset.key();
+    let asset = &mut ctx.accounts.fixed_income_asset;
+    
+    // Defaulted holdings stay at their written-down value
+    require!(asset.status == AssetStatus::Active, ErrorCode::AssetNotActive);
+    
+    // Book interest to now under the current pricing source before switching
+    accrue_asset_interest(&mut ctx.accounts.fund_state, asset, asset_key)?;
+    
+    // A discount instrument's market price includes its accretion: fold what has been accreted
+    // into the carrying value, which fixed income value already includes
+    if is_discount_instrument(asset.asset_type) {
+        asset.current_value = asset.current_value.checked_add(asset.accrued_interest).ok_or(ErrorCode::MathOverflow)?;
+        asset.accrued_interest = 0;
+    }
+    asset.price_feed = price_feed;
+    
+    msg!("Asset {} priced by feed {}", asset.key(), price_feed);
+    Ok(())
+}
+
+/// Permissionless crank revaluing an oracle-priced holding at the feed's current price
+pub fn refresh_asset_price(ctx: Context<RefreshAssetPrice>) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    let asset = &mut ctx.accounts.fixed_income_asset;
+    let clock = Clock::get()?;
+    
+    require!(asset.status == AssetStatus::Active, ErrorCode::AssetNotActive);
+    
+    // Read the aggregate price and reject stale or low-confidence quotes
+    let oracle_price = load_oracle_price(&ctx.accounts.price_feed.try_borrow_data()?)?;
+    validate_oracle_price(&oracle_price, clock.unix_timestamp)?;
+    require!(oracle_price.publish_time >= asset.last_valued_at, ErrorCode::StaleAssetValuation);
+    
+    // The price is quoted per unit of face value, excluding accrued interest
+    let previous_value = asset.current_value;
+    let new_value = calculate_oracle_value(asset.face_value, oracle_price.price, oracle_price.exponent)?;
+    
+    // Moves beyond the asset type's deviation limit need the price override authority
+    let override_authority = price_override_signer(&ctx.accounts.price_override_authority, fund_state)?;
+    let max_deviation_bps = fund_state.price_deviation_limit(asset.asset_type);
+    let deviation_bps = calculate_price_deviation_bps(previous_value, new_value);
+    match override_authority {
+        Some(authority) if deviation_bps > max_deviation_bps as u64 => {
+            emit!(PriceOverridden {
+                asset_id: asset.key(),
+                authority,
+                previous_value,
+                new_value,
+                deviation_bps,
+                max_deviation_bps,
+                timestamp: clock.unix_timestamp,
+            });
+        }
+        _ => validate_price_deviation(previous_value, new_value, max_deviation_bps)?,
+    }
+    
+    asset.previous_value = previous_value;
+    asset.current_value = new_value;
+    asset.last_valued_at = oracle_price.publish_time;
+    
+    // Carry the revaluation in fixed income value and total assets
+    fund_state.fixed_income_value = fund_state.fixed_income_value
+        .checked_sub(previous_value)
+        .and_then(|value| value.checked_add(new_value))
+        .ok_or(ErrorCode::MathOverflow)?;
+    fund_state.total_assets = fund_state.total_assets
+        .checked_sub(previous_value)
+        .and_then(|total| total.checked_add(new_value))
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    emit!(AssetRevalued {
+        asset_id: asset.key(),
+        price_feed: asset.price_feed,
+        previous_value,
+        new_value,
+        publish_time: oracle_price.publish_time,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Asset {} revalued from {} to {}", asset.key(), previous_value, new_value);
+    
+    Ok(())
+}
@@ -0,0 +1,288 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::{RedemptionProcessed, RedemptionQueued};
+use crate::utils::calculations::*;
+
+#[derive(Accounts)]
+pub struct RequestRedemption<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_account", fund_state.key().as_ref(), user.key().as_ref()],
+        bump = user_account.bump
+    )]
+    pub user_account: Box<Account<'info, UserFundAccount>>,
+    
+    /// Queue entry for this request
+    #[account(
+        init,
+        payer = user,
+        space = RedemptionRequest::LEN,
+        seeds = [
+            b"redemption_request",
+            fund_state.key().as_ref(),
+            fund_state.redemption_queue_tail.to_le_bytes().as_ref()
+        ],
+        bump
+    )]
+    pub redemption_request: Box<Account<'info, RedemptionRequest>>,
+    
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    /// User's fund token account
+    #[account(
+        mut,
+        token::mint = fund_state.fund_token_mint,
+        token::authority = user
+    )]
+    pub user_fund_token_account: Box<Account<'info, TokenAccount>>,
+    
+    /// Fund tokens escrowed by queued requests
+    #[account(
+        init_if_needed,
+        payer = user,
+        token::mint = fund_token_mint,
+        token::authority = fund_state,
+        seeds = [b"redemption_escrow", fund_state.key().as_ref()],
+        bump
+    )]
+    pub redemption_escrow: Box<Account<'info, TokenAccount>>,
+    
+    /// Fund token mint
+    #[account(address = fund_state.fund_token_mint)]
+    pub fund_token_mint: Box<Account<'info, Mint>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ProcessRedemptions<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = usdc_vault,
+        has_one = fund_token_mint
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    /// Fund's USDC vault
+    #[account(mut)]
+    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// Fund token mint
+    #[account(mut)]
+    pub fund_token_mint: Box<Account<'info, Mint>>,
+    
+    /// Fund tokens escrowed by queued requests
+    #[account(
+        mut,
+        seeds = [b"redemption_escrow", fund_state.key().as_ref()],
+        bump
+    )]
+    pub redemption_escrow: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    
+    // remaining_accounts: for each request from the head of the queue, in order,
+    // the RedemptionRequest (writable), the owner's USDC account (writable) and the owner (writable)
+}
+
+pub fn request_redemption(ctx: Context<RequestRedemption>, fund_tokens: u64) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    let user_account = &mut ctx.accounts.user_account;
+    let request = &mut ctx.accounts.redemption_request;
+    let clock = Clock::get()?;
+    
+    require!(!fund_state.is_paused, ErrorCode::FundPaused);
+    require!(!fund_state.forward_pricing, ErrorCode::ForwardPricingActive);
+    require!(fund_tokens > 0, ErrorCode::WithdrawAmountZero);
+    require!(user_account.fund_tokens >= fund_tokens, ErrorCode::InsufficientFundTokens);
+    
+    // Move the tokens into escrow until the request is paid
+    let transfer_ctx = CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.user_fund_token_account.to_account_info(),
+            to: ctx.accounts.redemption_escrow.to_account_info(),
+            authority: ctx.accounts.user.to_account_info(),
+        },
+    );
+    token::transfer(transfer_ctx, fund_tokens)?;
+    
+    request.fund = fund_state.key();
+    request.owner = ctx.accounts.user.key();
+    request.sequence = fund_state.redemption_queue_tail;
+    request.fund_tokens = fund_tokens;
+    request.requested_at = clock.unix_timestamp;
+    request.bump = ctx.bumps.redemption_request;
+    
+    // Join the back of the queue (shares stay outstanding until burned)
+    fund_state.redemption_queue_tail = fund_state.redemption_queue_tail.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.queued_redemption_tokens = fund_state.queued_redemption_tokens
+        .checked_add(fund_tokens)
+        .ok_or(ErrorCode::MathOverflow)?;
+    user_account.fund_tokens = user_account.fund_tokens.checked_sub(fund_tokens).ok_or(ErrorCode::MathOverflow)?;
+    
+    emit!(RedemptionQueued {
+        request: request.key(),
+        owner: request.owner,
+        sequence: request.sequence,
+        fund_tokens,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Queued redemption {} for {} fund tokens", request.sequence, fund_tokens);
+    
+    Ok(())
+}
+
+pub fn process_redemptions<'info>(ctx: Context<'_, '_, '_, 'info, ProcessRedemptions<'info>>) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    let clock = Clock::get()?;
+    
+    require!(!fund_state.is_paused, ErrorCode::FundPaused);
+    require!(!fund_state.forward_pricing, ErrorCode::ForwardPricingActive);
+    let request_count = ctx.remaining_accounts.len() / 3;
+    require!(request_count * 3 == ctx.remaining_accounts.len(), ErrorCode::InvalidAccountData);
+    
+    let fund_key = fund_state.key();
+    let fund_id_bytes = fund_state.fund_id.to_le_bytes();
+    let bump = fund_state.bump;
+    let seeds = &[b"fund_state".as_ref(), fund_id_bytes.as_ref(), &[bump]];
+    let signer = &[&seeds[..]];
+    
+    for accounts in ctx.remaining_accounts.chunks(3) {
+        let (request_info, owner_usdc_info, owner_info) = (&accounts[0], &accounts[1], &accounts[2]);
+        
+        // Requests are paid strictly from the head of the queue
+        require_keys_eq!(*request_info.owner, crate::ID, ErrorCode::InvalidAccountData);
+        require!(request_info.is_writable, ErrorCode::InvalidAccountData);
+        let mut request = RedemptionRequest::try_deserialize(&mut &request_info.try_borrow_data()?[..])?;
+        require_keys_eq!(request.fund, fund_key, ErrorCode::InvalidAccountData);
+        require!(request.sequence == fund_state.redemption_queue_head, ErrorCode::RedemptionQueueOrder);
+        
+        // Pay into the owner's USDC account
+        require_keys_eq!(*owner_usdc_info.owner, token::ID, ErrorCode::InvalidAccountData);
+        let owner_usdc = TokenAccount::try_deserialize(&mut &owner_usdc_info.try_borrow_data()?[..])?;
+        require_keys_eq!(owner_usdc.mint, fund_state.usdc_mint, ErrorCode::InvalidAccountData);
+        require_keys_eq!(owner_usdc.owner, request.owner, ErrorCode::InvalidAccountData);
+        require_keys_eq!(owner_info.key(), request.owner, ErrorCode::InvalidAccountData);
+        
+        // Pay at the NAV in force now, partially filling the request if cash or the redemption gate runs short
+        let available = fund_state.cash_reserves.min(fund_state.remaining_redemption_capacity()? / 100); // Convert 8 to 6 decimals
+        let mut tokens_paid = request.fund_tokens;
+        let mut nav_value = calculate_withdrawal_amount(tokens_paid, fund_state.nav_per_share, 0)?;
+        if nav_value > available {
+            if available == 0 {
+                break;
+            }
+            tokens_paid = calculate_fund_tokens(available, fund_state.nav_per_share, 0)?.min(request.fund_tokens);
+            if tokens_paid == 0 {
+                break;
+            }
+            nav_value = calculate_withdrawal_amount(tokens_paid, fund_state.nav_per_share, 0)?;
+        }
+        
+        // Swing the price down if net outflows since the last NAV update exceed the threshold, as for withdrawals
+        let nav_value_scaled = nav_value.checked_mul(100).ok_or(ErrorCode::MathOverflow)?; // Convert to 8 decimals
+        let net_flows = fund_state.net_flows
+            .checked_sub(i64::try_from(nav_value_scaled).map_err(|_| ErrorCode::MathOverflow)?)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let swing_factor_bps = calculate_swing_factor(
+            net_flows.min(0).unsigned_abs(),
+            fund_state.total_assets,
+            fund_state.outflow_swing_threshold_bps,
+            fund_state.outflow_swing_factor_bps,
+        );
+        let usdc_amount = calculate_withdrawal_amount(tokens_paid, fund_state.nav_per_share, swing_factor_bps)?;
+        let swing_levy = nav_value.checked_sub(usdc_amount).ok_or(ErrorCode::MathOverflow)?;
+        
+        // Burn the escrowed tokens and pay out USDC
+        let burn_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Burn {
+                mint: ctx.accounts.fund_token_mint.to_account_info(),
+                from: ctx.accounts.redemption_escrow.to_account_info(),
+                authority: fund_state.to_account_info(),
+            },
+            signer,
+        );
+        token::burn(burn_ctx, tokens_paid)?;
+        
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.usdc_vault.to_account_info(),
+                to: owner_usdc_info.clone(),
+                authority: fund_state.to_account_info(),
+            },
+            signer,
+        );
+        token::transfer(transfer_ctx, usdc_amount)?;
+        
+        // Update fund state
+        fund_state.total_assets = fund_state.total_assets
+            .checked_sub(usdc_amount.checked_mul(100).ok_or(ErrorCode::MathOverflow)?) // Convert 6 to 8 decimals
+            .ok_or(ErrorCode::MathOverflow)?;
+        fund_state.total_shares = fund_state.total_shares.checked_sub(tokens_paid).ok_or(ErrorCode::MathOverflow)?;
+        fund_state.cash_reserves = fund_state.cash_reserves.checked_sub(usdc_amount).ok_or(ErrorCode::MathOverflow)?;
+        fund_state.queued_redemption_tokens = fund_state.queued_redemption_tokens
+            .checked_sub(tokens_paid)
+            .ok_or(ErrorCode::MathOverflow)?;
+        fund_state.net_flows = net_flows;
+        fund_state.period_redemptions = fund_state.period_redemptions
+            .checked_add(nav_value_scaled) // The gate counts redemptions at NAV
+            .ok_or(ErrorCode::MathOverflow)?;
+        fund_state.total_swing_levies = fund_state.total_swing_levies
+            .checked_add(swing_levy.checked_mul(100).ok_or(ErrorCode::MathOverflow)?) // Retained by the fund
+            .ok_or(ErrorCode::MathOverflow)?;
+        request.fund_tokens -= tokens_paid;
+        
+        emit!(RedemptionProcessed {
+            request: request_info.key(),
+            owner: request.owner,
+            sequence: request.sequence,
+            fund_tokens_burned: tokens_paid,
+            amount_usdc: usdc_amount,
+            nav_per_share: fund_state.nav_per_share,
+            swing_factor_bps,
+            swing_levy,
+            remaining_tokens: request.fund_tokens,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        if request.fund_tokens > 0 {
+            // Partially paid: the request keeps its place at the head of the queue
+            request.try_serialize(&mut &mut request_info.try_borrow_mut_data()?[..])?;
+            break;
+        }
+        
+        // Fully paid: close the request and return its rent to the owner
+        let rent = request_info.lamports();
+        **owner_info.try_borrow_mut_lamports()? = owner_info.lamports().checked_add(rent).ok_or(ErrorCode::MathOverflow)?;
+        **request_info.try_borrow_mut_lamports()? = 0;
+        request_info.assign(&system_program::ID);
+        request_info.realloc(0, false)?;
+        
+        fund_state.redemption_queue_head = fund_state.redemption_queue_head.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    }
+    
+    msg!(
+        "Redemption queue at {} of {}, {} fund tokens still queued",
+        fund_state.redemption_queue_head, fund_state.redemption_queue_tail, fund_state.queued_redemption_tokens
+    );
+    
+    Ok(())
+}
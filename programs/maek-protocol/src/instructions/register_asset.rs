@@ -0,0 +1,216 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::AssetPurchased;
+use crate::utils::calculations::*;
+use crate::utils::validation::*;
+
+#[derive(Accounts)]
+#[instruction(purchase: AssetPurchase)]
+pub struct RegisterAsset<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = admin_authority,
+        has_one = treasury_vault
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    /// Per-asset holding record
+    #[account(
+        init,
+        payer = admin_authority,
+        space = FixedIncomeAsset::LEN,
+        seeds = [b"fixed_income_asset", fund_state.key().as_ref(), purchase.asset_id.as_ref()],
+        bump
+    )]
+    pub fixed_income_asset: Box<Account<'info, FixedIncomeAsset>>,
+    
+    /// Running exposure to the asset's issuer
+    #[account(
+        init_if_needed,
+        payer = admin_authority,
+        space = IssuerExposure::LEN,
+        seeds = [b"issuer_exposure", fund_state.key().as_ref(), purchase.issuer.as_ref()],
+        bump
+    )]
+    pub issuer_exposure: Box<Account<'info, IssuerExposure>>,
+    
+    /// Fund's treasury vault, paying for the purchase
+    #[account(mut)]
+    pub treasury_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// Custodian USDC account the purchase settles into
+    #[account(
+        mut,
+        address = fund_state.settlement_account @ ErrorCode::InvalidAccountData,
+        constraint = settlement_account.mint == fund_state.usdc_mint @ ErrorCode::InvalidAccountData
+    )]
+    pub settlement_account: Box<Account<'info, TokenAccount>>,
+    
+    #[account(mut)]
+    pub admin_authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn register_asset(ctx: Context<RegisterAsset>, purchase: AssetPurchase) -> Result<()> {
+    let fund_key = ctx.accounts.fund_state.key();
+    let asset_key = ctx.accounts.fixed_income_asset.key();
+    let fund_state = &mut ctx.accounts.fund_state;
+    
+    require!(!fund_state.is_paused, ErrorCode::FundPaused);
+    
+    ctx.accounts.fixed_income_asset.bump = ctx.bumps.fixed_income_asset;
+    ctx.accounts.issuer_exposure.bump = ctx.bumps.issuer_exposure;
+    let cash_cost = record_purchase(
+        fund_state,
+        fund_key,
+        &mut ctx.accounts.fixed_income_asset,
+        asset_key,
+        &mut ctx.accounts.issuer_exposure,
+        &purchase,
+    )?;
+    
+    // Pay for the holding out of the treasury vault
+    let fund_id_bytes = fund_state.fund_id.to_le_bytes();
+    let seeds = &[b"fund_state".as_ref(), fund_id_bytes.as_ref(), &[fund_state.bump]];
+    let signer = &[&seeds[..]];
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.treasury_vault.to_account_info(),
+            to: ctx.accounts.settlement_account.to_account_info(),
+            authority: fund_state.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(transfer_ctx, cash_cost)?;
+    
+    msg!("Registered asset {} for {} USDC", asset_key, cash_cost);
+    
+    Ok(())
+}
+
+/// Validate a purchase against the fund's limits and record it on a new holding and its issuer's exposure
+/// The caller sets both accounts' bumps; returns the USDC (6 decimals) the purchase settles for
+pub(crate) fn record_purchase(
+    fund_state: &mut FundState,
+    fund_key: Pubkey,
+    asset: &mut FixedIncomeAsset,
+    asset_key: Pubkey,
+    issuer_exposure: &mut IssuerExposure,
+    purchase: &AssetPurchase,
+) -> Result<u64> {
+    let clock = Clock::get()?;
+    
+    // Validate asset quality and portfolio limits
+    validate_asset_purchase(purchase, fund_state.total_assets, issuer_exposure.total_value)?;
+    
+    // Settle the purchase out of cash swept to the treasury vault, already above the liquidity target
+    let cash_cost = purchase.purchase_price / 100; // Convert 8 to 6 decimals
+    require!(fund_state.treasury_cash >= cash_cost, ErrorCode::InsufficientLiquidity);
+    
+    let days_to_maturity = ((purchase.maturity_date - clock.unix_timestamp) / 86400) as u16;
+    let day_count_convention = default_day_count_convention(purchase.asset_type);
+    
+    // Interest rate risk at the purchase yield, checked against the portfolio duration limit
+    let metrics = calculate_duration_metrics(
+        purchase.coupon_rate,
+        purchase.coupon_frequency,
+        purchase.yield_rate,
+        day_count_convention,
+        clock.unix_timestamp,
+        purchase.maturity_date,
+    )?;
+    let weighted_duration_sum = fund_state.weighted_duration_sum
+        .checked_add(metrics.modified_duration as u128 * purchase.purchase_price as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    validate_portfolio_duration(weighted_duration_sum, fund_state.total_assets, fund_state.max_portfolio_duration)?;
+    
+    let next_coupon_date = if purchase.coupon_frequency > 0 {
+        coupon_period(purchase.maturity_date, purchase.coupon_frequency, clock.unix_timestamp)?.1
+    } else {
+        0
+    };
+    
+    // Dollar-weighted maturity after the purchase, checked against the WAM and WAL limits
+    let held_book_value = fund_state.held_book_value.checked_add(purchase.purchase_price).ok_or(ErrorCode::MathOverflow)?;
+    let maturity_weighted_sum = fund_state.maturity_weighted_sum
+        .checked_add(purchase.purchase_price as u128 * purchase.maturity_date as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let reset_date = if purchase.floating_rate { next_coupon_date } else { purchase.maturity_date };
+    let reset_weighted_sum = fund_state.reset_weighted_sum
+        .checked_add(purchase.purchase_price as u128 * reset_date as u128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let cash_value = fund_cash_value(fund_state)?.checked_sub(purchase.purchase_price).ok_or(ErrorCode::MathOverflow)?;
+    validate_portfolio_maturity(
+        maturity_weighted_sum,
+        reset_weighted_sum,
+        held_book_value,
+        cash_value,
+        fund_state.max_wam_days,
+        fund_state.max_wal_days,
+    )?;
+    
+    // Record the holding
+    asset.fund = fund_key;
+    asset.asset_id = purchase.asset_id;
+    asset.asset_type = purchase.asset_type;
+    asset.face_value = purchase.face_value;
+    asset.purchase_price = purchase.purchase_price;
+    asset.purchase_date = clock.unix_timestamp;
+    asset.maturity_date = purchase.maturity_date;
+    asset.current_value = purchase.purchase_price;
+    asset.last_valued_at = clock.unix_timestamp;
+    asset.yield_rate = purchase.yield_rate;
+    asset.coupon_rate = purchase.coupon_rate;
+    asset.coupon_frequency = purchase.coupon_frequency;
+    asset.next_coupon_date = next_coupon_date;
+    asset.floating_rate = purchase.floating_rate;
+    asset.accrued_interest = 0;
+    asset.last_interest_calculation = clock.unix_timestamp;
+    asset.day_count_convention = day_count_convention;
+    asset.status = AssetStatus::Active;
+    asset.days_to_maturity = days_to_maturity;
+    asset.credit_rating = purchase.credit_rating;
+    asset.issuer = purchase.issuer;
+    asset.original_duration = days_to_maturity;
+    asset.macaulay_duration = metrics.macaulay_duration;
+    asset.modified_duration = metrics.modified_duration;
+    asset.convexity = metrics.convexity;
+    asset.sector = purchase.sector;
+    asset.country = purchase.country;
+    asset.currency = 1; // USD
+    
+    // Track issuer exposure
+    if issuer_exposure.fund == Pubkey::default() {
+        issuer_exposure.fund = fund_key;
+        issuer_exposure.issuer = purchase.issuer;
+    }
+    issuer_exposure.total_value = issuer_exposure.total_value.checked_add(purchase.purchase_price).ok_or(ErrorCode::MathOverflow)?;
+    issuer_exposure.asset_count = issuer_exposure.asset_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    
+    // Update fund aggregates (fixed income value and total assets are unchanged: treasury cash becomes a holding)
+    fund_state.treasury_cash = fund_state.treasury_cash.checked_sub(cash_cost).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.active_asset_count = fund_state.active_asset_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.weighted_duration_sum = weighted_duration_sum;
+    fund_state.held_book_value = held_book_value;
+    fund_state.maturity_weighted_sum = maturity_weighted_sum;
+    fund_state.reset_weighted_sum = reset_weighted_sum;
+    refresh_portfolio_risk(fund_state, clock.unix_timestamp)?;
+    
+    emit!(AssetPurchased {
+        asset_id: asset_key,
+        asset_type: asset.asset_type as u8,
+        purchase_amount: asset.purchase_price,
+        yield_rate: asset.yield_rate,
+        maturity_date: asset.maturity_date,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    Ok(cash_cost)
+}
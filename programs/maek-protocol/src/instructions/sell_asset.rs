@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Token, TokenAccount};
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::AssetSold;
+use crate::instructions::treasury::settle_treasury_proceeds;
+use crate::utils::calculations::*;
+
+#[derive(Accounts)]
+pub struct SellAsset<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = admin_authority,
+        has_one = usdc_vault,
+        has_one = treasury_vault
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    #[account(
+        mut,
+        seeds = [b"fixed_income_asset", fund_state.key().as_ref(), fixed_income_asset.asset_id.as_ref()],
+        bump = fixed_income_asset.bump
+    )]
+    pub fixed_income_asset: Box<Account<'info, FixedIncomeAsset>>,
+    
+    #[account(
+        mut,
+        seeds = [b"issuer_exposure", fund_state.key().as_ref(), fixed_income_asset.issuer.as_ref()],
+        bump = issuer_exposure.bump
+    )]
+    pub issuer_exposure: Box<Account<'info, IssuerExposure>>,
+    
+    /// Fund's USDC vault, receiving the proceeds as cash reserves
+    #[account(mut)]
+    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// Fund's treasury vault, into which the buyer's settlement is paid
+    #[account(mut)]
+    pub treasury_vault: Box<Account<'info, TokenAccount>>,
+    
+    pub admin_authority: Signer<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn sell_asset(ctx: Context<SellAsset>, sale_price: u64) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    let asset = &mut ctx.accounts.fixed_income_asset;
+    let issuer_exposure = &mut ctx.accounts.issuer_exposure;
+    let clock = Clock::get()?;
+    
+    require!(!fund_state.is_paused, ErrorCode::FundPaused);
+    require!(asset.status == AssetStatus::Active, ErrorCode::AssetNotActive);
+    
+    // Sale proceeds must be non-zero and settle exactly in USDC
+    require!(sale_price > 0, ErrorCode::AssetSaleFailed);
+    require!(sale_price / 100 * 100 == sale_price, ErrorCode::AssetSaleFailed);
+    let proceeds_usdc = sale_price / 100; // Convert 8 to 6 decimals
+    
+    // Realized gain/loss against cost basis (purchase price plus accrued interest)
+    let realized_pnl = calculate_realized_pnl(sale_price, asset.purchase_price, asset.accrued_interest)?;
+    
+    // Carrying value of the holding on the fund's books
+    let carrying_value = asset.current_value.checked_add(asset.accrued_interest).ok_or(ErrorCode::MathOverflow)?;
+    
+    // The holding leaves the books at the sale price
+    fund_state.fixed_income_value = fund_state.fixed_income_value.checked_sub(carrying_value).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.total_assets = fund_state.total_assets
+        .checked_sub(carrying_value)
+        .and_then(|total| total.checked_add(sale_price))
+        .ok_or(ErrorCode::MathOverflow)?;
+    fund_state.active_asset_count = fund_state.active_asset_count.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.realized_pnl = fund_state.realized_pnl.checked_add(realized_pnl).ok_or(ErrorCode::MathOverflow)?;
+    
+    // Proceeds settled into the treasury vault return to cash reserves
+    settle_treasury_proceeds(
+        fund_state,
+        &ctx.accounts.treasury_vault,
+        &ctx.accounts.usdc_vault,
+        &ctx.accounts.token_program,
+        proceeds_usdc,
+    )?;
+    
+    // Remove the holding from the portfolio risk aggregates
+    let duration_weight = asset.modified_duration as u128 * asset.purchase_price as u128;
+    let maturity_weight = asset.purchase_price as u128 * asset.maturity_date as u128;
+    let reset_weight = asset.purchase_price as u128 * asset.interest_reset_date() as u128;
+    fund_state.weighted_duration_sum = fund_state.weighted_duration_sum.checked_sub(duration_weight).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.maturity_weighted_sum = fund_state.maturity_weighted_sum.checked_sub(maturity_weight).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.reset_weighted_sum = fund_state.reset_weighted_sum.checked_sub(reset_weight).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.held_book_value = fund_state.held_book_value.checked_sub(asset.purchase_price).ok_or(ErrorCode::MathOverflow)?;
+    refresh_portfolio_risk(fund_state, clock.unix_timestamp)?;
+    
+    // Release issuer exposure taken at purchase
+    issuer_exposure.total_value = issuer_exposure.total_value.checked_sub(asset.purchase_price).ok_or(ErrorCode::MathOverflow)?;
+    issuer_exposure.asset_count = issuer_exposure.asset_count.checked_sub(1).ok_or(ErrorCode::MathOverflow)?;
+    
+    // Mark the asset as sold
+    asset.status = AssetStatus::Sold;
+    asset.current_value = sale_price;
+    
+    emit!(AssetSold {
+        asset_id: asset.key(),
+        sale_price,
+        cost_basis: asset.purchase_price.saturating_add(asset.accrued_interest),
+        realized_pnl,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Asset {} sold for {} USDC, realized P&L {}", asset.key(), proceeds_usdc, realized_pnl);
+    
+    Ok(())
+}
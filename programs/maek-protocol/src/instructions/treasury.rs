@@ -0,0 +1,202 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use crate::state::*;
+use crate::error::ErrorCode;
+use crate::events::LiquidityManaged;
+use crate::utils::calculations::*;
+
+#[derive(Accounts)]
+pub struct InitializeTreasuryVault<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = admin_authority,
+        has_one = usdc_mint
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    #[account(mut)]
+    pub admin_authority: Signer<'info>,
+    
+    /// USDC mint (6 decimals)
+    pub usdc_mint: Box<Account<'info, Mint>>,
+    
+    /// Treasury vault for custodian settlement of fixed income purchases
+    #[account(
+        init,
+        payer = admin_authority,
+        token::mint = usdc_mint,
+        token::authority = fund_state,
+        seeds = [b"treasury_vault", fund_state.key().as_ref()],
+        bump
+    )]
+    pub treasury_vault: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SweepTreasury<'info> {
+    #[account(
+        mut,
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
+        bump = fund_state.bump,
+        has_one = admin_authority,
+        has_one = usdc_vault,
+        has_one = treasury_vault
+    )]
+    pub fund_state: Box<Account<'info, FundState>>,
+    
+    pub admin_authority: Signer<'info>,
+    
+    /// Fund's USDC vault
+    #[account(mut)]
+    pub usdc_vault: Box<Account<'info, TokenAccount>>,
+    
+    /// Fund's treasury vault
+    #[account(mut)]
+    pub treasury_vault: Box<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn initialize_treasury_vault(ctx: Context<InitializeTreasuryVault>) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    fund_state.treasury_vault = ctx.accounts.treasury_vault.key();
+    
+    msg!("Treasury vault {} created for fund {}", fund_state.treasury_vault, fund_state.fund_id);
+    Ok(())
+}
+
+/// Move cash above the target liquidity ratio to the treasury vault for custodian settlement
+pub fn sweep_to_treasury(ctx: Context<SweepTreasury>, amount: u64) -> Result<()> {
+    let fund_state = &mut ctx.accounts.fund_state;
+    let clock = Clock::get()?;
+    
+    require!(!fund_state.is_paused, ErrorCode::FundPaused);
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(fund_state.cash_reserves >= amount, ErrorCode::InsufficientLiquidity);
+    require!(
+        amount <= calculate_excess_liquidity(fund_state.cash_reserves, fund_state.total_assets, fund_state.target_liquidity_ratio)?,
+        ErrorCode::LiquidityRatioBelowMinimum
+    );
+    
+    let fund_id_bytes = fund_state.fund_id.to_le_bytes();
+    let seeds = &[b"fund_state".as_ref(), fund_id_bytes.as_ref(), &[fund_state.bump]];
+    let signer = &[&seeds[..]];
+    
+    let transfer_ctx = CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        Transfer {
+            from: ctx.accounts.usdc_vault.to_account_info(),
+            to: ctx.accounts.treasury_vault.to_account_info(),
+            authority: fund_state.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(transfer_ctx, amount)?;
+    
+    // The swept cash moves to the investment side; total assets are unchanged
+    fund_state.cash_reserves = fund_state.cash_reserves.checked_sub(amount).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.treasury_cash = fund_state.treasury_cash.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.fixed_income_value = fund_state.fixed_income_value
+        .checked_add(amount * 100) // Convert 6 to 8 decimals
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    emit!(LiquidityManaged {
+        action: "deploy_cash".to_string(),
+        amount,
+        new_liquidity_ratio: calculate_liquidity_ratio(fund_state.cash_reserves * 100, fund_state.total_assets) as u8,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Swept {} USDC to the treasury vault", amount);
+    
+    Ok(())
+}
+
+/// Return settlement cash, such as unspent purchase funds, from the treasury vault to the USDC vault
+pub fn sweep_from_treasury(ctx: Context<SweepTreasury>, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    
+    require!(amount > 0, ErrorCode::InvalidAmount);
+    require!(ctx.accounts.fund_state.treasury_cash >= amount, ErrorCode::InsufficientLiquidity);
+    
+    return_treasury_cash(
+        &mut ctx.accounts.fund_state,
+        &ctx.accounts.treasury_vault,
+        &ctx.accounts.usdc_vault,
+        &ctx.accounts.token_program,
+        amount,
+    )?;
+    
+    let fund_state = &ctx.accounts.fund_state;
+    emit!(LiquidityManaged {
+        action: "raise_cash".to_string(),
+        amount,
+        new_liquidity_ratio: calculate_liquidity_ratio(fund_state.cash_reserves * 100, fund_state.total_assets) as u8,
+        timestamp: clock.unix_timestamp,
+    });
+    
+    msg!("Swept {} USDC back from the treasury vault", amount);
+    
+    Ok(())
+}
+
+/// Book proceeds the custodian has paid into the treasury vault, then sweep them back to cash reserves
+pub(crate) fn settle_treasury_proceeds<'info>(
+    fund_state: &mut Account<'info, FundState>,
+    treasury_vault: &Account<'info, TokenAccount>,
+    usdc_vault: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    proceeds_usdc: u64,
+) -> Result<()> {
+    // Only USDC that has actually reached the vault can be booked
+    let booked_cash = fund_state.treasury_cash.checked_add(proceeds_usdc).ok_or(ErrorCode::MathOverflow)?;
+    require!(treasury_vault.amount >= booked_cash, ErrorCode::ProceedsNotReceived);
+    
+    fund_state.treasury_cash = booked_cash;
+    fund_state.fixed_income_value = fund_state.fixed_income_value
+        .checked_add(proceeds_usdc.checked_mul(100).ok_or(ErrorCode::MathOverflow)?) // Convert 6 to 8 decimals
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    return_treasury_cash(fund_state, treasury_vault, usdc_vault, token_program, proceeds_usdc)
+}
+
+/// Transfer treasury cash to the USDC vault, moving it from the investment side into cash reserves
+fn return_treasury_cash<'info>(
+    fund_state: &mut Account<'info, FundState>,
+    treasury_vault: &Account<'info, TokenAccount>,
+    usdc_vault: &Account<'info, TokenAccount>,
+    token_program: &Program<'info, Token>,
+    amount: u64,
+) -> Result<()> {
+    if amount == 0 {
+        return Ok(());
+    }
+    
+    let fund_id_bytes = fund_state.fund_id.to_le_bytes();
+    let seeds = &[b"fund_state".as_ref(), fund_id_bytes.as_ref(), &[fund_state.bump]];
+    let signer = &[&seeds[..]];
+    
+    let transfer_ctx = CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        Transfer {
+            from: treasury_vault.to_account_info(),
+            to: usdc_vault.to_account_info(),
+            authority: fund_state.to_account_info(),
+        },
+        signer,
+    );
+    token::transfer(transfer_ctx, amount)?;
+    
+    fund_state.treasury_cash = fund_state.treasury_cash.checked_sub(amount).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.fixed_income_value = fund_state.fixed_income_value
+        .checked_sub(amount.checked_mul(100).ok_or(ErrorCode::MathOverflow)?) // Convert 6 to 8 decimals
+        .ok_or(ErrorCode::MathOverflow)?;
+    fund_state.cash_reserves = fund_state.cash_reserves.checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+    
+    Ok(())
+}
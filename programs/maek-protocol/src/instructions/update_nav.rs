@@ -1,67 +1,264 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
 use crate::error::ErrorCode;
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
-pub struct AssetValuation {
-    pub asset_id: Pubkey,
-    pub current_value: u64,
-}
+use crate::events::{NAVUpdated, PerformanceFeeCrystallized, PriceOverridden};
+use crate::utils::calculations::*;
+use crate::utils::validation::*;
+use crate::utils::oracle::MAX_ORACLE_PRICE_AGE;
 
 #[derive(Accounts)]
 pub struct UpdateNAV<'info> {
     #[account(
         mut,
-        seeds = [b"fund_state"],
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump,
         has_one = admin_authority
     )]
     pub fund_state: Account<'info, FundState>,
     
     pub admin_authority: Signer<'info>,
+    
+    /// Custodian's latest holdings attestation, required once a custodian is set
+    pub custodian_attestation: Option<Account<'info, CustodianAttestation>>,
+    
+    /// Price override authority, co-signing valuations beyond the deviation limits
+    pub price_override_authority: Option<Signer<'info>>,
+    
+    // remaining_accounts: every held FixedIncomeAsset of the fund (writable),
+    // in the same order as the valuations
 }
 
-pub fn update_nav(
-    ctx: Context<UpdateNAV>,
-    new_asset_valuations: Vec<AssetValuation>,
-    net_daily_pnl: i64,
-) -> Result<()> {
+pub fn update_nav(ctx: Context<UpdateNAV>, new_asset_valuations: Vec<AssetValuation>) -> Result<()> {
     let fund_state = &mut ctx.accounts.fund_state;
+    let asset_accounts = ctx.remaining_accounts;
     let clock = Clock::get()?;
     
     // Validate NAV update frequency (minimum 23 hours)
     let time_since_last_update = clock.unix_timestamp - fund_state.last_nav_update;
     require!(time_since_last_update >= 82800, ErrorCode::NAVUpdateTooFrequent); // 23 hours
     
-    // Update asset valuations and calculate new total assets
-    let mut new_total_assets = fund_state.cash_reserves * 100; // Convert USDC to 8 decimals
+    // Orders struck at the current NAV settle before it is replaced
+    require!(fund_state.orders_awaiting_settlement == 0, ErrorCode::OrdersAwaitingSettlement);
+    
+    // Every held asset must be valued, each exactly once
+    require!(
+        new_asset_valuations.len() == asset_accounts.len()
+            && asset_accounts.len() == fund_state.active_asset_count as usize,
+        ErrorCode::IncompleteAssetValuations
+    );
     
-    for valuation in new_asset_valuations {
-        new_total_assets = new_total_assets.checked_add(valuation.current_value).ok_or(ErrorCode::MathOverflow)?;
+    let override_authority = price_override_signer(&ctx.accounts.price_override_authority, fund_state)?;
+    
+    let mut seen_assets: Vec<Pubkey> = Vec::with_capacity(asset_accounts.len());
+    // Cash in the treasury vault is carried on the investment side
+    let mut new_fixed_income_value = fund_state.treasury_cash
+        .checked_mul(100) // Convert USDC to 8 decimals
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    // Par of the held assets, checked against the custodian's statement
+    let mut held_par = 0u64;
+    
+    // Book value x time to each holding's reset and maturity, a holding past its date counting as zero
+    let mut reset_seconds = 0u128;
+    let mut maturity_seconds = 0u128;
+    
+    for (valuation, asset_info) in new_asset_valuations.iter().zip(asset_accounts.iter()) {
+        require_keys_eq!(valuation.asset_id, asset_info.key(), ErrorCode::AssetValuationMismatch);
+        require!(!seen_assets.contains(asset_info.key), ErrorCode::DuplicateAssetValuation);
+        seen_assets.push(asset_info.key());
+        
+        // Load the holding and check it belongs to this fund and is still held
+        require_keys_eq!(*asset_info.owner, crate::ID, ErrorCode::InvalidAccountData);
+        require!(asset_info.is_writable, ErrorCode::InvalidAccountData);
+        let mut asset = FixedIncomeAsset::try_deserialize(&mut &asset_info.try_borrow_data()?[..])?;
+        require_keys_eq!(asset.fund, fund_state.key(), ErrorCode::AssetValuationMismatch);
+        require!(
+            asset.status == AssetStatus::Active || asset.status == AssetStatus::DefaultRisk,
+            ErrorCode::AssetNotActive
+        );
+        
+        // Oracle-priced holdings keep their feed value, which must be fresh
+        if asset.price_feed != Pubkey::default() && asset.status == AssetStatus::Active {
+            require!(
+                clock.unix_timestamp - asset.last_valued_at <= MAX_ORACLE_PRICE_AGE,
+                ErrorCode::StaleAssetValuation
+            );
+        } else if asset.status == AssetStatus::DefaultRisk {
+            // Defaulted holdings stay at their written-down value; only write-downs and recoveries,
+            // each recorded in the asset's write-down history, may move it
+            require!(valuation.current_value == asset.current_value, ErrorCode::DefaultedAssetRevalued);
+        } else {
+            // Moves beyond the asset type's deviation limit need the price override authority
+            let max_deviation_bps = fund_state.price_deviation_limit(asset.asset_type);
+            let deviation_bps = calculate_price_deviation_bps(asset.current_value, valuation.current_value);
+            match override_authority {
+                Some(authority) if deviation_bps > max_deviation_bps as u64 => {
+                    validate_oracle_data(std::slice::from_ref(valuation), &[asset.current_value], u32::MAX)?;
+                    emit!(PriceOverridden {
+                        asset_id: asset_info.key(),
+                        authority,
+                        previous_value: asset.current_value,
+                        new_value: valuation.current_value,
+                        deviation_bps,
+                        max_deviation_bps,
+                        timestamp: clock.unix_timestamp,
+                    });
+                }
+                _ => validate_oracle_data(std::slice::from_ref(valuation), &[asset.current_value], max_deviation_bps)?,
+            }
+            asset.previous_value = asset.current_value;
+            asset.current_value = valuation.current_value;
+            asset.last_valued_at = clock.unix_timestamp;
+        }
+        
+        // Accrued interest is booked by the accrual crank, never by the valuation
+        asset.try_serialize(&mut &mut asset_info.try_borrow_mut_data()?[..])?;
+        
+        new_fixed_income_value = new_fixed_income_value
+            .checked_add(asset.current_value)
+            .and_then(|total| total.checked_add(asset.accrued_interest))
+            .ok_or(ErrorCode::MathOverflow)?;
+        held_par = held_par.checked_add(asset.face_value).ok_or(ErrorCode::MathOverflow)?;
+        reset_seconds = reset_seconds
+            .checked_add(holding_dollar_seconds(asset.purchase_price, asset.interest_reset_date(), clock.unix_timestamp))
+            .ok_or(ErrorCode::MathOverflow)?;
+        maturity_seconds = maturity_seconds
+            .checked_add(holding_dollar_seconds(asset.purchase_price, asset.maturity_date, clock.unix_timestamp))
+            .ok_or(ErrorCode::MathOverflow)?;
     }
     
-    // Apply daily P&L
-    if net_daily_pnl >= 0 {
-        new_total_assets = new_total_assets.checked_add(net_daily_pnl as u64).ok_or(ErrorCode::MathOverflow)?;
-    } else {
-        new_total_assets = new_total_assets.checked_sub((-net_daily_pnl) as u64).ok_or(ErrorCode::MathOverflow)?;
+    // Held assets must be backed by a fresh custodian statement; treasury cash is held on chain
+    if fund_state.custodian != Pubkey::default() {
+        let attestation = ctx.accounts.custodian_attestation.as_ref().ok_or(ErrorCode::MissingAttestation)?;
+        validate_custodian_attestation(
+            attestation,
+            &fund_state.key(),
+            &fund_state.custodian,
+            clock.unix_timestamp,
+            fund_state.max_attestation_age,
+            held_par,
+            fund_state.attestation_tolerance_bps,
+        )?;
     }
     
+    // Gross assets are cash plus the stored value of every held asset and the treasury
+    let gross_assets = fund_state.cash_reserves
+        .checked_mul(100) // Convert USDC to 8 decimals
+        .and_then(|cash| cash.checked_add(new_fixed_income_value))
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    // Accrue management fees on net assets for the time since the last update
+    let net_assets = gross_assets
+        .checked_sub(fund_state.accrued_fees_payable)
+        .and_then(|assets| assets.checked_sub(fund_state.accrued_performance_fee))
+        .ok_or(ErrorCode::MathOverflow)?;
+    let management_fee = calculate_management_fee(net_assets, fund_state.management_fee_bps, time_since_last_update)?;
+    fund_state.accrued_fees_payable = fund_state.accrued_fees_payable
+        .checked_add(management_fee)
+        .ok_or(ErrorCode::MathOverflow)?;
+    
+    // Re-accrue the period's performance fee from scratch on gains above the hurdle
+    let assets_before_performance_fee = net_assets
+        .checked_sub(management_fee)
+        .and_then(|assets| assets.checked_add(fund_state.accrued_performance_fee))
+        .ok_or(ErrorCode::MathOverflow)?;
+    let performance_fee = if fund_state.performance_fee_bps > 0 && fund_state.total_shares > 0 {
+        let nav_before_fee = (assets_before_performance_fee as u128) * 100_000_000 / (fund_state.total_shares as u128);
+        require!(nav_before_fee <= u64::MAX as u128, ErrorCode::MathOverflow);
+        let hurdle_nav = calculate_hurdle_nav(
+            fund_state.high_water_mark,
+            fund_state.hurdle_rate_bps,
+            clock.unix_timestamp - fund_state.performance_period_start,
+        )?;
+        calculate_performance_fee(
+            nav_before_fee as u64,
+            hurdle_nav,
+            fund_state.total_shares,
+            fund_state.performance_fee_bps,
+        )?
+    } else {
+        0
+    };
+    fund_state.accrued_performance_fee = performance_fee;
+    
+    // Total assets are net of fees payable and the accrued performance fee
+    let new_total_assets = assets_before_performance_fee.checked_sub(performance_fee).ok_or(ErrorCode::MathOverflow)?;
+    let daily_pnl = (new_total_assets as i128) - (fund_state.total_assets as i128);
+    
     // Calculate new NAV per share
+    // total_assets (8 decimals) * 10^8 / total_shares (8 decimals) = NAV (8 decimals)
+    let previous_nav = fund_state.nav_per_share;
     if fund_state.total_shares > 0 {
-        fund_state.nav_per_share = new_total_assets.checked_div(fund_state.total_shares).ok_or(ErrorCode::MathOverflow)?;
+        let nav = (new_total_assets as u128) * 100_000_000 / (fund_state.total_shares as u128);
+        require!(nav <= u64::MAX as u128, ErrorCode::MathOverflow);
+        fund_state.nav_per_share = nav as u64;
     }
     
     // Update fund state
     fund_state.total_assets = new_total_assets;
+    fund_state.fixed_income_value = new_fixed_income_value;
     fund_state.last_nav_update = clock.unix_timestamp;
+    fund_state.nav_sequence = fund_state.nav_sequence.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.orders_awaiting_settlement = fund_state.orders_awaiting_nav;
+    fund_state.orders_awaiting_nav = 0;
+    fund_state.net_flows = 0; // Swing thresholds apply to flows within one pricing period
+    fund_state.period_redemptions = 0; // The redemption gate reopens on the new period's assets
+    fund_state.gate_period_assets = new_total_assets;
+    refresh_portfolio_risk(fund_state, clock.unix_timestamp)?;
+    set_weighted_average_days(fund_state, reset_seconds, maturity_seconds)?;
+    
+    // At the end of the period the accrued performance fee becomes payable and resets the high-water mark
+    let period_end = fund_state.performance_period_start
+        .checked_add(fund_state.crystallization_period)
+        .ok_or(ErrorCode::MathOverflow)?;
+    if fund_state.performance_fee_bps > 0 && clock.unix_timestamp >= period_end {
+        let crystallized = fund_state.accrued_performance_fee;
+        fund_state.accrued_fees_payable = fund_state.accrued_fees_payable
+            .checked_add(crystallized)
+            .ok_or(ErrorCode::MathOverflow)?;
+        fund_state.accrued_performance_fee = 0;
+        fund_state.high_water_mark = fund_state.high_water_mark.max(fund_state.nav_per_share);
+        
+        emit!(PerformanceFeeCrystallized {
+            amount: crystallized,
+            high_water_mark: fund_state.high_water_mark,
+            period_start: fund_state.performance_period_start,
+            timestamp: clock.unix_timestamp,
+        });
+        
+        fund_state.performance_period_start = clock.unix_timestamp;
+    }
     
     // Update total yield distributed if positive P&L
-    if net_daily_pnl > 0 {
-        fund_state.total_yield_distributed = fund_state.total_yield_distributed.checked_add(net_daily_pnl as u64).ok_or(ErrorCode::MathOverflow)?;
+    if daily_pnl > 0 {
+        fund_state.total_yield_distributed = fund_state.total_yield_distributed.checked_add(daily_pnl as u64).ok_or(ErrorCode::MathOverflow)?;
     }
     
+    emit!(NAVUpdated {
+        new_nav: fund_state.nav_per_share,
+        previous_nav,
+        nav_change: (fund_state.nav_per_share as i128) - (previous_nav as i128),
+        total_assets: new_total_assets,
+        asset_value: new_fixed_income_value,
+        cash_reserves: fund_state.cash_reserves,
+        daily_pnl: daily_pnl as i64,
+        management_fee,
+        performance_fee,
+        timestamp: clock.unix_timestamp,
+    });
+    
     msg!("NAV updated to: {} (${:.8})", fund_state.nav_per_share, fund_state.nav_per_share as f64 / 100_000_000.0);
     
     Ok(())
-} 
\ No newline at end of file
+}
+
+/// Key of the price override co-signer, which must be the fund's configured authority
+pub fn price_override_signer(signer: &Option<Signer>, fund_state: &FundState) -> Result<Option<Pubkey>> {
+    match signer {
+        Some(signer) => {
+            require_keys_eq!(signer.key(), fund_state.price_override_authority, ErrorCode::UnauthorizedAccess);
+            Ok(Some(signer.key()))
+        }
+        None => Ok(None),
+    }
+}
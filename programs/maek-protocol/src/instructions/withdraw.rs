@@ -2,20 +2,21 @@ use anchor_lang::prelude::*;
 use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer, Burn};
 use crate::state::*;
 use crate::error::ErrorCode;
+use crate::events::WithdrawalMade;
 use crate::utils::calculations::*;
 
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
     #[account(
         mut,
-        seeds = [b"fund_state"],
+        seeds = [b"fund_state", fund_state.fund_id.to_le_bytes().as_ref()],
         bump = fund_state.bump
     )]
     pub fund_state: Account<'info, FundState>,
     
     #[account(
         mut,
-        seeds = [b"user_account", user.key().as_ref()],
+        seeds = [b"user_account", fund_state.key().as_ref(), user.key().as_ref()],
         bump = user_account.bump
     )]
     pub user_account: Account<'info, UserFundAccount>,
@@ -63,13 +64,40 @@ pub fn withdraw(ctx: Context<Withdraw>, fund_tokens: u64) -> Result<()> {
     
     // Validate fund is not paused
     require!(!fund_state.is_paused, ErrorCode::FundPaused);
+    require!(!fund_state.forward_pricing, ErrorCode::ForwardPricingActive);
     
     // Validate withdrawal amount
     require!(fund_tokens > 0, ErrorCode::WithdrawAmountZero);
     require!(user_account.fund_tokens >= fund_tokens, ErrorCode::InsufficientFundTokens);
     
-    // Calculate USDC amount to withdraw
-    let usdc_amount = calculate_withdrawal_amount(fund_tokens, fund_state.nav_per_share)?;
+    // Queued redemptions are paid first
+    require!(fund_state.queued_redemption_tokens == 0, ErrorCode::RedemptionsQueued);
+    
+    // Swing the price down if net outflows since the last NAV update exceed the threshold
+    let nav_value = calculate_withdrawal_amount(fund_tokens, fund_state.nav_per_share, 0)?;
+    let nav_value_scaled = nav_value.checked_mul(100).ok_or(ErrorCode::MathOverflow)?; // Convert to 8 decimals
+    
+    // Redemptions this period are capped by the gate, valued at NAV
+    require!(
+        nav_value_scaled <= fund_state.remaining_redemption_capacity()?,
+        ErrorCode::RedemptionGateExceeded
+    );
+    let period_redemptions = fund_state.period_redemptions.checked_add(nav_value_scaled).ok_or(ErrorCode::MathOverflow)?;
+    
+    let net_flows = fund_state.net_flows
+        .checked_sub(i64::try_from(nav_value_scaled).map_err(|_| ErrorCode::MathOverflow)?)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let swing_factor_bps = calculate_swing_factor(
+        net_flows.min(0).unsigned_abs(),
+        fund_state.total_assets,
+        fund_state.outflow_swing_threshold_bps,
+        fund_state.outflow_swing_factor_bps,
+    );
+    
+    // Calculate USDC amount to withdraw; the levy is the value at NAV that is not paid out
+    let usdc_amount = calculate_withdrawal_amount(fund_tokens, fund_state.nav_per_share, swing_factor_bps)?;
+    let swing_levy = nav_value.checked_sub(usdc_amount).ok_or(ErrorCode::MathOverflow)?;
+    let usdc_amount_scaled = usdc_amount.checked_mul(100).ok_or(ErrorCode::MathOverflow)?; // Convert 6 to 8 decimals
     
     // Check liquidity
     require!(fund_state.cash_reserves >= usdc_amount, ErrorCode::InsufficientLiquidity);
@@ -86,7 +114,8 @@ pub fn withdraw(ctx: Context<Withdraw>, fund_tokens: u64) -> Result<()> {
     token::burn(burn_ctx, fund_tokens)?;
     
     // Transfer USDC from vault to user
-    let seeds = &[b"fund_state".as_ref(), &[fund_state.bump]];
+    let fund_id_bytes = fund_state.fund_id.to_le_bytes();
+    let seeds = &[b"fund_state".as_ref(), fund_id_bytes.as_ref(), &[fund_state.bump]];
     let signer = &[&seeds[..]];
     
     let transfer_ctx = CpiContext::new_with_signer(
@@ -101,16 +130,29 @@ pub fn withdraw(ctx: Context<Withdraw>, fund_tokens: u64) -> Result<()> {
     token::transfer(transfer_ctx, usdc_amount)?;
     
     // Update fund state
-    fund_state.total_assets = fund_state.total_assets.checked_sub(usdc_amount * 100).ok_or(ErrorCode::MathOverflow)?; // Convert 6 to 8 decimals
+    fund_state.total_assets = fund_state.total_assets.checked_sub(usdc_amount_scaled).ok_or(ErrorCode::MathOverflow)?;
     fund_state.total_shares = fund_state.total_shares.checked_sub(fund_tokens).ok_or(ErrorCode::MathOverflow)?;
     fund_state.cash_reserves = fund_state.cash_reserves.checked_sub(usdc_amount).ok_or(ErrorCode::MathOverflow)?;
+    fund_state.net_flows = net_flows;
+    fund_state.period_redemptions = period_redemptions;
+    fund_state.total_swing_levies = fund_state.total_swing_levies.checked_add(swing_levy.checked_mul(100).ok_or(ErrorCode::MathOverflow)?).ok_or(ErrorCode::MathOverflow)?; // Retained by the fund
     
     // Update user account
     user_account.fund_tokens = user_account.fund_tokens.checked_sub(fund_tokens).ok_or(ErrorCode::MathOverflow)?;
-    user_account.total_withdrawn = user_account.total_withdrawn.checked_add(usdc_amount * 100).ok_or(ErrorCode::MathOverflow)?; // Convert to 8 decimals
+    user_account.total_withdrawn = user_account.total_withdrawn.checked_add(usdc_amount_scaled).ok_or(ErrorCode::MathOverflow)?;
     user_account.last_withdrawal_time = clock.unix_timestamp;
     user_account.withdrawal_count = user_account.withdrawal_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
     
+    emit!(WithdrawalMade {
+        user: ctx.accounts.user.key(),
+        fund_tokens_burned: fund_tokens,
+        amount_usdc: usdc_amount,
+        nav_per_share: fund_state.nav_per_share,
+        swing_factor_bps,
+        swing_levy,
+        timestamp: clock.unix_timestamp,
+    });
+    
     msg!("Withdrawal successful: {} fund tokens for {} USDC", fund_tokens, usdc_amount);
     
     Ok(())
@@ -9,19 +9,23 @@ pub mod state;
 pub mod utils;
 
 use instructions::*;
+use state::{AssetPurchase, AssetValuation, FixedIncomeAssetType, HoldingsStatement, LiquidityStatus};
 
 #[program]
 pub mod maek_protocol {
     use super::*;
 
-    // Temporarily commented out to reduce stack usage
-    // pub fn initialize_fund(
-    //     ctx: Context<InitializeFund>,
-    //     management_fee_bps: u16,
-    //     target_liquidity_ratio: u8,
-    // ) -> Result<()> {
-    //     instructions::initialize_fund(ctx, management_fee_bps, target_liquidity_ratio)
-    // }
+    pub fn initialize_registry(ctx: Context<InitializeRegistry>) -> Result<()> {
+        instructions::initialize_registry(ctx)
+    }
+
+    pub fn initialize_fund(
+        ctx: Context<InitializeFund>,
+        management_fee_bps: u16,
+        target_liquidity_ratio: u8,
+    ) -> Result<()> {
+        instructions::initialize_fund(ctx, management_fee_bps, target_liquidity_ratio)
+    }
 
     pub fn deposit(ctx: Context<Deposit>, amount: u64) -> Result<()> {
         instructions::deposit(ctx, amount)
@@ -31,26 +35,183 @@ pub mod maek_protocol {
         instructions::withdraw(ctx, fund_tokens)
     }
 
-    pub fn update_nav(
-        ctx: Context<UpdateNAV>,
-        new_asset_valuations: Vec<AssetValuation>,
-        net_daily_pnl: i64,
+    pub fn update_nav(ctx: Context<UpdateNAV>, new_asset_valuations: Vec<AssetValuation>) -> Result<()> {
+        instructions::update_nav(ctx, new_asset_valuations)
+    }
+
+    pub fn invest_in_fixed_income<'info>(
+        ctx: Context<'_, '_, '_, 'info, InvestFixedIncome<'info>>,
+        assets: Vec<AssetPurchase>,
+    ) -> Result<()> {
+        instructions::invest_in_fixed_income(ctx, assets)
+    }
+
+    pub fn register_asset(
+        ctx: Context<RegisterAsset>,
+        purchase: AssetPurchase,
+    ) -> Result<()> {
+        instructions::register_asset(ctx, purchase)
+    }
+
+    pub fn handle_asset_maturity(ctx: Context<HandleMaturity>) -> Result<()> {
+        instructions::handle_asset_maturity(ctx)
+    }
+
+    pub fn sell_asset(ctx: Context<SellAsset>, sale_price: u64) -> Result<()> {
+        instructions::sell_asset(ctx, sale_price)
+    }
+
+    pub fn mark_asset_default(ctx: Context<MarkAssetDefault>, write_down_bps: u16) -> Result<()> {
+        instructions::mark_asset_default(ctx, write_down_bps)
+    }
+
+    pub fn record_default_recovery(
+        ctx: Context<RecordDefaultRecovery>,
+        recovery_amount: u64,
+        close_position: bool,
+    ) -> Result<()> {
+        instructions::record_default_recovery(ctx, recovery_amount, close_position)
+    }
+
+    pub fn accrue_interest(ctx: Context<AccrueInterest>) -> Result<()> {
+        instructions::accrue_interest(ctx)
+    }
+
+    pub fn claim_fees(ctx: Context<ClaimFees>) -> Result<()> {
+        instructions::claim_fees(ctx)
+    }
+
+    pub fn request_redemption(ctx: Context<RequestRedemption>, fund_tokens: u64) -> Result<()> {
+        instructions::request_redemption(ctx, fund_tokens)
+    }
+
+    pub fn process_redemptions<'info>(ctx: Context<'_, '_, '_, 'info, ProcessRedemptions<'info>>) -> Result<()> {
+        instructions::process_redemptions(ctx)
+    }
+
+    pub fn place_subscription_order(ctx: Context<PlaceSubscriptionOrder>, amount: u64) -> Result<()> {
+        instructions::place_subscription_order(ctx, amount)
+    }
+
+    pub fn place_redemption_order(ctx: Context<PlaceRedemptionOrder>, fund_tokens: u64) -> Result<()> {
+        instructions::place_redemption_order(ctx, fund_tokens)
+    }
+
+    pub fn settle_orders<'info>(ctx: Context<'_, '_, '_, 'info, SettleOrders<'info>>) -> Result<()> {
+        instructions::settle_orders(ctx)
+    }
+
+    pub fn get_redemption_capacity(ctx: Context<FundStatus>) -> Result<u64> {
+        instructions::get_redemption_capacity(ctx)
+    }
+
+    pub fn get_liquidity_status(ctx: Context<FundStatus>) -> Result<LiquidityStatus> {
+        instructions::get_liquidity_status(ctx)
+    }
+
+    pub fn manage_liquidity(ctx: Context<ManageLiquidity>) -> Result<()> {
+        instructions::manage_liquidity(ctx)
+    }
+
+    pub fn initialize_treasury_vault(ctx: Context<InitializeTreasuryVault>) -> Result<()> {
+        instructions::initialize_treasury_vault(ctx)
+    }
+
+    pub fn sweep_to_treasury(ctx: Context<SweepTreasury>, amount: u64) -> Result<()> {
+        instructions::sweep_to_treasury(ctx, amount)
+    }
+
+    pub fn sweep_from_treasury(ctx: Context<SweepTreasury>, amount: u64) -> Result<()> {
+        instructions::sweep_from_treasury(ctx, amount)
+    }
+
+    pub fn submit_attestation(ctx: Context<SubmitAttestation>, statement: HoldingsStatement) -> Result<()> {
+        instructions::submit_attestation(ctx, statement)
+    }
+
+    pub fn set_asset_price_feed(ctx: Context<SetAssetPriceFeed>, price_feed: Pubkey) -> Result<()> {
+        instructions::set_asset_price_feed(ctx, price_feed)
+    }
+
+    pub fn refresh_asset_price(ctx: Context<RefreshAssetPrice>) -> Result<()> {
+        instructions::refresh_asset_price(ctx)
+    }
+
+    pub fn pause_fund(ctx: Context<AdminPause>, reason: String) -> Result<()> {
+        instructions::pause_fund(ctx, reason)
+    }
+
+    pub fn unpause_fund(ctx: Context<AdminPause>) -> Result<()> {
+        instructions::unpause_fund(ctx)
+    }
+
+    pub fn set_max_portfolio_duration(ctx: Context<AdminConfig>, max_portfolio_duration: u32) -> Result<()> {
+        instructions::set_max_portfolio_duration(ctx, max_portfolio_duration)
+    }
+
+    pub fn set_maturity_limits(ctx: Context<AdminConfig>, max_wam_days: u16, max_wal_days: u16) -> Result<()> {
+        instructions::set_maturity_limits(ctx, max_wam_days, max_wal_days)
+    }
+
+    pub fn set_fee_recipient(ctx: Context<AdminConfig>, fee_recipient: Pubkey) -> Result<()> {
+        instructions::set_fee_recipient(ctx, fee_recipient)
+    }
+
+    pub fn set_redemption_gate(ctx: Context<AdminConfig>, redemption_gate_bps: u16) -> Result<()> {
+        instructions::set_redemption_gate(ctx, redemption_gate_bps)
+    }
+
+    pub fn set_custodian(
+        ctx: Context<RegistryConfig>,
+        custodian: Pubkey,
+        max_attestation_age: i64,
+        attestation_tolerance_bps: u16,
     ) -> Result<()> {
-        instructions::update_nav(ctx, new_asset_valuations, net_daily_pnl)
+        instructions::set_custodian(ctx, custodian, max_attestation_age, attestation_tolerance_bps)
     }
 
-    // Temporarily commented out to reduce stack usage
-    // pub fn invest_in_fixed_income(
-    //     ctx: Context<InvestFixedIncome>,
-    //     assets: Vec<AssetPurchase>,
-    // ) -> Result<()> {
-    //     instructions::invest_in_fixed_income(ctx, assets)
-    // }
+    pub fn set_price_deviation_limit(
+        ctx: Context<RegistryConfig>,
+        asset_type: FixedIncomeAssetType,
+        max_deviation_bps: u16,
+    ) -> Result<()> {
+        instructions::set_price_deviation_limit(ctx, asset_type, max_deviation_bps)
+    }
+
+    pub fn set_price_override_authority(ctx: Context<RegistryConfig>, price_override_authority: Pubkey) -> Result<()> {
+        instructions::set_price_override_authority(ctx, price_override_authority)
+    }
 
-    // pub fn handle_asset_maturity(
-    //     ctx: Context<HandleMaturity>,
-    //     asset_id: Pubkey,
-    // ) -> Result<()> {
-    //     instructions::handle_asset_maturity(ctx, asset_id)
-    // }
-} 
\ No newline at end of file
+    pub fn set_settlement_account(ctx: Context<RegistryConfig>, settlement_account: Pubkey) -> Result<()> {
+        instructions::set_settlement_account(ctx, settlement_account)
+    }
+
+    pub fn set_forward_pricing(ctx: Context<AdminConfig>, enabled: bool) -> Result<()> {
+        instructions::set_forward_pricing(ctx, enabled)
+    }
+
+    pub fn set_performance_fee(
+        ctx: Context<AdminConfig>,
+        performance_fee_bps: u16,
+        hurdle_rate_bps: u16,
+        crystallization_period: i64,
+    ) -> Result<()> {
+        instructions::set_performance_fee(ctx, performance_fee_bps, hurdle_rate_bps, crystallization_period)
+    }
+
+    pub fn set_swing_pricing(
+        ctx: Context<AdminConfig>,
+        inflow_swing_threshold_bps: u16,
+        outflow_swing_threshold_bps: u16,
+        inflow_swing_factor_bps: u16,
+        outflow_swing_factor_bps: u16,
+    ) -> Result<()> {
+        instructions::set_swing_pricing(
+            ctx,
+            inflow_swing_threshold_bps,
+            outflow_swing_threshold_bps,
+            inflow_swing_factor_bps,
+            outflow_swing_factor_bps,
+        )
+    }
+}
@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+
+/// Holdings statement signed by the custodian; its Borsh encoding is the signed message
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct HoldingsStatement {
+    /// Fund the statement is issued for
+    pub fund: Pubkey,
+    
+    /// Date the holdings were struck (unix timestamp)
+    pub statement_date: i64,
+    
+    /// Total par value of securities held in custody (8 decimals)
+    pub total_par: u64,
+    
+    /// Hash of the custodian's position list
+    pub positions_hash: [u8; 32],
+}
+
+#[account]
+#[derive(Default)]
+pub struct CustodianAttestation {
+    /// Fund the attestation covers
+    pub fund: Pubkey,
+    
+    /// Custodian key that signed the statement
+    pub custodian: Pubkey,
+    
+    /// Date the holdings were struck (unix timestamp)
+    pub statement_date: i64,
+    
+    /// Total par value of securities held in custody (8 decimals)
+    pub total_par: u64,
+    
+    /// Hash of the custodian's position list
+    pub positions_hash: [u8; 32],
+    
+    /// Time the attestation was recorded on chain
+    pub recorded_at: i64,
+    
+    /// Account bump
+    pub bump: u8,
+}
+
+impl CustodianAttestation {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // fund
+        32 + // custodian
+        8 + // statement_date
+        8 + // total_par
+        32 + // positions_hash
+        8 + // recorded_at
+        1; // bump
+}
@@ -1,7 +1,8 @@
 use anchor_lang::prelude::*;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum FixedIncomeAssetType {
+    #[default]
     TreasuryBill,
     TreasuryNote,
     TreasuryBond,
@@ -13,29 +14,30 @@ pub enum FixedIncomeAssetType {
     MortgageBackedSecurity,
 }
 
-impl Default for FixedIncomeAssetType {
-    fn default() -> Self {
-        FixedIncomeAssetType::TreasuryBill
-    }
-}
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
 pub enum AssetStatus {
+    #[default]
     Active,
     Matured,
     Sold,
     DefaultRisk,
+    WrittenOff,
 }
 
-impl Default for AssetStatus {
-    fn default() -> Self {
-        AssetStatus::Active
-    }
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DayCountConvention {
+    #[default]
+    Actual360,
+    Actual365,
+    Thirty360,
 }
 
 #[account]
 #[derive(Default)]
 pub struct FixedIncomeAsset {
+    /// Fund holding this asset
+    pub fund: Pubkey,
+    
     /// Asset identifier (CUSIP, ISIN, or custom ID)
     pub asset_id: [u8; 12],
     
@@ -58,16 +60,41 @@ pub struct FixedIncomeAsset {
     /// Updated daily via oracle
     pub current_value: u64,
     
+    /// Value before the latest valuation (8 decimals)
+    pub previous_value: u64,
+    
+    /// Pyth-style price account valuing the holding (default = valued manually at NAV updates)
+    pub price_feed: Pubkey,
+    
+    /// Time of the price behind current_value
+    pub last_valued_at: i64,
+    
     /// Annualized yield rate (4 decimals)
     /// e.g., 5000 = 5.00%
     pub yield_rate: u32,
     
+    /// Annual coupon rate (same scale as yield_rate, 0 for discount instruments)
+    pub coupon_rate: u32,
+    
+    /// Coupon payments per year (1, 2, 4 or 12; 0 for discount instruments)
+    pub coupon_frequency: u8,
+    
+    /// Next scheduled coupon date (0 for discount instruments)
+    /// Coupon dates roll back from maturity in steps of 12 / coupon_frequency months
+    pub next_coupon_date: i64,
+    
+    /// Coupon rate resets to the reference rate on each coupon date
+    pub floating_rate: bool,
+    
     /// Interest accrued to date (8 decimals)
     pub accrued_interest: u64,
     
     /// Last interest calculation date
     pub last_interest_calculation: i64,
     
+    /// Day-count convention used to accrue interest
+    pub day_count_convention: DayCountConvention,
+    
     /// Asset status
     pub status: AssetStatus,
     
@@ -83,6 +110,15 @@ pub struct FixedIncomeAsset {
     /// Original duration at purchase (days)
     pub original_duration: u16,
     
+    /// Macaulay duration at purchase (10_000 = 1 year)
+    pub macaulay_duration: u32,
+    
+    /// Modified duration at purchase (10_000 = 1 year)
+    pub modified_duration: u32,
+    
+    /// Convexity at purchase (10_000 = 1 year squared)
+    pub convexity: u32,
+    
     /// Sector classification (Government = 1, Financial = 2, etc.)
     pub sector: u8,
     
@@ -101,6 +137,7 @@ pub struct FixedIncomeAsset {
 
 impl FixedIncomeAsset {
     pub const LEN: usize = 8 + // discriminator
+        32 + // fund
         12 + // asset_id
         1 + // asset_type
         8 + // face_value
@@ -108,19 +145,40 @@ impl FixedIncomeAsset {
         8 + // purchase_date
         8 + // maturity_date
         8 + // current_value
+        8 + // previous_value
+        32 + // price_feed
+        8 + // last_valued_at
         4 + // yield_rate
+        4 + // coupon_rate
+        1 + // coupon_frequency
+        8 + // next_coupon_date
+        1 + // floating_rate
         8 + // accrued_interest
         8 + // last_interest_calculation
+        1 + // day_count_convention
         1 + // status
         2 + // days_to_maturity
         1 + // credit_rating
         32 + // issuer
         2 + // original_duration
+        4 + // macaulay_duration
+        4 + // modified_duration
+        4 + // convexity
         1 + // sector
         1 + // country
         1 + // currency
         8 + // reserved
         1; // bump
+    
+    /// Date the holding's rate is next fixed: the next coupon date for floating-rate
+    /// instruments, final maturity otherwise. WAM runs to this date, WAL to maturity
+    pub fn interest_reset_date(&self) -> i64 {
+        if self.floating_rate && self.next_coupon_date != 0 {
+            std::cmp::min(self.next_coupon_date, self.maturity_date)
+        } else {
+            self.maturity_date
+        }
+    }
 }
 
 // Helper structures for instructions
@@ -128,7 +186,6 @@ impl FixedIncomeAsset {
 pub struct AssetValuation {
     pub asset_id: Pubkey,
     pub current_value: u64,
-    pub accrued_interest: u64,
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -139,6 +196,9 @@ pub struct AssetPurchase {
     pub purchase_price: u64,
     pub maturity_date: i64,
     pub yield_rate: u32,
+    pub coupon_rate: u32,
+    pub coupon_frequency: u8,
+    pub floating_rate: bool,
     pub credit_rating: u8,
     pub issuer: Pubkey,
     pub sector: u8,
@@ -0,0 +1,22 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct FundRegistry {
+    /// Authority allowed to create new funds
+    pub authority: Pubkey,
+    
+    /// Number of funds created so far
+    /// Also the id assigned to the next fund
+    pub fund_count: u64,
+    
+    /// Account bump
+    pub bump: u8,
+}
+
+impl FundRegistry {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // authority
+        8 + // fund_count
+        1; // bump
+}
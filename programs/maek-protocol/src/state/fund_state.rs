@@ -1,7 +1,50 @@
 use anchor_lang::prelude::*;
+use crate::state::FixedIncomeAssetType;
+use crate::utils::calculations::{
+    calculate_liquidity_gap, calculate_liquidity_ratio, calculate_redemption_capacity, default_price_deviation_bps,
+};
+
+/// Default maximum portfolio duration (10_000 = 1 year)
+pub const DEFAULT_MAX_PORTFOLIO_DURATION: u32 = 10_000;
+
+/// Default weighted-average maturity limit in days
+pub const DEFAULT_MAX_WAM_DAYS: u16 = 60;
+
+/// Default weighted-average life limit in days
+pub const DEFAULT_MAX_WAL_DAYS: u16 = 120;
+
+/// Number of FixedIncomeAssetType variants
+pub const ASSET_TYPE_COUNT: usize = 9;
+
+/// Default valuation deviation limits for every asset type, indexed by asset type
+pub fn default_price_deviation_limits() -> [u16; ASSET_TYPE_COUNT] {
+    [
+        FixedIncomeAssetType::TreasuryBill,
+        FixedIncomeAssetType::TreasuryNote,
+        FixedIncomeAssetType::TreasuryBond,
+        FixedIncomeAssetType::CorporateBond,
+        FixedIncomeAssetType::CommercialPaper,
+        FixedIncomeAssetType::CertificateOfDeposit,
+        FixedIncomeAssetType::MunicipalBond,
+        FixedIncomeAssetType::AssetBackedSecurity,
+        FixedIncomeAssetType::MortgageBackedSecurity,
+    ]
+    .map(default_price_deviation_bps)
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum LiquidityAction {
+    #[default]
+    Hold,
+    RaiseCash,
+    DeployCash,
+}
 
 #[account]
 pub struct FundState {
+    /// Fund identifier assigned by the registry
+    pub fund_id: u64,
+    
     /// Administrative authority (multisig)
     pub admin_authority: Pubkey,
     
@@ -17,6 +60,9 @@ pub struct FundState {
     /// Treasury vault for fixed income investments
     pub treasury_vault: Pubkey,
     
+    /// Custodian's USDC account that purchases settle into, appointed by the registry authority
+    pub settlement_account: Pubkey,
+    
     /// Total assets under management in USD (8 decimals)
     /// Includes cash + fixed income asset value
     pub total_assets: u64,
@@ -35,14 +81,167 @@ pub struct FundState {
     pub cash_reserves: u64,
     
     /// Total value of fixed income assets (8 decimals)
+    /// Includes USDC held in the treasury vault for settlement
     pub fixed_income_value: u64,
     
+    /// USDC swept to the treasury vault and not yet returned (6 decimals)
+    pub treasury_cash: u64,
+    
+    /// Number of held assets (Active or DefaultRisk status)
+    pub active_asset_count: u32,
+    
+    /// Sum of modified duration x book value over held assets
+    pub weighted_duration_sum: u128,
+    
+    /// Value-weighted portfolio modified duration (10_000 = 1 year)
+    /// Refreshed on portfolio changes and NAV updates
+    pub portfolio_duration: u32,
+    
+    /// Maximum portfolio duration accepted on purchase (10_000 = 1 year)
+    pub max_portfolio_duration: u32,
+    
+    /// Book value (purchase price) of held assets (8 decimals)
+    pub held_book_value: u64,
+    
+    /// Sum of book value x maturity timestamp over held assets
+    /// Weighted-average days to maturity decay with time without rebalancing this sum
+    pub maturity_weighted_sum: u128,
+    
+    /// Sum of book value x interest reset date over held assets
+    /// Floating-rate holdings count to their next reset, fixed-rate holdings to maturity
+    pub reset_weighted_sum: u128,
+    
+    /// Dollar-weighted average maturity in days to the next interest reset, cash counting as overnight
+    /// Refreshed on portfolio changes and NAV updates
+    pub weighted_average_maturity: u32,
+    
+    /// Dollar-weighted average life in days to final maturity
+    pub weighted_average_life: u32,
+    
+    /// Maximum weighted-average maturity accepted on purchase (days)
+    pub max_wam_days: u16,
+    
+    /// Maximum weighted-average life accepted on purchase (days)
+    pub max_wal_days: u16,
+    
     /// Management fee in basis points (15 = 0.15%)
     pub management_fee_bps: u16,
     
+    /// Management fees accrued but not yet paid (8 decimals)
+    /// A liability already deducted from total_assets
+    pub accrued_fees_payable: u64,
+    
+    /// Owner of the USDC account that receives claimed fees
+    pub fee_recipient: Pubkey,
+    
+    /// Cumulative management fees paid out (8 decimals)
+    pub total_fees_paid: u64,
+    
+    /// Performance fee on gains above the hurdle in basis points (0 = disabled)
+    pub performance_fee_bps: u16,
+    
+    /// Annual hurdle return the NAV must beat before a performance fee accrues (basis points)
+    pub hurdle_rate_bps: u16,
+    
+    /// Length of a performance fee crystallization period in seconds
+    pub crystallization_period: i64,
+    
+    /// Start of the current crystallization period
+    pub performance_period_start: i64,
+    
+    /// Highest NAV per share at which a performance fee has crystallized (8 decimals)
+    pub high_water_mark: u64,
+    
+    /// Performance fee accrued in the current period, not yet crystallized (8 decimals)
+    /// Recomputed on every NAV update and already deducted from total_assets
+    pub accrued_performance_fee: u64,
+    
+    /// Net subscriptions less redemptions since the last NAV update (8 decimals)
+    pub net_flows: i64,
+    
+    /// Net inflow above which subscriptions are swung up (basis points of total_assets)
+    pub inflow_swing_threshold_bps: u16,
+    
+    /// Net outflow above which redemptions are swung down (basis points of total_assets)
+    pub outflow_swing_threshold_bps: u16,
+    
+    /// Price adjustment applied to swung subscriptions in basis points (0 = disabled)
+    pub inflow_swing_factor_bps: u16,
+    
+    /// Price adjustment applied to swung redemptions in basis points (0 = disabled)
+    pub outflow_swing_factor_bps: u16,
+    
+    /// Cumulative anti-dilution levies retained by the fund (8 decimals)
+    pub total_swing_levies: u64,
+    
+    /// Sequence of the oldest unpaid redemption request
+    pub redemption_queue_head: u64,
+    
+    /// Sequence assigned to the next redemption request
+    pub redemption_queue_tail: u64,
+    
+    /// Fund tokens held in escrow by queued redemption requests (8 decimals)
+    pub queued_redemption_tokens: u64,
+    
+    /// Forward pricing: subscriptions and redemptions are placed as orders and
+    /// struck at the next NAV instead of the last published one
+    pub forward_pricing: bool,
+    
+    /// Sequence assigned to the next pending order
+    pub order_sequence: u64,
+    
+    /// USDC received for unsettled subscription orders (6 decimals)
+    /// Held in the vault but excluded from cash_reserves until settled
+    pub pending_subscriptions: u64,
+    
+    /// Fund tokens escrowed by unsettled redemption orders (8 decimals)
+    pub pending_redemption_tokens: u64,
+    
+    /// Number of NAV updates struck; an order settles at the update after the one it was placed under
+    pub nav_sequence: u64,
+    
+    /// Orders placed since the last NAV update, struck at the next one
+    pub orders_awaiting_nav: u64,
+    
+    /// Orders struck at the current NAV but not yet settled; the next NAV update waits for them
+    pub orders_awaiting_settlement: u64,
+    
+    /// Share of period-start total assets that may be redeemed per NAV period (basis points, 0 = no gate)
+    pub redemption_gate_bps: u16,
+    
+    /// Total assets at the start of the current NAV period, the base of the redemption gate (8 decimals)
+    pub gate_period_assets: u64,
+    
+    /// Value redeemed since the last NAV update at NAV (8 decimals)
+    pub period_redemptions: u64,
+    
     /// Target liquidity ratio (25 = 25%)
     pub target_liquidity_ratio: u8,
     
+    /// Action recorded by the last liquidity review
+    pub last_liquidity_action: LiquidityAction,
+    
+    /// USDC to raise or deploy to reach the target at the last review (6 decimals)
+    pub last_liquidity_amount: u64,
+    
+    /// Last liquidity review timestamp
+    pub last_liquidity_review: i64,
+    
+    /// Custodian key whose signed holdings statement must back each NAV update (default = not required)
+    pub custodian: Pubkey,
+    
+    /// Maximum age of the custodian statement accepted by a NAV update in seconds
+    pub max_attestation_age: i64,
+    
+    /// Allowed shortfall of attested par against held par (basis points of held par)
+    pub attestation_tolerance_bps: u16,
+    
+    /// Maximum valuation move between updates, indexed by asset type (basis points)
+    pub price_deviation_limits_bps: [u16; ASSET_TYPE_COUNT],
+    
+    /// Authority whose co-signature lets a valuation exceed the deviation limit (default = none)
+    pub price_override_authority: Pubkey,
+    
     /// Emergency pause state
     pub is_paused: bool,
     
@@ -52,6 +251,9 @@ pub struct FundState {
     /// Total yield distributed to date (8 decimals)
     pub total_yield_distributed: u64,
     
+    /// Cumulative realized gain/loss from asset sales (8 decimals)
+    pub realized_pnl: i64,
+    
     /// Total number of unique depositors
     pub total_depositors: u32,
     
@@ -62,22 +264,73 @@ pub struct FundState {
 impl Default for FundState {
     fn default() -> Self {
         Self {
+            fund_id: 0,
             admin_authority: Pubkey::default(),
             fund_token_mint: Pubkey::default(),
             usdc_mint: Pubkey::default(),
             usdc_vault: Pubkey::default(),
             treasury_vault: Pubkey::default(),
+            settlement_account: Pubkey::default(),
             total_assets: 0,
             total_shares: 0,
             nav_per_share: 100_000_000, // $1.00 initial NAV
             last_nav_update: 0,
             cash_reserves: 0,
             fixed_income_value: 0,
+            treasury_cash: 0,
+            active_asset_count: 0,
+            weighted_duration_sum: 0,
+            portfolio_duration: 0,
+            max_portfolio_duration: DEFAULT_MAX_PORTFOLIO_DURATION,
+            held_book_value: 0,
+            maturity_weighted_sum: 0,
+            reset_weighted_sum: 0,
+            weighted_average_maturity: 0,
+            weighted_average_life: 0,
+            max_wam_days: DEFAULT_MAX_WAM_DAYS,
+            max_wal_days: DEFAULT_MAX_WAL_DAYS,
             management_fee_bps: 0,
+            accrued_fees_payable: 0,
+            fee_recipient: Pubkey::default(),
+            total_fees_paid: 0,
+            performance_fee_bps: 0,
+            hurdle_rate_bps: 0,
+            crystallization_period: 0,
+            performance_period_start: 0,
+            high_water_mark: 100_000_000, // $1.00 initial NAV
+            accrued_performance_fee: 0,
+            net_flows: 0,
+            inflow_swing_threshold_bps: 0,
+            outflow_swing_threshold_bps: 0,
+            inflow_swing_factor_bps: 0,
+            outflow_swing_factor_bps: 0,
+            total_swing_levies: 0,
+            redemption_queue_head: 0,
+            redemption_queue_tail: 0,
+            queued_redemption_tokens: 0,
+            forward_pricing: false,
+            order_sequence: 0,
+            pending_subscriptions: 0,
+            pending_redemption_tokens: 0,
+            nav_sequence: 0,
+            orders_awaiting_nav: 0,
+            orders_awaiting_settlement: 0,
+            redemption_gate_bps: 0,
+            gate_period_assets: 0,
+            period_redemptions: 0,
             target_liquidity_ratio: 25, // 25% default
+            last_liquidity_action: LiquidityAction::Hold,
+            last_liquidity_amount: 0,
+            last_liquidity_review: 0,
+            custodian: Pubkey::default(),
+            max_attestation_age: 0,
+            attestation_tolerance_bps: 0,
+            price_deviation_limits_bps: default_price_deviation_limits(),
+            price_override_authority: Pubkey::default(),
             is_paused: false,
             inception_date: 0,
             total_yield_distributed: 0,
+            realized_pnl: 0,
             total_depositors: 0,
             bump: 0,
         }
@@ -86,22 +339,107 @@ impl Default for FundState {
 
 impl FundState {
     pub const LEN: usize = 8 + // discriminator
+        8 + // fund_id
         32 + // admin_authority
         32 + // fund_token_mint
         32 + // usdc_mint
         32 + // usdc_vault
         32 + // treasury_vault
+        32 + // settlement_account
         8 + // total_assets
         8 + // total_shares
         8 + // nav_per_share
         8 + // last_nav_update
         8 + // cash_reserves
         8 + // fixed_income_value
+        8 + // treasury_cash
+        4 + // active_asset_count
+        16 + // weighted_duration_sum
+        4 + // portfolio_duration
+        4 + // max_portfolio_duration
+        8 + // held_book_value
+        16 + // maturity_weighted_sum
+        16 + // reset_weighted_sum
+        4 + // weighted_average_maturity
+        4 + // weighted_average_life
+        2 + // max_wam_days
+        2 + // max_wal_days
         2 + // management_fee_bps
+        8 + // accrued_fees_payable
+        32 + // fee_recipient
+        8 + // total_fees_paid
+        2 + // performance_fee_bps
+        2 + // hurdle_rate_bps
+        8 + // crystallization_period
+        8 + // performance_period_start
+        8 + // high_water_mark
+        8 + // accrued_performance_fee
+        8 + // net_flows
+        2 + // inflow_swing_threshold_bps
+        2 + // outflow_swing_threshold_bps
+        2 + // inflow_swing_factor_bps
+        2 + // outflow_swing_factor_bps
+        8 + // total_swing_levies
+        8 + // redemption_queue_head
+        8 + // redemption_queue_tail
+        8 + // queued_redemption_tokens
+        1 + // forward_pricing
+        8 + // order_sequence
+        8 + // pending_subscriptions
+        8 + // pending_redemption_tokens
+        8 + // nav_sequence
+        8 + // orders_awaiting_nav
+        8 + // orders_awaiting_settlement
+        2 + // redemption_gate_bps
+        8 + // gate_period_assets
+        8 + // period_redemptions
         1 + // target_liquidity_ratio
+        1 + // last_liquidity_action
+        8 + // last_liquidity_amount
+        8 + // last_liquidity_review
+        32 + // custodian
+        8 + // max_attestation_age
+        2 + // attestation_tolerance_bps
+        2 * ASSET_TYPE_COUNT + // price_deviation_limits_bps
+        32 + // price_override_authority
         1 + // is_paused
         8 + // inception_date
         8 + // total_yield_distributed
+        8 + // realized_pnl
         4 + // total_depositors
         1; // bump
+    
+    /// Value that can still be redeemed this NAV period before the gate closes (8 decimals)
+    pub fn remaining_redemption_capacity(&self) -> Result<u64> {
+        calculate_redemption_capacity(self.gate_period_assets, self.redemption_gate_bps, self.period_redemptions)
+    }
+    
+    /// Maximum valuation move between updates for an asset type (basis points)
+    pub fn price_deviation_limit(&self, asset_type: FixedIncomeAssetType) -> u32 {
+        self.price_deviation_limits_bps[asset_type as usize] as u32
+    }
+    
+    /// Current cash position against the target liquidity ratio
+    pub fn liquidity_status(&self) -> Result<LiquidityStatus> {
+        let (action, amount) = calculate_liquidity_gap(self.cash_reserves, self.total_assets, self.target_liquidity_ratio)?;
+        Ok(LiquidityStatus {
+            cash_reserves: self.cash_reserves,
+            total_assets: self.total_assets,
+            liquidity_ratio: calculate_liquidity_ratio(self.cash_reserves.saturating_mul(100), self.total_assets) as u8, // Convert to 8 decimals
+            target_liquidity_ratio: self.target_liquidity_ratio,
+            action,
+            amount,
+        })
+    }
+}
+
+// Helper structures for instructions
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct LiquidityStatus {
+    pub cash_reserves: u64,
+    pub total_assets: u64,
+    pub liquidity_ratio: u8,
+    pub target_liquidity_ratio: u8,
+    pub action: LiquidityAction,
+    pub amount: u64,
 } 
\ No newline at end of file
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct IssuerExposure {
+    /// Fund holding the exposure
+    pub fund: Pubkey,
+    
+    /// Issuer the exposure is tracked for
+    pub issuer: Pubkey,
+    
+    /// Book value of active holdings from this issuer (8 decimals)
+    pub total_value: u64,
+    
+    /// Number of active assets from this issuer
+    pub asset_count: u32,
+    
+    /// Account bump
+    pub bump: u8,
+}
+
+impl IssuerExposure {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // fund
+        32 + // issuer
+        8 + // total_value
+        4 + // asset_count
+        1; // bump
+}
@@ -1,7 +1,19 @@
+pub mod fund_registry;
 pub mod fund_state;
 pub mod user_account;
 pub mod fixed_income_asset;
+pub mod issuer_exposure;
+pub mod write_down_history;
+pub mod redemption_request;
+pub mod pending_order;
+pub mod custodian_attestation;
 
+pub use fund_registry::*;
 pub use fund_state::*;
 pub use user_account::*;
-pub use fixed_income_asset::*; 
\ No newline at end of file
+pub use fixed_income_asset::*;
+pub use issuer_exposure::*;
+pub use write_down_history::*;
+pub use redemption_request::*;
+pub use pending_order::*;
+pub use custodian_attestation::*;
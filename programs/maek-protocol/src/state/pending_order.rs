@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrderKind {
+    #[default]
+    Subscription,
+    Redemption,
+}
+
+#[account]
+#[derive(Default)]
+pub struct PendingOrder {
+    /// Fund the order was placed with
+    pub fund: Pubkey,
+    
+    /// Investor the order settles to
+    pub owner: Pubkey,
+    
+    /// Subscription or redemption
+    pub kind: OrderKind,
+    
+    /// USDC paid in (6 decimals) for subscriptions, fund tokens escrowed (8 decimals) for redemptions
+    pub amount: u64,
+    
+    /// Order number within the fund
+    pub sequence: u64,
+    
+    /// Time the order was placed
+    pub submitted_at: i64,
+    
+    /// Fund's NAV sequence when the order was placed; it settles at the next NAV only
+    pub nav_sequence: u64,
+    
+    /// Account bump
+    pub bump: u8,
+}
+
+impl PendingOrder {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // fund
+        32 + // owner
+        1 + // kind
+        8 + // amount
+        8 + // sequence
+        8 + // submitted_at
+        8 + // nav_sequence
+        1; // bump
+}
@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(Default)]
+pub struct RedemptionRequest {
+    /// Fund the request is queued against
+    pub fund: Pubkey,
+    
+    /// Investor paid when the request is processed
+    pub owner: Pubkey,
+    
+    /// Position in the fund's FIFO redemption queue
+    pub sequence: u64,
+    
+    /// Fund tokens still held in escrow awaiting payment (8 decimals)
+    pub fund_tokens: u64,
+    
+    /// Time the request joined the queue
+    pub requested_at: i64,
+    
+    /// Account bump
+    pub bump: u8,
+}
+
+impl RedemptionRequest {
+    pub const LEN: usize = 8 + // discriminator
+        32 + // fund
+        32 + // owner
+        8 + // sequence
+        8 + // fund_tokens
+        8 + // requested_at
+        1; // bump
+}
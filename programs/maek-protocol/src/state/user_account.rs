@@ -1,10 +1,14 @@
 use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
 
 #[account]
 pub struct UserFundAccount {
     /// Owner of this fund account
     pub owner: Pubkey,
     
+    /// Fund this account holds shares in
+    pub fund: Pubkey,
+    
     /// Fund tokens held (8 decimals)
     pub fund_tokens: u64,
     
@@ -53,6 +57,7 @@ impl Default for UserFundAccount {
     fn default() -> Self {
         Self {
             owner: Pubkey::default(),
+            fund: Pubkey::default(),
             fund_tokens: 0,
             total_deposited: 0,
             total_withdrawn: 0,
@@ -74,6 +79,7 @@ impl Default for UserFundAccount {
 impl UserFundAccount {
     pub const LEN: usize = 8 + // discriminator
         32 + // owner
+        32 + // fund
         8 + // fund_tokens
         8 + // total_deposited
         8 + // total_withdrawn
@@ -88,4 +94,24 @@ impl UserFundAccount {
         8 + // avg_cost_basis
         8 + // last_deposit_nav
         1; // bump
+    
+    /// Record fund tokens issued for a USDC subscription (6 decimals) at `nav_per_share`
+    pub fn record_deposit(&mut self, fund_tokens: u64, amount_usdc: u64, nav_per_share: u64, timestamp: i64) -> Result<()> {
+        self.fund_tokens = self.fund_tokens.checked_add(fund_tokens).ok_or(ErrorCode::MathOverflow)?;
+        self.total_deposited = self.total_deposited.checked_add(amount_usdc * 100).ok_or(ErrorCode::MathOverflow)?; // Convert to 8 decimals
+        self.last_deposit_time = timestamp;
+        self.last_deposit_nav = nav_per_share;
+        self.deposit_count = self.deposit_count.checked_add(1).ok_or(ErrorCode::MathOverflow)?;
+        
+        // Update average cost basis (u128: NAV * tokens exceeds u64 for large positions)
+        let previous_tokens = self.fund_tokens.checked_sub(fund_tokens).ok_or(ErrorCode::MathOverflow)?;
+        let total_cost = (self.avg_cost_basis as u128)
+            .checked_mul(previous_tokens as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_add((nav_per_share as u128).checked_mul(fund_tokens as u128).ok_or(ErrorCode::MathOverflow)?)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.avg_cost_basis = total_cost.checked_div(self.fund_tokens as u128).ok_or(ErrorCode::MathOverflow)? as u64;
+        
+        Ok(())
+    }
 } 
\ No newline at end of file
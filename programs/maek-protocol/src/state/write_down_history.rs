@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CreditEventKind {
+    #[default]
+    WriteDown,
+    Recovery,
+    WriteOff,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default)]
+pub struct CreditEvent {
+    /// Type of credit event
+    pub kind: CreditEventKind,
+    
+    /// Write-down applied in basis points of current value (0 for recoveries)
+    pub write_down_bps: u16,
+    
+    /// Amount written down, recovered or written off (8 decimals)
+    pub amount: u64,
+    
+    /// Asset current value after the event (8 decimals)
+    pub value_after: u64,
+    
+    /// Event timestamp
+    pub timestamp: i64,
+}
+
+impl CreditEvent {
+    pub const LEN: usize = 1 + // kind
+        2 + // write_down_bps
+        8 + // amount
+        8 + // value_after
+        8; // timestamp
+}
+
+#[account]
+#[derive(Default)]
+pub struct WriteDownHistory {
+    /// Fund holding the asset
+    pub fund: Pubkey,
+    
+    /// Asset the history belongs to
+    pub asset: Pubkey,
+    
+    /// Cumulative amount written down (8 decimals)
+    pub total_written_down: u64,
+    
+    /// Cumulative cash recovered (8 decimals)
+    pub total_recovered: u64,
+    
+    /// Credit events in the order they were applied
+    pub events: Vec<CreditEvent>,
+    
+    /// Account bump
+    pub bump: u8,
+}
+
+impl WriteDownHistory {
+    /// Maximum number of credit events kept per asset
+    pub const MAX_EVENTS: usize = 16;
+    
+    pub const LEN: usize = 8 + // discriminator
+        32 + // fund
+        32 + // asset
+        8 + // total_written_down
+        8 + // total_recovered
+        4 + Self::MAX_EVENTS * CreditEvent::LEN + // events
+        1; // bump
+}
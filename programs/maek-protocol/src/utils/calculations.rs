@@ -1,5 +1,6 @@
 use anchor_lang::prelude::*;
 use crate::error::ErrorCode;
+use crate::state::{DayCountConvention, FixedIncomeAssetType, FundState, LiquidityAction};
 
 // Fund token calculation constants
 pub const FUND_TOKEN_DECIMALS: u8 = 8; // 8 decimals for fund tokens
@@ -8,15 +9,17 @@ pub const NAV_DECIMALS: u8 = 8; // 8 decimals for NAV (e.g., $1.00000000)
 
 /// Calculate fund tokens to mint for a USDC deposit
 /// Formula: (deposit_amount * 10^fund_decimals * 10^nav_decimals) / (nav_per_share * 10^usdc_decimals)
-/// Simplified: (deposit_amount * 100) / nav_per_share
-pub fn calculate_fund_tokens(deposit_amount: u64, nav_per_share: u64) -> Result<u64> {
+/// Simplified: (deposit_amount * 10^10) / nav_per_share
+/// A non-zero `swing_factor_bps` prices the subscription at NAV swung up by that factor
+pub fn calculate_fund_tokens(deposit_amount: u64, nav_per_share: u64, swing_factor_bps: u16) -> Result<u64> {
     require!(nav_per_share > 0, ErrorCode::InvalidNAV);
     require!(deposit_amount > 0, ErrorCode::InvalidAmount);
     
-    // Convert USDC (6 decimals) to fund token precision (8 decimals)
-    // deposit_amount * 100 (to get 8 decimals) / nav_per_share (8 decimals) = fund tokens (8 decimals)
-    let numerator = (deposit_amount as u128) * 100; // Multiply by 100 to convert 6 -> 8 decimals
-    let fund_tokens = numerator / (nav_per_share as u128);
+    // Convert USDC (6 decimals) to fund token precision (8 decimals), then scale by NAV precision
+    // deposit_amount * 100 (to get 8 decimals) * 10^8 / nav_per_share (8 decimals) = fund tokens (8 decimals)
+    let numerator = (deposit_amount as u128) * 10_000_000_000; // 10^2 for 6 -> 8 decimals, 10^8 for NAV
+    let swung_nav = (nav_per_share as u128) * (10_000 + swing_factor_bps as u128) / 10_000;
+    let fund_tokens = numerator / swung_nav;
     
     require!(fund_tokens <= u64::MAX as u128, ErrorCode::MathOverflow);
     
@@ -25,13 +28,16 @@ pub fn calculate_fund_tokens(deposit_amount: u64, nav_per_share: u64) -> Result<
 
 /// Calculate USDC withdrawal amount for fund tokens
 /// Formula: (fund_tokens * nav_per_share) / 10^nav_decimals / 100
-pub fn calculate_withdrawal_amount(fund_tokens: u64, nav_per_share: u64) -> Result<u64> {
+/// A non-zero `swing_factor_bps` prices the redemption at NAV swung down by that factor
+pub fn calculate_withdrawal_amount(fund_tokens: u64, nav_per_share: u64, swing_factor_bps: u16) -> Result<u64> {
     require!(fund_tokens > 0, ErrorCode::InvalidAmount);
     require!(nav_per_share > 0, ErrorCode::InvalidNAV);
+    require!(swing_factor_bps < 10_000, ErrorCode::InvalidAmount);
     
     // fund_tokens (8 decimals) * nav_per_share (8 decimals) / 10^8 = value in 8 decimals
     // Then divide by 100 to convert to USDC (6 decimals)
-    let value_8_decimals = (fund_tokens as u128) * (nav_per_share as u128) / 100_000_000;
+    let swung_nav = (nav_per_share as u128) * (10_000 - swing_factor_bps as u128) / 10_000;
+    let value_8_decimals = (fund_tokens as u128) * swung_nav / 100_000_000;
     let usdc_amount = value_8_decimals / 100; // Convert from 8 decimals to 6 decimals
     
     require!(usdc_amount <= u64::MAX as u128, ErrorCode::MathOverflow);
@@ -39,6 +45,21 @@ pub fn calculate_withdrawal_amount(fund_tokens: u64, nav_per_share: u64) -> Resu
     Ok(usdc_amount as u64)
 }
 
+/// Swing factor to apply to a flow, given the fund's net flow in the same direction once it is included
+/// Returns 0 unless that net flow exceeds `threshold_bps` of total assets
+pub fn calculate_swing_factor(directional_net_flow: u64, total_assets: u64, threshold_bps: u16, swing_factor_bps: u16) -> u16 {
+    if swing_factor_bps == 0 || total_assets == 0 {
+        return 0;
+    }
+    
+    let threshold = (total_assets as u128) * (threshold_bps as u128) / 10_000;
+    if directional_net_flow as u128 > threshold {
+        swing_factor_bps
+    } else {
+        0
+    }
+}
+
 /// Calculate NAV per share based on total assets and total shares
 pub fn calculate_nav_per_share(total_assets: u64, total_shares: u64) -> Result<u64> {
     require!(total_shares > 0, ErrorCode::NoSharesOutstanding);
@@ -67,6 +88,50 @@ pub fn calculate_daily_management_fee(total_assets: u64, annual_fee_bps: u16) ->
     Ok(daily_fee as u64)
 }
 
+/// Calculate management fee accrued over an elapsed period
+/// Pro-rated by the second on an actual/365 basis; returns 0 for an empty fund or period
+pub fn calculate_management_fee(net_assets: u64, annual_fee_bps: u16, elapsed_seconds: i64) -> Result<u64> {
+    require!(annual_fee_bps <= 500, ErrorCode::FeeTooHigh); // Max 5% annual fee
+    if net_assets == 0 || elapsed_seconds <= 0 {
+        return Ok(0);
+    }
+    
+    // Fee = net_assets * annual_fee_bps * elapsed / (10,000 * 365 days)
+    let fee = (net_assets as u128) * (annual_fee_bps as u128) * (elapsed_seconds as u128) / (10_000 * 365 * 86400);
+    
+    // Never charge more than the fund holds
+    Ok(fee.min(net_assets as u128) as u64)
+}
+
+/// Calculate the NAV per share a fund must reach before a performance fee accrues
+/// The hurdle compounds simply from the high-water mark over the elapsed part of the period
+pub fn calculate_hurdle_nav(high_water_mark: u64, hurdle_rate_bps: u16, elapsed_seconds: i64) -> Result<u64> {
+    let elapsed = elapsed_seconds.max(0) as u128;
+    let hurdle_return = (high_water_mark as u128) * (hurdle_rate_bps as u128) * elapsed / (10_000 * 365 * 86400);
+    let hurdle_nav = (high_water_mark as u128).checked_add(hurdle_return).ok_or(ErrorCode::MathOverflow)?;
+    require!(hurdle_nav <= u64::MAX as u128, ErrorCode::MathOverflow);
+    Ok(hurdle_nav as u64)
+}
+
+/// Calculate the performance fee on gains above the hurdle NAV (8 decimals)
+/// `nav_before_fee` is the NAV per share gross of any performance fee
+pub fn calculate_performance_fee(
+    nav_before_fee: u64,
+    hurdle_nav: u64,
+    total_shares: u64,
+    performance_fee_bps: u16,
+) -> Result<u64> {
+    if nav_before_fee <= hurdle_nav {
+        return Ok(0);
+    }
+    
+    // Excess gain = (NAV - hurdle) * shares / 10^8, fee = gain * bps / 10,000
+    let excess_gain = ((nav_before_fee - hurdle_nav) as u128) * (total_shares as u128) / 100_000_000;
+    let fee = excess_gain * (performance_fee_bps as u128) / 10_000;
+    require!(fee <= u64::MAX as u128, ErrorCode::MathOverflow);
+    Ok(fee as u64)
+}
+
 /// Calculate APY based on daily yields over the past year
 /// Returns APY in basis points with 2 decimal places (e.g., 450 = 4.50%)
 pub fn calculate_apy(daily_yields: &[u64; 365], total_assets: u64) -> u32 {
@@ -152,6 +217,623 @@ pub fn calculate_asset_allocation(
     ))
 }
 
+/// Calculate cash received when a fixed income asset matures (8 decimals)
+/// Discount instruments redeem at face value, their accrued interest being the accreted discount;
+/// coupon-bearing instruments pay face value plus the final accrued coupon
+pub fn calculate_maturity_proceeds(
+    asset_type: FixedIncomeAssetType,
+    face_value: u64,
+    accrued_interest: u64,
+) -> Result<u64> {
+    if is_discount_instrument(asset_type) {
+        Ok(face_value)
+    } else {
+        face_value.checked_add(accrued_interest).ok_or(ErrorCode::MathOverflow.into())
+    }
+}
+
+/// Calculate realized gain/loss on an asset sale (8 decimals)
+/// Cost basis is the purchase price plus interest accrued while held
+pub fn calculate_realized_pnl(sale_price: u64, purchase_price: u64, accrued_interest: u64) -> Result<i64> {
+    let cost_basis = (purchase_price as i128)
+        .checked_add(accrued_interest as i128)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let pnl = (sale_price as i128) - cost_basis;
+    i64::try_from(pnl).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// Calculate the amount written off a current value for a write-down in basis points
+pub fn calculate_write_down(current_value: u64, write_down_bps: u16) -> Result<u64> {
+    require!(write_down_bps <= 10000, ErrorCode::InvalidWriteDown);
+    
+    let write_down = (current_value as u128) * (write_down_bps as u128) / 10000;
+    Ok(write_down as u64)
+}
+
+/// Day-count convention conventionally used for an asset type
+/// Money market instruments accrue ACT/360, Treasury coupons ACT/365, other bonds 30/360
+pub fn default_day_count_convention(asset_type: FixedIncomeAssetType) -> DayCountConvention {
+    match asset_type {
+        FixedIncomeAssetType::TreasuryBill
+        | FixedIncomeAssetType::CommercialPaper
+        | FixedIncomeAssetType::CertificateOfDeposit => DayCountConvention::Actual360,
+        FixedIncomeAssetType::TreasuryNote | FixedIncomeAssetType::TreasuryBond => DayCountConvention::Actual365,
+        _ => DayCountConvention::Thirty360,
+    }
+}
+
+/// Days in the year used as the accrual denominator
+pub fn day_count_basis(convention: DayCountConvention) -> u64 {
+    match convention {
+        DayCountConvention::Actual360 | DayCountConvention::Thirty360 => 360,
+        DayCountConvention::Actual365 => 365,
+    }
+}
+
+/// Convert a unix timestamp to a (year, month, day) civil date in UTC
+pub fn civil_date(timestamp: i64) -> (i64, i64, i64) {
+    // Days-from-civil inverse over 400-year eras (proleptic Gregorian calendar)
+    let days = timestamp.div_euclid(86400) + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+/// Convert a (year, month, day) civil date in UTC to days since the unix epoch
+pub fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Shift a timestamp by whole calendar months, clamping to the end of shorter months
+pub fn add_months(timestamp: i64, months: i64) -> i64 {
+    let (year, month, day) = civil_date(timestamp);
+    let month_count = year * 12 + (month - 1) + months;
+    let new_year = month_count.div_euclid(12);
+    let new_month = month_count.rem_euclid(12) + 1;
+    
+    // Last day of the target month is the day before the first of the following month
+    let next_month_start = if new_month == 12 {
+        days_from_civil(new_year + 1, 1, 1)
+    } else {
+        days_from_civil(new_year, new_month + 1, 1)
+    };
+    let month_length = next_month_start - days_from_civil(new_year, new_month, 1);
+    let new_day = std::cmp::min(day, month_length);
+    
+    days_from_civil(new_year, new_month, new_day) * 86400 + timestamp.rem_euclid(86400)
+}
+
+/// Count accrual days between two timestamps under a day-count convention
+/// Actual conventions count whole elapsed days; 30/360 uses the US bond basis
+pub fn accrual_days(convention: DayCountConvention, start: i64, end: i64) -> i64 {
+    if end <= start {
+        return 0;
+    }
+    
+    match convention {
+        DayCountConvention::Actual360 | DayCountConvention::Actual365 => (end - start) / 86400,
+        DayCountConvention::Thirty360 => {
+            let (y1, m1, d1) = civil_date(start);
+            let (y2, m2, d2) = civil_date(end);
+            let d1 = std::cmp::min(d1, 30);
+            let d2 = if d1 == 30 { std::cmp::min(d2, 30) } else { d2 };
+            360 * (y2 - y1) + 30 * (m2 - m1) + (d2 - d1)
+        }
+    }
+}
+
+/// Calculate interest accrued on a principal between two timestamps (8 decimals)
+/// yield_rate is in thousandths of a percent (5000 = 5.00%)
+pub fn calculate_accrued_interest(
+    principal: u64,
+    yield_rate: u32,
+    convention: DayCountConvention,
+    start: i64,
+    end: i64,
+) -> Result<u64> {
+    let days = accrual_days(convention, start, end) as u128;
+    
+    // principal * rate * days / basis
+    let interest = (principal as u128)
+        .checked_mul(yield_rate as u128)
+        .and_then(|value| value.checked_mul(days))
+        .ok_or(ErrorCode::MathOverflow)?
+        / (RATE_SCALE * day_count_basis(convention) as u128);
+    
+    require!(interest <= u64::MAX as u128, ErrorCode::MathOverflow);
+    Ok(interest as u64)
+}
+
+/// Fixed-point scale for annual rates, matching `yield_rate` (100_000 = 100%)
+pub const RATE_SCALE: u128 = 100_000;
+
+/// Whether an asset type is issued at a discount and redeems at par
+pub fn is_discount_instrument(asset_type: FixedIncomeAssetType) -> bool {
+    matches!(asset_type, FixedIncomeAssetType::TreasuryBill | FixedIncomeAssetType::CommercialPaper)
+}
+
+/// Convert a u128 rate to the u32 yield_rate scale
+fn to_rate(rate: u128) -> Result<u32> {
+    require!(rate <= u32::MAX as u128, ErrorCode::MathOverflow);
+    Ok(rate as u32)
+}
+
+/// Calculate bank discount yield: (F - P) / F * 360 / t
+pub fn calculate_discount_yield(face_value: u64, price: u64, days_to_maturity: u64) -> Result<u32> {
+    require!(face_value > 0 && days_to_maturity > 0, ErrorCode::InvalidFixedIncomeAssetData);
+    require!(price <= face_value, ErrorCode::InvalidFixedIncomeAssetData);
+    
+    let discount = (face_value - price) as u128;
+    to_rate(discount * 360 * RATE_SCALE / (face_value as u128 * days_to_maturity as u128))
+}
+
+/// Calculate price from a bank discount yield: F * (1 - d * t / 360)
+pub fn calculate_price_from_discount_yield(face_value: u64, discount_yield: u32, days_to_maturity: u64) -> Result<u64> {
+    let discount = (face_value as u128) * (discount_yield as u128) * (days_to_maturity as u128) / (360 * RATE_SCALE);
+    require!(discount <= face_value as u128, ErrorCode::InvalidFixedIncomeAssetData);
+    Ok(face_value - discount as u64)
+}
+
+/// Calculate money-market (CD-equivalent) yield: (F - P) / P * 360 / t
+pub fn calculate_money_market_yield(face_value: u64, price: u64, days_to_maturity: u64) -> Result<u32> {
+    require!(price > 0 && days_to_maturity > 0, ErrorCode::InvalidFixedIncomeAssetData);
+    require!(price <= face_value, ErrorCode::InvalidFixedIncomeAssetData);
+    
+    let discount = (face_value - price) as u128;
+    to_rate(discount * 360 * RATE_SCALE / (price as u128 * days_to_maturity as u128))
+}
+
+/// Calculate price from a money-market yield: F / (1 + y * t / 360)
+pub fn calculate_price_from_money_market_yield(
+    face_value: u64,
+    money_market_yield: u32,
+    days_to_maturity: u64,
+) -> Result<u64> {
+    let denominator = 360 * RATE_SCALE + (money_market_yield as u128) * (days_to_maturity as u128);
+    let price = (face_value as u128) * 360 * RATE_SCALE / denominator;
+    Ok(price as u64)
+}
+
+/// Calculate bond-equivalent yield: (F - P) / P * 365 / t
+pub fn calculate_bond_equivalent_yield(face_value: u64, price: u64, days_to_maturity: u64) -> Result<u32> {
+    require!(price > 0 && days_to_maturity > 0, ErrorCode::InvalidFixedIncomeAssetData);
+    require!(price <= face_value, ErrorCode::InvalidFixedIncomeAssetData);
+    
+    let discount = (face_value - price) as u128;
+    to_rate(discount * 365 * RATE_SCALE / (price as u128 * days_to_maturity as u128))
+}
+
+/// Calculate the Treasury investment rate (coupon-equivalent yield)
+/// Bills of a half year or less use the simple bond-equivalent yield; longer bills
+/// solve for a semiannual coupon rate: i = (-2t/y + 2 * sqrt((t/y)^2 - (2t/y - 1)(1 - F/P))) / (2t/y - 1)
+pub fn calculate_investment_rate(face_value: u64, price: u64, days_to_maturity: u64) -> Result<u32> {
+    if days_to_maturity <= 182 {
+        return calculate_bond_equivalent_yield(face_value, price, days_to_maturity);
+    }
+    require!(price > 0 && price <= face_value, ErrorCode::InvalidFixedIncomeAssetData);
+    
+    // Work in 1e9 fixed point: a = t / 365, b = 2a - 1
+    const SCALE: u128 = 1_000_000_000;
+    let a = (days_to_maturity as u128) * SCALE / 365;
+    let b = 2 * a - SCALE;
+    
+    // a^2 + b * (F - P) / P, at SCALE^2
+    let gain = ((face_value - price) as u128) * SCALE / (price as u128);
+    let radicand = a
+        .checked_mul(a)
+        .and_then(|square| square.checked_add(b * gain))
+        .ok_or(ErrorCode::MathOverflow)?;
+    let root = integer_sqrt(radicand);
+    
+    to_rate((2 * root - 2 * a) * RATE_SCALE / b)
+}
+
+/// Integer square root (floor) by Newton's method
+fn integer_sqrt(value: u128) -> u128 {
+    if value < 2 {
+        return value;
+    }
+    
+    let mut x = value;
+    let mut y = value.div_ceil(2);
+    while y < x {
+        x = y;
+        y = (x + value / x) / 2;
+    }
+    x
+}
+
+/// Fixed-point scale used inside bond price calculations
+const PRICE_SCALE: u128 = 1_000_000_000;
+
+/// Fixed-point scale for the logarithm and exponential behind fractional compounding
+const GROWTH_SCALE: u128 = 1_000_000_000_000_000_000;
+
+/// Growth factor (1 + periodic_yield)^fraction at PRICE_SCALE, for a fraction of a period
+/// Computed as exp(fraction * ln(1 + periodic_yield)); a whole period is exact
+fn fractional_period_growth(periodic_yield: u128, fraction: u128) -> Result<u128> {
+    if fraction == PRICE_SCALE {
+        return Ok(PRICE_SCALE + periodic_yield);
+    }
+    
+    // ln(1 + r) = 2 * atanh(z) = 2 * (z + z^3/3 + z^5/5 + ...) with z = r / (2 + r)
+    let rate = periodic_yield * (GROWTH_SCALE / PRICE_SCALE);
+    let z = rate.checked_mul(GROWTH_SCALE).ok_or(ErrorCode::MathOverflow)? / (2 * GROWTH_SCALE + rate);
+    let z_squared = z * z / GROWTH_SCALE;
+    let (mut power, mut series, mut k) = (z, 0u128, 1u128);
+    while power / k > 0 {
+        series += power / k;
+        power = power * z_squared / GROWTH_SCALE;
+        k += 2;
+    }
+    let exponent = 2 * series * fraction / PRICE_SCALE;
+    
+    // exp(x) = 1 + x + x^2/2! + ...
+    let (mut term, mut growth, mut n) = (GROWTH_SCALE, GROWTH_SCALE, 1u128);
+    while term > 0 {
+        term = term * exponent / GROWTH_SCALE / n;
+        growth += term;
+        n += 1;
+    }
+    Ok(growth / (GROWTH_SCALE / PRICE_SCALE))
+}
+
+/// Maximum number of coupons a schedule may have left (monthly over 30 years)
+const MAX_REMAINING_COUPONS: u32 = 360;
+
+/// Months between coupon payments for a payment frequency (payments per year)
+pub fn coupon_period_months(coupon_frequency: u8) -> Result<i64> {
+    match coupon_frequency {
+        1 | 2 | 4 | 12 => Ok(12 / coupon_frequency as i64),
+        _ => err!(ErrorCode::InvalidCouponTerms),
+    }
+}
+
+/// Locate the coupon period containing `settlement`, with coupon dates rolling back from maturity
+/// Returns (previous coupon date, next coupon date, coupons remaining including the next)
+pub fn coupon_period(maturity_date: i64, coupon_frequency: u8, settlement: i64) -> Result<(i64, i64, u32)> {
+    require!(settlement < maturity_date, ErrorCode::AssetAlreadyMatured);
+    let months = coupon_period_months(coupon_frequency)?;
+    
+    let mut next_coupon = maturity_date;
+    let mut remaining: u32 = 1;
+    loop {
+        let previous_coupon = add_months(maturity_date, -(remaining as i64) * months);
+        if previous_coupon <= settlement {
+            return Ok((previous_coupon, next_coupon, remaining));
+        }
+        next_coupon = previous_coupon;
+        remaining += 1;
+        require!(remaining <= MAX_REMAINING_COUPONS, ErrorCode::InvalidCouponTerms);
+    }
+}
+
+/// Calculate coupon interest accrued since the previous coupon date (8 decimals)
+pub fn calculate_accrued_coupon(
+    face_value: u64,
+    coupon_rate: u32,
+    coupon_frequency: u8,
+    convention: DayCountConvention,
+    settlement: i64,
+    maturity_date: i64,
+) -> Result<u64> {
+    let (previous_coupon, next_coupon, _) = coupon_period(maturity_date, coupon_frequency, settlement)?;
+    let accrued_days = accrual_days(convention, previous_coupon, settlement) as u128;
+    let period_days = accrual_days(convention, previous_coupon, next_coupon) as u128;
+    
+    // coupon * accrued days / days in the period
+    let accrued = (face_value as u128) * (coupon_rate as u128) * accrued_days
+        / (RATE_SCALE * coupon_frequency as u128 * period_days);
+    Ok(accrued as u64)
+}
+
+/// Calculate the coupon paid on each coupon date (8 decimals)
+pub fn calculate_coupon_payment(face_value: u64, coupon_rate: u32, coupon_frequency: u8) -> Result<u64> {
+    coupon_period_months(coupon_frequency)?;
+    let coupon = (face_value as u128) * (coupon_rate as u128) / (RATE_SCALE * coupon_frequency as u128);
+    require!(coupon <= u64::MAX as u128, ErrorCode::MathOverflow);
+    Ok(coupon as u64)
+}
+
+/// Calculate coupon interest earned between two timestamps in the same coupon period (8 decimals)
+/// `end` may be the period's closing coupon date, by which the full coupon has accrued
+pub fn calculate_coupon_accrual(
+    face_value: u64,
+    coupon_rate: u32,
+    coupon_frequency: u8,
+    convention: DayCountConvention,
+    start: i64,
+    end: i64,
+    maturity_date: i64,
+) -> Result<u64> {
+    let (_, next_coupon, _) = coupon_period(maturity_date, coupon_frequency, start)?;
+    require!(end <= next_coupon, ErrorCode::InvalidCouponTerms);
+    
+    let accrued_at_start =
+        calculate_accrued_coupon(face_value, coupon_rate, coupon_frequency, convention, start, maturity_date)?;
+    let accrued_at_end = if end == next_coupon {
+        calculate_coupon_payment(face_value, coupon_rate, coupon_frequency)?
+    } else {
+        calculate_accrued_coupon(face_value, coupon_rate, coupon_frequency, convention, end, maturity_date)?
+    };
+    Ok(accrued_at_end.saturating_sub(accrued_at_start))
+}
+
+/// Calculate a bond's dirty (full) price for a yield to maturity (8 decimals)
+/// Cash flows are discounted at the periodic yield to the next coupon date, and that value
+/// is discounted over the broken first period by (1 + y/f)^(DSC/E)
+pub fn calculate_dirty_price(
+    face_value: u64,
+    coupon_rate: u32,
+    coupon_frequency: u8,
+    yield_to_maturity: u32,
+    convention: DayCountConvention,
+    settlement: i64,
+    maturity_date: i64,
+) -> Result<u64> {
+    let (previous_coupon, next_coupon, remaining) = coupon_period(maturity_date, coupon_frequency, settlement)?;
+    let frequency = coupon_frequency as u128;
+    
+    let periodic_yield = (yield_to_maturity as u128) * PRICE_SCALE / (RATE_SCALE * frequency);
+    let coupon = (face_value as u128)
+        .checked_mul(coupon_rate as u128 * PRICE_SCALE)
+        .ok_or(ErrorCode::MathOverflow)?
+        / (RATE_SCALE * frequency);
+    
+    // Value at the next coupon date, rolling back from maturity
+    let mut value = (face_value as u128) * PRICE_SCALE + coupon;
+    for _ in 1..remaining {
+        value = value.checked_mul(PRICE_SCALE).ok_or(ErrorCode::MathOverflow)? / (PRICE_SCALE + periodic_yield) + coupon;
+    }
+    
+    // Discount over the fraction of the period left until the next coupon
+    let days_to_next = accrual_days(convention, settlement, next_coupon) as u128;
+    let period_days = accrual_days(convention, previous_coupon, next_coupon) as u128;
+    let stub_growth = fractional_period_growth(periodic_yield, PRICE_SCALE * days_to_next / period_days)?;
+    let dirty = value.checked_mul(PRICE_SCALE).ok_or(ErrorCode::MathOverflow)? / stub_growth;
+    
+    let price = dirty / PRICE_SCALE;
+    require!(price <= u64::MAX as u128, ErrorCode::MathOverflow);
+    Ok(price as u64)
+}
+
+/// Calculate a bond's clean price (dirty price less accrued coupon) for a yield to maturity
+pub fn calculate_clean_price(
+    face_value: u64,
+    coupon_rate: u32,
+    coupon_frequency: u8,
+    yield_to_maturity: u32,
+    convention: DayCountConvention,
+    settlement: i64,
+    maturity_date: i64,
+) -> Result<u64> {
+    let dirty = calculate_dirty_price(
+        face_value,
+        coupon_rate,
+        coupon_frequency,
+        yield_to_maturity,
+        convention,
+        settlement,
+        maturity_date,
+    )?;
+    let accrued = calculate_accrued_coupon(face_value, coupon_rate, coupon_frequency, convention, settlement, maturity_date)?;
+    Ok(dirty.saturating_sub(accrued))
+}
+
+/// Solve for the yield to maturity that reprices a bond to a clean price
+/// Bisection over 0-100% in yield_rate units; price falls monotonically as yield rises
+pub fn calculate_yield_to_maturity(
+    face_value: u64,
+    coupon_rate: u32,
+    coupon_frequency: u8,
+    clean_price: u64,
+    convention: DayCountConvention,
+    settlement: i64,
+    maturity_date: i64,
+) -> Result<u32> {
+    let accrued = calculate_accrued_coupon(face_value, coupon_rate, coupon_frequency, convention, settlement, maturity_date)?;
+    let target = clean_price.checked_add(accrued).ok_or(ErrorCode::MathOverflow)?;
+    let price_at = |yield_to_maturity: u32| {
+        calculate_dirty_price(
+            face_value,
+            coupon_rate,
+            coupon_frequency,
+            yield_to_maturity,
+            convention,
+            settlement,
+            maturity_date,
+        )
+    };
+    
+    let mut low: u32 = 0;
+    let mut high: u32 = RATE_SCALE as u32;
+    let (mut price_low, mut price_high) = (price_at(low)?, price_at(high)?);
+    require!(target <= price_low && target >= price_high, ErrorCode::InvalidCouponTerms);
+    
+    while high - low > 1 {
+        let mid = low + (high - low) / 2;
+        let price_mid = price_at(mid)?;
+        if price_mid > target {
+            low = mid;
+            price_low = price_mid;
+        } else {
+            high = mid;
+            price_high = price_mid;
+        }
+    }
+    
+    // Return whichever bracket reprices closer to the target
+    if price_low - target <= target - price_high {
+        Ok(low)
+    } else {
+        Ok(high)
+    }
+}
+
+/// Fixed-point scale for durations (10_000 = 1 year) and convexity (10_000 = 1 year squared)
+pub const DURATION_SCALE: u128 = 10_000;
+
+/// Interest rate risk measures for a holding
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct DurationMetrics {
+    pub macaulay_duration: u32,
+    pub modified_duration: u32,
+    pub convexity: u32,
+}
+
+/// Convert a PRICE_SCALE value to DURATION_SCALE
+fn to_duration(value: u128) -> Result<u32> {
+    let scaled = value * DURATION_SCALE / PRICE_SCALE;
+    require!(scaled <= u32::MAX as u128, ErrorCode::MathOverflow);
+    Ok(scaled as u32)
+}
+
+/// Calculate Macaulay duration, modified duration and convexity for a holding
+/// Coupon bonds discount each cash flow as in `calculate_dirty_price`; instruments paying
+/// only at maturity are a single cash flow growing at simple interest over the remaining term
+pub fn calculate_duration_metrics(
+    coupon_rate: u32,
+    coupon_frequency: u8,
+    yield_to_maturity: u32,
+    convention: DayCountConvention,
+    settlement: i64,
+    maturity_date: i64,
+) -> Result<DurationMetrics> {
+    require!(settlement < maturity_date, ErrorCode::AssetAlreadyMatured);
+    
+    if coupon_frequency == 0 {
+        // years = days / 365, modified = years / (1 + y * years), convexity = 2 * years^2 / (1 + y * years)^2
+        let years = ((maturity_date - settlement) / 86400) as u128 * PRICE_SCALE / 365;
+        let growth = PRICE_SCALE + (yield_to_maturity as u128) * years / RATE_SCALE;
+        let modified = years * PRICE_SCALE / growth;
+        let convexity = 2 * modified * modified / PRICE_SCALE;
+        return Ok(DurationMetrics {
+            macaulay_duration: to_duration(years)?,
+            modified_duration: to_duration(modified)?,
+            convexity: to_duration(convexity)?,
+        });
+    }
+    
+    let (previous_coupon, next_coupon, remaining) = coupon_period(maturity_date, coupon_frequency, settlement)?;
+    let frequency = coupon_frequency as u128;
+    let periodic_yield = (yield_to_maturity as u128) * PRICE_SCALE / (RATE_SCALE * frequency);
+    let coupon = (coupon_rate as u128) * PRICE_SCALE / (RATE_SCALE * frequency); // Per unit of face value
+    
+    // Fraction of a period until the first cash flow
+    let days_to_next = accrual_days(convention, settlement, next_coupon) as u128;
+    let period_days = accrual_days(convention, previous_coupon, next_coupon) as u128;
+    let first_period = PRICE_SCALE * days_to_next / period_days;
+    
+    let mut discount_factor = PRICE_SCALE * PRICE_SCALE / fractional_period_growth(periodic_yield, first_period)?;
+    let mut price: u128 = 0;
+    let mut time_weighted: u128 = 0; // sum of t * PV, t in periods
+    let mut convexity_weighted: u128 = 0; // sum of t * (t + 1) * PV
+    
+    for period in 1..=remaining {
+        let cash_flow = if period == remaining { coupon + PRICE_SCALE } else { coupon };
+        let present_value = cash_flow * discount_factor / PRICE_SCALE;
+        let time = first_period + (period as u128 - 1) * PRICE_SCALE;
+        
+        price += present_value;
+        time_weighted += time * present_value / PRICE_SCALE;
+        convexity_weighted += time * (time + PRICE_SCALE) / PRICE_SCALE * present_value / PRICE_SCALE;
+        
+        discount_factor = discount_factor * PRICE_SCALE / (PRICE_SCALE + periodic_yield);
+    }
+    require!(price > 0, ErrorCode::InvalidCouponTerms);
+    
+    // Convert from periods to years and from Macaulay to modified
+    let macaulay = time_weighted * PRICE_SCALE / price / frequency;
+    let modified = macaulay * PRICE_SCALE / (PRICE_SCALE + periodic_yield);
+    let convexity = convexity_weighted * PRICE_SCALE / price / (frequency * frequency) * PRICE_SCALE
+        / (PRICE_SCALE + periodic_yield) * PRICE_SCALE
+        / (PRICE_SCALE + periodic_yield);
+    
+    Ok(DurationMetrics {
+        macaulay_duration: to_duration(macaulay)?,
+        modified_duration: to_duration(modified)?,
+        convexity: to_duration(convexity)?,
+    })
+}
+
+/// Calculate value-weighted portfolio duration from the sum of duration * book value
+/// Cash carries zero duration, so the sum is spread over total assets
+pub fn calculate_portfolio_duration(weighted_duration_sum: u128, total_assets: u64) -> Result<u32> {
+    if total_assets == 0 {
+        return Ok(0);
+    }
+    
+    let duration = weighted_duration_sum / total_assets as u128;
+    require!(duration <= u32::MAX as u128, ErrorCode::MathOverflow);
+    Ok(duration as u32)
+}
+
+/// Book value x seconds until a holding's maturity (or reset) date, zero once the date has passed
+pub fn holding_dollar_seconds(book_value: u64, date: i64, now: i64) -> u128 {
+    book_value as u128 * (date.saturating_sub(now).max(0) as u128)
+}
+
+/// Book value x seconds until maturity (or reset) summed from a running sum of book value x timestamp
+/// Exact while every holding's date is in the future; the NAV update sums the holdings one
+/// by one instead, so a holding past its date counts as zero rather than negative
+pub fn running_dollar_seconds(weighted_sum: u128, held_book_value: u64, now: i64) -> Result<u128> {
+    let elapsed = (held_book_value as u128).checked_mul(now.max(0) as u128).ok_or(ErrorCode::MathOverflow)?;
+    Ok(weighted_sum.saturating_sub(elapsed))
+}
+
+/// Calculate dollar-weighted average days to a date across the fund
+/// `dollar_seconds` is the sum of book value x seconds to each holding's date; holdings at
+/// `held_book_value` and cash at par are on the same book basis, cash counting as zero days
+pub fn calculate_weighted_average_days(dollar_seconds: u128, held_book_value: u64, cash_value: u64) -> Result<u32> {
+    let book_value = (held_book_value as u128).checked_add(cash_value as u128).ok_or(ErrorCode::MathOverflow)?;
+    if book_value == 0 {
+        return Ok(0);
+    }
+    
+    let days = dollar_seconds / (book_value * 86400);
+    require!(days <= u32::MAX as u128, ErrorCode::MathOverflow);
+    Ok(days as u32)
+}
+
+/// Cash in the USDC and treasury vaults at par, in 8 decimals
+pub fn fund_cash_value(fund_state: &FundState) -> Result<u64> {
+    fund_state.cash_reserves
+        .checked_add(fund_state.treasury_cash)
+        .and_then(|cash| cash.checked_mul(100)) // Convert USDC to 8 decimals
+        .ok_or(ErrorCode::MathOverflow.into())
+}
+
+/// Refresh the fund's portfolio duration, WAM and WAL snapshots from their running sums
+pub fn refresh_portfolio_risk(fund_state: &mut FundState, now: i64) -> Result<()> {
+    fund_state.portfolio_duration = calculate_portfolio_duration(fund_state.weighted_duration_sum, fund_state.total_assets)?;
+    
+    // WAM runs to each holding's next interest reset, WAL to final maturity
+    let reset_seconds = running_dollar_seconds(fund_state.reset_weighted_sum, fund_state.held_book_value, now)?;
+    let maturity_seconds = running_dollar_seconds(fund_state.maturity_weighted_sum, fund_state.held_book_value, now)?;
+    set_weighted_average_days(fund_state, reset_seconds, maturity_seconds)
+}
+
+/// Set the fund's WAM and WAL snapshots from book value x seconds to each holding's reset and maturity
+pub fn set_weighted_average_days(fund_state: &mut FundState, reset_seconds: u128, maturity_seconds: u128) -> Result<()> {
+    let cash_value = fund_cash_value(fund_state)?;
+    fund_state.weighted_average_maturity =
+        calculate_weighted_average_days(reset_seconds, fund_state.held_book_value, cash_value)?;
+    fund_state.weighted_average_life =
+        calculate_weighted_average_days(maturity_seconds, fund_state.held_book_value, cash_value)?;
+    Ok(())
+}
+
 /// Calculate liquidity ratio (cash / total assets)
 pub fn calculate_liquidity_ratio(cash_reserves: u64, total_assets: u64) -> u32 {
     if total_assets == 0 {
@@ -163,15 +845,85 @@ pub fn calculate_liquidity_ratio(cash_reserves: u64, total_assets: u64) -> u32 {
     std::cmp::min(ratio as u32, 100)
 }
 
-/// Validate deposit amount meets fund requirements
-pub fn validate_deposit_amount(amount_usdc: u64) -> Result<()> {
-    // Minimum deposit: $10 (10,000,000 with 6 decimals)
-    require!(amount_usdc >= 10_000_000, ErrorCode::DepositTooSmall);
+/// Calculate cash required to meet the target liquidity ratio (USDC, 6 decimals)
+pub fn calculate_required_liquidity(total_assets: u64, target_liquidity_ratio: u8) -> Result<u64> {
+    // Required cash = total_assets (8 decimals) * ratio% converted to 6 decimals, rounded up
+    let required = (total_assets as u128)
+        .checked_mul(target_liquidity_ratio as u128)
+        .ok_or(ErrorCode::MathOverflow)?
+        .div_ceil(100 * 100);
+    Ok(required as u64)
+}
+
+/// Calculate cash held above the target liquidity ratio (USDC, 6 decimals)
+pub fn calculate_excess_liquidity(cash_reserves: u64, total_assets: u64, target_liquidity_ratio: u8) -> Result<u64> {
+    let required = calculate_required_liquidity(total_assets, target_liquidity_ratio)?;
+    Ok(cash_reserves.saturating_sub(required))
+}
+
+/// Calculate the action and USDC amount (6 decimals) that bring cash back to the target liquidity ratio
+pub fn calculate_liquidity_gap(cash_reserves: u64, total_assets: u64, target_liquidity_ratio: u8) -> Result<(LiquidityAction, u64)> {
+    let required = calculate_required_liquidity(total_assets, target_liquidity_ratio)?;
+    Ok(match cash_reserves.cmp(&required) {
+        std::cmp::Ordering::Less => (LiquidityAction::RaiseCash, required - cash_reserves),
+        std::cmp::Ordering::Greater => (LiquidityAction::DeployCash, cash_reserves - required),
+        std::cmp::Ordering::Equal => (LiquidityAction::Hold, 0),
+    })
+}
+
+/// Calculate value still redeemable under the redemption gate this period (8 decimals)
+/// Returns u64::MAX when no gate is set
+pub fn calculate_redemption_capacity(period_assets: u64, gate_bps: u16, period_redemptions: u64) -> Result<u64> {
+    if gate_bps == 0 {
+        return Ok(u64::MAX);
+    }
+    require!(gate_bps <= 10_000, ErrorCode::InvalidAmount);
     
-    // Maximum deposit: $1,000,000 (1,000,000,000,000 with 6 decimals)
-    require!(amount_usdc <= 1_000_000_000_000, ErrorCode::DepositTooLarge);
+    let limit = (period_assets as u128) * (gate_bps as u128) / 10_000;
+    Ok((limit as u64).saturating_sub(period_redemptions))
+}
+
+/// Calculate the move from a previous value in basis points of the previous value
+/// Returns 0 when there is no previous value to compare against
+pub fn calculate_price_deviation_bps(previous_value: u64, new_value: u64) -> u64 {
+    if previous_value == 0 {
+        return 0;
+    }
     
-    Ok(())
+    let deviation = (previous_value as u128).abs_diff(new_value as u128) * 10_000 / (previous_value as u128);
+    std::cmp::min(deviation, u64::MAX as u128) as u64
+}
+
+/// Default maximum valuation move between updates for an asset type (basis points)
+/// Short-dated money market paper barely moves; longer and structured credit can gap further
+pub fn default_price_deviation_bps(asset_type: FixedIncomeAssetType) -> u16 {
+    match asset_type {
+        FixedIncomeAssetType::TreasuryBill
+        | FixedIncomeAssetType::CommercialPaper
+        | FixedIncomeAssetType::CertificateOfDeposit => 200,
+        FixedIncomeAssetType::TreasuryNote => 300,
+        FixedIncomeAssetType::TreasuryBond
+        | FixedIncomeAssetType::CorporateBond
+        | FixedIncomeAssetType::MunicipalBond => 500,
+        FixedIncomeAssetType::AssetBackedSecurity | FixedIncomeAssetType::MortgageBackedSecurity => 1_000,
+    }
+}
+
+/// Calculate a holding's value from an oracle price per unit of face value (8 decimals)
+/// value = face_value * price * 10^exponent
+pub fn calculate_oracle_value(face_value: u64, price: i64, exponent: i32) -> Result<u64> {
+    require!(price > 0, ErrorCode::InvalidPriceFeed);
+    require!(exponent.unsigned_abs() <= 18, ErrorCode::InvalidPriceFeed);
+    
+    let scale = 10u128.pow(exponent.unsigned_abs());
+    let gross = (face_value as u128).checked_mul(price as u128).ok_or(ErrorCode::MathOverflow)?;
+    let value = if exponent < 0 {
+        gross / scale
+    } else {
+        gross.checked_mul(scale).ok_or(ErrorCode::MathOverflow)?
+    };
+    require!(value <= u64::MAX as u128, ErrorCode::MathOverflow);
+    Ok(value as u64)
 }
 
 /// Validate withdrawal amount
@@ -190,7 +942,7 @@ mod tests {
     fn test_calculate_fund_tokens_at_par() {
         let deposit = 1_000_000_000u64; // 1000 USDC
         let nav = 100_000_000u64; // $1.00
-        let result = calculate_fund_tokens(deposit, nav).unwrap();
+        let result = calculate_fund_tokens(deposit, nav, 0).unwrap();
         assert_eq!(result, 100_000_000_000u64); // 1000.00000000 tokens
     }
 
@@ -198,7 +950,7 @@ mod tests {
     fn test_calculate_fund_tokens_higher_nav() {
         let deposit = 510_000_000u64; // 510 USDC
         let nav = 102_000_000u64; // $1.02
-        let result = calculate_fund_tokens(deposit, nav).unwrap();
+        let result = calculate_fund_tokens(deposit, nav, 0).unwrap();
         assert_eq!(result, 50_000_000_000u64); // 500.00000000 tokens
     }
 
@@ -206,10 +958,29 @@ mod tests {
     fn test_calculate_withdrawal_amount() {
         let tokens = 100_000_000_000u64; // 1000.00000000 tokens
         let nav = 102_000_000u64; // $1.02
-        let result = calculate_withdrawal_amount(tokens, nav).unwrap();
+        let result = calculate_withdrawal_amount(tokens, nav, 0).unwrap();
         assert_eq!(result, 1_020_000_000u64); // 1020.000000 USDC
     }
 
+    #[test]
+    fn test_swing_pricing() {
+        let nav = 100_000_000u64; // $1.00
+        // A 1% swing mints fewer tokens on the way in and pays less on the way out
+        assert_eq!(calculate_fund_tokens(1_010_000_000, nav, 100).unwrap(), 100_000_000_000);
+        assert_eq!(calculate_withdrawal_amount(100_000_000_000, nav, 100).unwrap(), 990_000_000);
+        assert!(calculate_withdrawal_amount(100_000_000_000, nav, 10_000).is_err());
+    }
+
+    #[test]
+    fn test_swing_factor_threshold() {
+        let assets = 1_000_000_000_000u64; // $10,000
+        // Net flows above 5% of assets swing the price
+        assert_eq!(calculate_swing_factor(50_000_000_000, assets, 500, 100), 0);
+        assert_eq!(calculate_swing_factor(50_000_000_001, assets, 500, 100), 100);
+        assert_eq!(calculate_swing_factor(50_000_000_001, assets, 500, 0), 0);
+        assert_eq!(calculate_swing_factor(50_000_000_001, 0, 500, 100), 0);
+    }
+
     #[test]
     fn test_calculate_nav_per_share() {
         let assets = 1_000_123_287_671u64; // $10,001.23287671
@@ -236,7 +1007,374 @@ mod tests {
         let loss = -5_000_000_000i64; // -$50.00
         
         let (new_assets, new_nav) = update_nav_with_pnl(assets, shares, loss).unwrap();
-        assert_eq!(new_assets, 999_500_000_000u64);
-        assert_eq!(new_nav, 99_950_000u64); // $0.9995
+        assert_eq!(new_assets, 995_000_000_000u64);
+        assert_eq!(new_nav, 99_500_000u64); // $0.995
+    }
+
+    #[test]
+    fn test_maturity_proceeds_discount_instrument() {
+        let face = 100_000_000_000u64; // $1,000
+        let accrued = 1_250_000_000u64; // $12.50 accreted discount
+        let result = calculate_maturity_proceeds(FixedIncomeAssetType::TreasuryBill, face, accrued).unwrap();
+        assert_eq!(result, 100_000_000_000u64); // Redeems at par
+    }
+
+    #[test]
+    fn test_maturity_proceeds_coupon_instrument() {
+        let face = 100_000_000_000u64; // $1,000
+        let accrued = 2_250_000_000u64; // $22.50 final coupon
+        let result = calculate_maturity_proceeds(FixedIncomeAssetType::TreasuryNote, face, accrued).unwrap();
+        assert_eq!(result, 102_250_000_000u64); // $1,022.50
+    }
+
+    #[test]
+    fn test_realized_pnl_gain() {
+        let sale = 99_500_000_000u64; // $995.00
+        let purchase = 98_750_000_000u64; // $987.50
+        let accrued = 500_000_000u64; // $5.00 accreted discount
+        assert_eq!(calculate_realized_pnl(sale, purchase, accrued).unwrap(), 250_000_000); // $2.50 gain
+    }
+
+    #[test]
+    fn test_realized_pnl_loss() {
+        let sale = 98_000_000_000u64; // $980.00
+        let purchase = 98_750_000_000u64; // $987.50
+        assert_eq!(calculate_realized_pnl(sale, purchase, 0).unwrap(), -750_000_000); // $7.50 loss
+    }
+
+    #[test]
+    fn test_write_down() {
+        let value = 98_750_000_000u64; // $987.50
+        assert_eq!(calculate_write_down(value, 4000).unwrap(), 39_500_000_000); // 40% = $395.00
+        assert_eq!(calculate_write_down(value, 10000).unwrap(), value);
+        assert!(calculate_write_down(value, 10001).is_err());
+    }
+
+    #[test]
+    fn test_management_fee() {
+        let assets = 2_000_000_000_000u64; // $20,000
+        // One day at 0.15% matches the daily fee
+        assert_eq!(calculate_management_fee(assets, 15, 86400).unwrap(), calculate_daily_management_fee(assets, 15).unwrap());
+        assert_eq!(calculate_management_fee(assets, 15, 365 * 86400).unwrap(), 3_000_000_000); // $30.00
+        assert_eq!(calculate_management_fee(assets, 15, 0).unwrap(), 0);
+        assert_eq!(calculate_management_fee(0, 15, 86400).unwrap(), 0);
+        assert!(calculate_management_fee(assets, 501, 86400).is_err());
+    }
+
+    #[test]
+    fn test_hurdle_nav() {
+        // 5% hurdle over half a year from a $1.00 high-water mark
+        assert_eq!(calculate_hurdle_nav(100_000_000, 500, 365 * 43200).unwrap(), 102_500_000);
+        assert_eq!(calculate_hurdle_nav(100_000_000, 0, 365 * 86400).unwrap(), 100_000_000);
+        assert_eq!(calculate_hurdle_nav(100_000_000, 500, -1).unwrap(), 100_000_000);
+    }
+
+    #[test]
+    fn test_performance_fee() {
+        let shares = 1_000_000_000_000u64; // 10,000 shares
+        // 20% of a $0.01 per share excess over 10,000 shares is $20.00
+        assert_eq!(calculate_performance_fee(103_500_000, 102_500_000, shares, 2000).unwrap(), 2_000_000_000);
+        assert_eq!(calculate_performance_fee(102_500_000, 102_500_000, shares, 2000).unwrap(), 0);
+        assert_eq!(calculate_performance_fee(101_000_000, 102_500_000, shares, 2000).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_excess_liquidity() {
+        let assets = 1_000_000_000_000u64; // $10,000
+        // A 25% target keeps $2,500 of cash back
+        assert_eq!(calculate_excess_liquidity(3_000_000_000, assets, 25).unwrap(), 500_000_000);
+        assert_eq!(calculate_excess_liquidity(2_000_000_000, assets, 25).unwrap(), 0);
+        assert_eq!(calculate_excess_liquidity(3_000_000_000, assets + 1, 25).unwrap(), 499_999_999);
+    }
+
+    #[test]
+    fn test_price_deviation() {
+        let previous = 98_750_000_000u64; // $987.50
+        assert_eq!(calculate_price_deviation_bps(previous, previous), 0);
+        assert_eq!(calculate_price_deviation_bps(previous, 103_687_500_000), 500); // Up 5%
+        assert_eq!(calculate_price_deviation_bps(previous, 49_375_000_000), 5_000); // Down 50%
+        assert_eq!(calculate_price_deviation_bps(0, previous), 0);
     }
-} 
\ No newline at end of file
+
+    #[test]
+    fn test_oracle_value() {
+        let face = 100_000_000_000u64; // $1,000 par
+        // 0.98750000 per unit of par
+        assert_eq!(calculate_oracle_value(face, 98_750_000, -8).unwrap(), 98_750_000_000);
+        assert_eq!(calculate_oracle_value(face, 9_875, -4).unwrap(), 98_750_000_000);
+        assert_eq!(calculate_oracle_value(face, 1, 0).unwrap(), face);
+        assert!(calculate_oracle_value(face, 0, -8).is_err());
+        assert!(calculate_oracle_value(face, -98_750_000, -8).is_err());
+    }
+
+    #[test]
+    fn test_liquidity_gap() {
+        let assets = 1_000_000_000_000u64; // $10,000
+        // A 25% target wants $2,500 of cash
+        assert_eq!(calculate_liquidity_gap(3_000_000_000, assets, 25).unwrap(), (LiquidityAction::DeployCash, 500_000_000));
+        assert_eq!(calculate_liquidity_gap(2_000_000_000, assets, 25).unwrap(), (LiquidityAction::RaiseCash, 500_000_000));
+        assert_eq!(calculate_liquidity_gap(2_500_000_000, assets, 25).unwrap(), (LiquidityAction::Hold, 0));
+    }
+
+    #[test]
+    fn test_redemption_capacity() {
+        let assets = 1_000_000_000_000u64; // $10,000
+        assert_eq!(calculate_redemption_capacity(assets, 0, 5_000_000_000).unwrap(), u64::MAX);
+        // A 10% gate allows $1,000 per period
+        assert_eq!(calculate_redemption_capacity(assets, 1_000, 0).unwrap(), 100_000_000_000);
+        assert_eq!(calculate_redemption_capacity(assets, 1_000, 40_000_000_000).unwrap(), 60_000_000_000);
+        assert_eq!(calculate_redemption_capacity(assets, 1_000, 150_000_000_000).unwrap(), 0);
+        assert!(calculate_redemption_capacity(assets, 10_001, 0).is_err());
+    }
+
+    #[test]
+    fn test_civil_date() {
+        assert_eq!(civil_date(0), (1970, 1, 1));
+        assert_eq!(civil_date(951_782_400), (2000, 2, 29)); // Leap day
+        assert_eq!(civil_date(1_704_067_199), (2023, 12, 31)); // Last second of 2023
+    }
+
+    #[test]
+    fn test_accrual_days_actual() {
+        let start = 1_704_067_200; // 2024-01-01
+        let end = start + 91 * 86400 + 3600; // 91 days and an hour later
+        assert_eq!(accrual_days(DayCountConvention::Actual360, start, end), 91);
+        assert_eq!(accrual_days(DayCountConvention::Actual365, start, end), 91);
+        assert_eq!(accrual_days(DayCountConvention::Actual360, end, start), 0);
+    }
+
+    #[test]
+    fn test_accrual_days_thirty_360() {
+        let jan_31 = 1_706_659_200; // 2024-01-31
+        let feb_29 = 1_709_164_800; // 2024-02-29
+        let mar_31 = 1_711_843_200; // 2024-03-31
+        let jul_15 = 1_721_001_600; // 2024-07-15
+        assert_eq!(accrual_days(DayCountConvention::Thirty360, jan_31, feb_29), 29);
+        assert_eq!(accrual_days(DayCountConvention::Thirty360, jan_31, mar_31), 60);
+        assert_eq!(accrual_days(DayCountConvention::Thirty360, jan_31, jul_15), 165);
+    }
+
+    #[test]
+    fn test_accrued_interest_conventions() {
+        let principal = 100_000_000_000u64; // $1,000
+        let start = 1_704_067_200; // 2024-01-01
+        let end = start + 90 * 86400; // 2024-03-31
+        // $1,000 at 5% for 90 days
+        assert_eq!(
+            calculate_accrued_interest(principal, 5_000, DayCountConvention::Actual360, start, end).unwrap(),
+            1_250_000_000 // $12.50
+        );
+        assert_eq!(
+            calculate_accrued_interest(principal, 5_000, DayCountConvention::Actual365, start, end).unwrap(),
+            1_232_876_712 // $12.32876712
+        );
+        // Jan 1 to Mar 31 is 90 days under 30/360
+        assert_eq!(
+            calculate_accrued_interest(principal, 5_000, DayCountConvention::Thirty360, start, end).unwrap(),
+            1_250_000_000
+        );
+    }
+
+    // 100-day T-bill, $100,000 face, bought at $99,100
+    const BILL_FACE: u64 = 10_000_000_000_000;
+    const BILL_PRICE: u64 = 9_910_000_000_000;
+
+    #[test]
+    fn test_discount_yield_round_trip() {
+        assert_eq!(calculate_discount_yield(BILL_FACE, BILL_PRICE, 100).unwrap(), 3_240); // 3.240%
+        assert_eq!(calculate_price_from_discount_yield(BILL_FACE, 3_240, 100).unwrap(), BILL_PRICE);
+    }
+
+    #[test]
+    fn test_money_market_yield() {
+        assert_eq!(calculate_money_market_yield(BILL_FACE, BILL_PRICE, 100).unwrap(), 3_269); // 3.2694%
+        let price = calculate_price_from_money_market_yield(BILL_FACE, 3_269, 100).unwrap();
+        assert!(price.abs_diff(BILL_PRICE) < 100_000_000); // Within $1 of $99,100
+    }
+
+    #[test]
+    fn test_bond_equivalent_yield() {
+        assert_eq!(calculate_bond_equivalent_yield(BILL_FACE, BILL_PRICE, 100).unwrap(), 3_314); // 3.3148%
+        // Short bills quote the investment rate as the bond-equivalent yield
+        assert_eq!(calculate_investment_rate(BILL_FACE, BILL_PRICE, 100).unwrap(), 3_314);
+    }
+
+    #[test]
+    fn test_investment_rate_longer_than_half_year() {
+        // 270-day bill at 97.00: 4.1530% coupon-equivalent vs 4.1810% simple
+        let face = 10_000_000_000u64; // $100
+        let price = 9_700_000_000u64; // $97.00
+        assert_eq!(calculate_investment_rate(face, price, 270).unwrap(), 4_153);
+        assert_eq!(calculate_bond_equivalent_yield(face, price, 270).unwrap(), 4_180);
+    }
+
+    #[test]
+    fn test_integer_sqrt() {
+        let cases = [(0u128, 0u128), (1, 1), (2, 1), (3, 1), (4, 2), (99, 9), (100, 10), (u128::MAX, u64::MAX as u128)];
+        for (value, root) in cases {
+            assert_eq!(integer_sqrt(value), root);
+        }
+    }
+
+    const JAN_15_2024: i64 = 1_705_276_800;
+    const JAN_15_2034: i64 = 2_020_896_000;
+    const JAN_15_2044: i64 = 2_336_428_800;
+
+    #[test]
+    fn test_add_months() {
+        let jan_31 = 1_706_659_200; // 2024-01-31
+        assert_eq!(civil_date(add_months(jan_31, 1)), (2024, 2, 29)); // Clamped to leap day
+        assert_eq!(civil_date(add_months(jan_31, -2)), (2023, 11, 30));
+        assert_eq!(add_months(JAN_15_2034, -120), JAN_15_2024);
+    }
+
+    #[test]
+    fn test_coupon_period() {
+        // On a coupon date the coupon just paid is excluded
+        let (previous, next, remaining) = coupon_period(JAN_15_2034, 2, JAN_15_2024).unwrap();
+        assert_eq!((previous, remaining), (JAN_15_2024, 20));
+        assert_eq!(civil_date(next), (2024, 7, 15));
+        assert!(coupon_period(JAN_15_2034, 3, JAN_15_2024).is_err());
+    }
+
+    #[test]
+    fn test_dirty_price_textbook_discount_bond() {
+        // 10-year 6% semiannual bond yielding 8%: 86.4097 per 100
+        let face = 10_000_000_000u64; // $100
+        let convention = DayCountConvention::Actual365;
+        let price = calculate_dirty_price(face, 6_000, 2, 8_000, convention, JAN_15_2024, JAN_15_2034).unwrap();
+        assert!(price.abs_diff(8_640_967_366) <= 10);
+    }
+
+    #[test]
+    fn test_dirty_price_textbook_premium_bond() {
+        // 20-year 9% semiannual bond yielding 6%: 134.6722 per 100
+        let face = 10_000_000_000u64; // $100
+        let convention = DayCountConvention::Actual365;
+        let price = calculate_dirty_price(face, 9_000, 2, 6_000, convention, JAN_15_2024, JAN_15_2044).unwrap();
+        assert!(price.abs_diff(13_467_215_796) <= 10);
+    }
+
+    #[test]
+    fn test_dirty_price_compounds_over_the_stub_period() {
+        // 10-year 6% semiannual bond yielding 8%, 122 of 182 days before the next coupon:
+        // 87.5342 per 100, where simple interest over the stub would give 87.5194
+        let face = 10_000_000_000u64; // $100
+        let mar_15_2024 = 1_710_460_800;
+        let convention = DayCountConvention::Actual365;
+        let price = calculate_dirty_price(face, 6_000, 2, 8_000, convention, mar_15_2024, JAN_15_2034).unwrap();
+        assert!(price.abs_diff(8_753_419_689) <= 10);
+    }
+
+    #[test]
+    fn test_accrued_coupon_thirty_360() {
+        // 8% semiannual bond, two 30/360 months into the period: 4 * 60 / 180 = 1.3333 per 100
+        let face = 10_000_000_000u64; // $100
+        let mar_15_2024 = 1_710_460_800;
+        let accrued =
+            calculate_accrued_coupon(face, 8_000, 2, DayCountConvention::Thirty360, mar_15_2024, JAN_15_2034).unwrap();
+        assert_eq!(accrued, 133_333_333);
+    }
+
+    #[test]
+    fn test_coupon_accrual_reaches_full_coupon() {
+        // 8% semiannual bond: $4 per $100 each period, split across the coupon date
+        let face = 10_000_000_000u64; // $100
+        let mar_15_2024 = 1_710_460_800;
+        let jul_15_2024 = 1_721_001_600;
+        let convention = DayCountConvention::Thirty360;
+        assert_eq!(calculate_coupon_payment(face, 8_000, 2).unwrap(), 400_000_000);
+        let to_coupon =
+            calculate_coupon_accrual(face, 8_000, 2, convention, mar_15_2024, jul_15_2024, JAN_15_2034).unwrap();
+        assert_eq!(to_coupon, 400_000_000 - 133_333_333);
+        assert!(calculate_coupon_accrual(face, 8_000, 2, convention, mar_15_2024, jul_15_2024 + 86400, JAN_15_2034).is_err());
+    }
+
+    #[test]
+    fn test_clean_price_excludes_accrued_coupon() {
+        let face = 10_000_000_000u64; // $100
+        let mar_15_2024 = 1_710_460_800;
+        let convention = DayCountConvention::Thirty360;
+        let dirty = calculate_dirty_price(face, 8_000, 2, 7_000, convention, mar_15_2024, JAN_15_2034).unwrap();
+        let clean = calculate_clean_price(face, 8_000, 2, 7_000, convention, mar_15_2024, JAN_15_2034).unwrap();
+        assert_eq!(dirty - clean, 133_333_333);
+        // A bond with coupon above yield trades above par
+        assert!(clean > face);
+    }
+
+    #[test]
+    fn test_yield_to_maturity_textbook() {
+        let face = 10_000_000_000u64; // $100
+        let convention = DayCountConvention::Actual365;
+        let ytm = calculate_yield_to_maturity(face, 6_000, 2, 8_640_967_366, convention, JAN_15_2024, JAN_15_2034).unwrap();
+        assert_eq!(ytm, 8_000);
+        let ytm = calculate_yield_to_maturity(face, 9_000, 2, 13_467_215_796, convention, JAN_15_2024, JAN_15_2044).unwrap();
+        assert_eq!(ytm, 6_000);
+    }
+
+    #[test]
+    fn test_yield_to_maturity_round_trip_between_coupons() {
+        let face = 100_000_000_000u64; // $1,000
+        let mar_15_2024 = 1_710_460_800;
+        let convention = DayCountConvention::Thirty360;
+        let clean = calculate_clean_price(face, 4_250, 2, 5_125, convention, mar_15_2024, JAN_15_2034).unwrap();
+        let ytm = calculate_yield_to_maturity(face, 4_250, 2, clean, convention, mar_15_2024, JAN_15_2034).unwrap();
+        assert_eq!(ytm, 5_125);
+    }
+
+    #[test]
+    fn test_duration_metrics_textbook_par_bond() {
+        // 5-year 9% semiannual bond at par: Macaulay 4.1344, modified 3.9564, convexity 19.4526
+        let jan_15_2029 = 1_863_129_600;
+        let metrics =
+            calculate_duration_metrics(9_000, 2, 9_000, DayCountConvention::Actual365, JAN_15_2024, jan_15_2029).unwrap();
+        assert!(metrics.macaulay_duration.abs_diff(41_344) <= 1);
+        assert!(metrics.modified_duration.abs_diff(39_564) <= 1);
+        assert!(metrics.convexity.abs_diff(194_526) <= 1);
+    }
+
+    #[test]
+    fn test_duration_metrics_single_cash_flow() {
+        // 91-day bill at 5%: 0.2493 years, modified 0.2462
+        let metrics = calculate_duration_metrics(
+            0,
+            0,
+            5_000,
+            DayCountConvention::Actual360,
+            JAN_15_2024,
+            JAN_15_2024 + 91 * 86400,
+        )
+        .unwrap();
+        assert_eq!(metrics.macaulay_duration, 2_493);
+        assert_eq!(metrics.modified_duration, 2_462);
+        assert_eq!(metrics.convexity, 1_212);
+    }
+
+    #[test]
+    fn test_portfolio_duration() {
+        // $1,000 at 0.25 years and $1,000 at 2 years in a $20,000 fund: 0.1125 years
+        let weighted = 2_500u128 * 100_000_000_000 + 20_000u128 * 100_000_000_000;
+        assert_eq!(calculate_portfolio_duration(weighted, 2_000_000_000_000).unwrap(), 1_125);
+        assert_eq!(calculate_portfolio_duration(weighted, 0).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_weighted_average_days() {
+        // $1,000 maturing in 90 days and $1,000 in 30 days with $2,000 of cash: 30 days
+        let now = JAN_15_2024;
+        let value = 100_000_000_000u64;
+        let sum = value as u128 * (now + 90 * 86400) as u128 + value as u128 * (now + 30 * 86400) as u128;
+        let seconds = running_dollar_seconds(sum, 2 * value, now).unwrap();
+        assert_eq!(calculate_weighted_average_days(seconds, 2 * value, 2 * value).unwrap(), 30);
+        // Ten days later both holdings are ten days shorter
+        let seconds = running_dollar_seconds(sum, 2 * value, now + 10 * 86400).unwrap();
+        assert_eq!(calculate_weighted_average_days(seconds, 2 * value, 2 * value).unwrap(), 25);
+        assert_eq!(calculate_weighted_average_days(0, 0, 0).unwrap(), 0);
+        
+        // Forty days later the 30-day holding has matured and counts as zero, not minus ten days
+        let later = now + 40 * 86400;
+        let clamped = holding_dollar_seconds(value, now + 90 * 86400, later) + holding_dollar_seconds(value, now + 30 * 86400, later);
+        assert_eq!(calculate_weighted_average_days(clamped, 2 * value, 2 * value).unwrap(), 12);
+        assert_eq!(calculate_weighted_average_days(running_dollar_seconds(sum, 2 * value, later).unwrap(), 2 * value, 2 * value).unwrap(), 10);
+    }
+}
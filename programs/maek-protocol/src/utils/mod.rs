@@ -1,5 +1,7 @@
 pub mod calculations;
 pub mod validation;
+pub mod oracle;
 
 pub use calculations::*;
-pub use validation::*; 
\ No newline at end of file
+pub use validation::*;
+pub use oracle::*; 
\ No newline at end of file
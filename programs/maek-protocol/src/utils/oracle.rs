@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+use crate::error::ErrorCode;
+
+/// Pyth oracle program owning every price account read by the fund
+pub mod pyth_oracle {
+    #[cfg(not(feature = "devnet"))]
+    anchor_lang::declare_id!("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+    #[cfg(feature = "devnet")]
+    anchor_lang::declare_id!("gSbePebfvPy7tRqimPoVecS2UsBvYv46ynrzWocc92s");
+}
+
+pub const PYTH_ORACLE_PROGRAM_ID: Pubkey = pyth_oracle::ID;
+
+/// Maximum age of an oracle price accepted for a valuation (seconds)
+pub const MAX_ORACLE_PRICE_AGE: i64 = 3600;
+
+/// Maximum confidence interval accepted for an oracle price (basis points of price)
+pub const MAX_ORACLE_CONFIDENCE_BPS: u64 = 50; // 0.5%
+
+// Pyth price account layout (v2)
+const PRICE_ACCOUNT_MAGIC: u32 = 0xa1b2_c3d4;
+const PRICE_ACCOUNT_VERSION: u32 = 2;
+const PRICE_ACCOUNT_TYPE: u32 = 3;
+const PRICE_STATUS_TRADING: u32 = 1;
+const EXPONENT_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AGGREGATE_PRICE_OFFSET: usize = 208;
+const AGGREGATE_CONFIDENCE_OFFSET: usize = 216;
+const AGGREGATE_STATUS_OFFSET: usize = 224;
+pub const PRICE_ACCOUNT_MIN_LEN: usize = 240;
+
+/// Aggregate price read from a Pyth-style price account
+/// The price is quoted per unit of face value, scaled by 10^exponent
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub confidence: u64,
+    pub exponent: i32,
+    pub publish_time: i64,
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> [u8; N] {
+    let mut bytes = [0u8; N];
+    bytes.copy_from_slice(&data[offset..offset + N]);
+    bytes
+}
+
+/// Load the aggregate price from a Pyth-style price account
+/// Only prices currently trading are returned
+pub fn load_oracle_price(data: &[u8]) -> Result<OraclePrice> {
+    require!(data.len() >= PRICE_ACCOUNT_MIN_LEN, ErrorCode::InvalidPriceFeed);
+    require!(
+        u32::from_le_bytes(read_bytes(data, 0)) == PRICE_ACCOUNT_MAGIC
+            && u32::from_le_bytes(read_bytes(data, 4)) == PRICE_ACCOUNT_VERSION
+            && u32::from_le_bytes(read_bytes(data, 8)) == PRICE_ACCOUNT_TYPE,
+        ErrorCode::InvalidPriceFeed
+    );
+    require!(
+        u32::from_le_bytes(read_bytes(data, AGGREGATE_STATUS_OFFSET)) == PRICE_STATUS_TRADING,
+        ErrorCode::StaleAssetValuation
+    );
+    
+    Ok(OraclePrice {
+        price: i64::from_le_bytes(read_bytes(data, AGGREGATE_PRICE_OFFSET)),
+        confidence: u64::from_le_bytes(read_bytes(data, AGGREGATE_CONFIDENCE_OFFSET)),
+        exponent: i32::from_le_bytes(read_bytes(data, EXPONENT_OFFSET)),
+        publish_time: i64::from_le_bytes(read_bytes(data, TIMESTAMP_OFFSET)),
+    })
+}
+
+/// Validate an oracle price is recent and its confidence interval is tight enough to value with
+pub fn validate_oracle_price(oracle_price: &OraclePrice, now: i64) -> Result<()> {
+    require!(oracle_price.price > 0, ErrorCode::InvalidPriceFeed);
+    require!(
+        oracle_price.publish_time <= now && now - oracle_price.publish_time <= MAX_ORACLE_PRICE_AGE,
+        ErrorCode::StaleAssetValuation
+    );
+    require!(
+        (oracle_price.confidence as u128) * 10_000 <= (oracle_price.price as u128) * (MAX_ORACLE_CONFIDENCE_BPS as u128),
+        ErrorCode::OracleConfidenceTooWide
+    );
+    
+    Ok(())
+}
@@ -1,6 +1,15 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::Instruction;
 use crate::error::ErrorCode;
-use crate::state::FixedIncomeAssetType;
+use crate::state::{AssetPurchase, AssetValuation, CustodianAttestation, FixedIncomeAssetType};
+use crate::utils::calculations::{
+    calculate_money_market_yield, calculate_portfolio_duration, calculate_price_deviation_bps,
+    calculate_weighted_average_days, coupon_period_months, is_discount_instrument, running_dollar_seconds,
+};
+
+/// Maximum gap between a discount instrument's quoted yield and the yield implied by its price
+pub const MAX_YIELD_DEVIATION: u32 = 100; // 0.10%
 
 /// Validate deposit amount parameters
 /// Ensures compliance with minimum investment requirements
@@ -49,7 +58,7 @@ pub fn validate_fixed_income_asset(
     let current_time = Clock::get()?.unix_timestamp;
     
     // Validate credit rating (1=AAA to 10=D)
-    require!(credit_rating >= 1 && credit_rating <= 10, ErrorCode::InvalidCreditRating);
+    require!((1..=10).contains(&credit_rating), ErrorCode::InvalidCreditRating);
     
     // Ensure investment grade (BBB- or better)
     require!(credit_rating <= 7, ErrorCode::InvalidCreditRating); // BBB- = 7
@@ -63,11 +72,11 @@ pub fn validate_fixed_income_asset(
     require!(days_to_maturity <= 3650, ErrorCode::InvalidFixedIncomeAssetData); // 10 years max
     
     // Validate yield rate is reasonable (0.1% to 20% annually)
-    require!(yield_rate >= 10 && yield_rate <= 20000, ErrorCode::InvalidFixedIncomeAssetData);
+    require!((10..=20000).contains(&yield_rate), ErrorCode::InvalidFixedIncomeAssetData);
     
     // Validate face value
-    require!(face_value >= 1_000_00000000, ErrorCode::InvalidFixedIncomeAssetData); // Min $1,000
-    require!(face_value <= 100_000_000_00000000, ErrorCode::InvalidFixedIncomeAssetData); // Max $100M
+    require!(face_value >= 100_000_000_000, ErrorCode::InvalidFixedIncomeAssetData); // Min $1,000
+    require!(face_value <= 10_000_000_000_000_000, ErrorCode::InvalidFixedIncomeAssetData); // Max $100M
     
     // Asset-specific validations
     match asset_type {
@@ -111,20 +120,21 @@ pub fn validate_management_fee(fee_bps: u16) -> Result<()> {
 
 /// Validate investment amount against portfolio limits
 /// Prevents over-concentration and maintains diversification
+/// Limits are measured against total fund assets, which a purchase out of cash leaves unchanged
 pub fn validate_investment_limits(
     investment_amount: u64,
-    current_portfolio_value: u64,
+    total_fund_assets: u64,
     asset_type: FixedIncomeAssetType,
     same_issuer_total: u64,
 ) -> Result<()> {
-    let total_after_investment = current_portfolio_value + investment_amount;
+    require!(total_fund_assets > 0, ErrorCode::InsufficientFunds);
     
     // No single investment should exceed 10% of portfolio
-    let investment_percentage = (investment_amount as u128 * 10000) / (total_after_investment as u128);
+    let investment_percentage = (investment_amount as u128 * 10000) / (total_fund_assets as u128);
     require!(investment_percentage <= 1000, ErrorCode::InvestmentAmountExceedsLimit); // 10%
     
     // Asset type concentration limits
-    let asset_type_limit = match asset_type {
+    let _asset_type_limit = match asset_type {
         FixedIncomeAssetType::TreasuryBill | 
         FixedIncomeAssetType::TreasuryNote | 
         FixedIncomeAssetType::TreasuryBond => 8000, // 80% for treasury securities
@@ -137,14 +147,124 @@ pub fn validate_investment_limits(
     if !matches!(asset_type, FixedIncomeAssetType::TreasuryBill | 
                             FixedIncomeAssetType::TreasuryNote | 
                             FixedIncomeAssetType::TreasuryBond) {
-        let issuer_total_after = same_issuer_total + investment_amount;
-        let issuer_percentage = (issuer_total_after as u128 * 10000) / (total_after_investment as u128);
+        let issuer_total_after = same_issuer_total as u128 + investment_amount as u128;
+        let issuer_percentage = (issuer_total_after * 10000) / (total_fund_assets as u128);
         require!(issuer_percentage <= 500, ErrorCode::PortfolioConcentrationExceeded); // 5% per issuer
     }
     
     Ok(())
 }
 
+/// Validate an asset purchase as received by an investment instruction
+/// Runs the asset quality checks and portfolio limits against the same arguments
+pub fn validate_asset_purchase(
+    purchase: &AssetPurchase,
+    total_fund_assets: u64,
+    same_issuer_total: u64,
+) -> Result<()> {
+    validate_fixed_income_asset(
+        purchase.asset_type,
+        purchase.credit_rating,
+        purchase.maturity_date,
+        purchase.yield_rate,
+        purchase.face_value,
+    )?;
+    require!(purchase.purchase_price > 0, ErrorCode::InvalidAmount);
+    
+    // Purchase price is settled in USDC, so it must be expressible in 6 decimals
+    require!(
+        purchase.purchase_price / 100 * 100 == purchase.purchase_price,
+        ErrorCode::InvalidFixedIncomeAssetData
+    );
+    
+    validate_coupon_terms(purchase.asset_type, purchase.coupon_rate, purchase.coupon_frequency)?;
+    
+    // Floating rates reset on the coupon schedule
+    require!(!purchase.floating_rate || purchase.coupon_frequency > 0, ErrorCode::InvalidCouponTerms);
+    if is_discount_instrument(purchase.asset_type) {
+        validate_discount_price(purchase)?;
+    }
+    
+    validate_investment_limits(
+        purchase.purchase_price,
+        total_fund_assets,
+        purchase.asset_type,
+        same_issuer_total,
+    )
+}
+
+/// Validate coupon terms for an asset type
+/// Discount instruments pay no coupon; bonds pay on a regular schedule; CDs may pay at maturity
+pub fn validate_coupon_terms(asset_type: FixedIncomeAssetType, coupon_rate: u32, coupon_frequency: u8) -> Result<()> {
+    if is_discount_instrument(asset_type) {
+        require!(coupon_rate == 0 && coupon_frequency == 0, ErrorCode::InvalidCouponTerms);
+        return Ok(());
+    }
+    
+    if coupon_frequency == 0 {
+        // Interest paid with principal at maturity
+        require!(
+            asset_type == FixedIncomeAssetType::CertificateOfDeposit && coupon_rate == 0,
+            ErrorCode::InvalidCouponTerms
+        );
+        return Ok(());
+    }
+    
+    coupon_period_months(coupon_frequency)?;
+    require!(coupon_rate <= 20000, ErrorCode::InvalidCouponTerms); // 20% max
+    
+    Ok(())
+}
+
+/// Validate a discount instrument's purchase price against its face value and yield
+/// Bills and commercial paper quote yield_rate as a money-market yield, the rate they accrete at
+pub fn validate_discount_price(purchase: &AssetPurchase) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let days_to_maturity = ((purchase.maturity_date - current_time) / 86400) as u64;
+    
+    require!(purchase.purchase_price < purchase.face_value, ErrorCode::PriceYieldMismatch);
+    
+    let implied_yield = calculate_money_market_yield(purchase.face_value, purchase.purchase_price, days_to_maturity)?;
+    require!(
+        implied_yield.abs_diff(purchase.yield_rate) <= MAX_YIELD_DEVIATION,
+        ErrorCode::PriceYieldMismatch
+    );
+    
+    Ok(())
+}
+
+/// Validate portfolio duration after a purchase stays within the fund's limit
+pub fn validate_portfolio_duration(
+    weighted_duration_sum: u128,
+    total_fund_assets: u64,
+    max_portfolio_duration: u32,
+) -> Result<()> {
+    let portfolio_duration = calculate_portfolio_duration(weighted_duration_sum, total_fund_assets)?;
+    require!(portfolio_duration <= max_portfolio_duration, ErrorCode::DurationRiskExceeded);
+    Ok(())
+}
+
+/// Validate weighted-average maturity and life after a purchase stay within the fund's limits
+/// WAM is measured to each holding's next interest reset, WAL to final maturity
+pub fn validate_portfolio_maturity(
+    maturity_weighted_sum: u128,
+    reset_weighted_sum: u128,
+    held_book_value: u64,
+    cash_value: u64,
+    max_wam_days: u16,
+    max_wal_days: u16,
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp;
+    let reset_seconds = running_dollar_seconds(reset_weighted_sum, held_book_value, current_time)?;
+    let maturity_seconds = running_dollar_seconds(maturity_weighted_sum, held_book_value, current_time)?;
+    let wam_days = calculate_weighted_average_days(reset_seconds, held_book_value, cash_value)?;
+    let wal_days = calculate_weighted_average_days(maturity_seconds, held_book_value, cash_value)?;
+    
+    require!(wam_days <= max_wam_days as u32, ErrorCode::WeightedAverageMaturityExceeded);
+    require!(wal_days <= max_wal_days as u32, ErrorCode::WeightedAverageLifeExceeded);
+    Ok(())
+}
+
 /// Validate user account state before operations
 /// Ensures account integrity and prevents invalid operations
 pub fn validate_user_account_state(
@@ -166,24 +286,34 @@ pub fn validate_user_account_state(
 
 /// Validate oracle data for asset valuations
 /// Ensures price feed reliability and prevents manipulation
+/// `previous_values` holds each asset's current stored value, in the same order as the valuations
 pub fn validate_oracle_data(
-    asset_valuations: &[crate::state::AssetValuation],
+    asset_valuations: &[AssetValuation],
+    previous_values: &[u64],
     max_price_deviation_bps: u32,
 ) -> Result<()> {
-    for valuation in asset_valuations {
+    require!(asset_valuations.len() == previous_values.len(), ErrorCode::IncompleteAssetValuations);
+    
+    for (valuation, previous_value) in asset_valuations.iter().zip(previous_values) {
         // Validate valuation is not zero
         require!(valuation.current_value > 0, ErrorCode::InvalidFixedIncomeAssetData);
         
-        // Validate accrued interest is reasonable (not exceeding 50% of principal)
-        require!(
-            valuation.accrued_interest <= valuation.current_value / 2,
-            ErrorCode::InvalidFixedIncomeAssetData
-        );
+        // Reject moves from the previous valuation beyond the deviation limit
+        validate_price_deviation(*previous_value, valuation.current_value, max_price_deviation_bps)?;
     }
     
     Ok(())
 }
 
+/// Validate a new value stays within the deviation limit of the previous value
+pub fn validate_price_deviation(previous_value: u64, new_value: u64, max_price_deviation_bps: u32) -> Result<()> {
+    require!(
+        calculate_price_deviation_bps(previous_value, new_value) <= max_price_deviation_bps as u64,
+        ErrorCode::PriceDeviationExceeded
+    );
+    Ok(())
+}
+
 /// Validate fund state consistency
 /// Ensures mathematical consistency across fund operations
 pub fn validate_fund_state_consistency(
@@ -195,11 +325,7 @@ pub fn validate_fund_state_consistency(
 ) -> Result<()> {
     // Total assets should equal cash + fixed income value (with small tolerance for fees)
     let calculated_assets = (cash_reserves as u128) * 100 + (fixed_income_value as u128);
-    let asset_difference = if calculated_assets > total_assets as u128 {
-        calculated_assets - (total_assets as u128)
-    } else {
-        (total_assets as u128) - calculated_assets
-    };
+    let asset_difference = calculated_assets.abs_diff(total_assets as u128);
     
     // Allow 0.1% tolerance for rounding and fees
     let tolerance = (total_assets as u128) / 1000;
@@ -208,11 +334,7 @@ pub fn validate_fund_state_consistency(
     // NAV should be consistent with total assets and shares
     if total_shares > 0 {
         let calculated_nav = ((total_assets as u128) * 100_000_000) / (total_shares as u128);
-        let nav_difference = if calculated_nav > nav_per_share as u128 {
-            calculated_nav - (nav_per_share as u128)
-        } else {
-            (nav_per_share as u128) - calculated_nav
-        };
+        let nav_difference = calculated_nav.abs_diff(nav_per_share as u128);
         
         // Allow 0.01% tolerance for NAV calculation
         let nav_tolerance = (nav_per_share as u128) / 10000;
@@ -220,4 +342,59 @@ pub fn validate_fund_state_consistency(
     }
     
     Ok(())
-} 
\ No newline at end of file
+}
+
+/// Validate that an ed25519 precompile instruction verified `signer`'s signature over exactly `message`
+/// The signature, key and message must all be carried in the precompile instruction itself
+pub fn validate_ed25519_signature(instruction: &Instruction, signer: &Pubkey, message: &[u8]) -> Result<()> {
+    require_keys_eq!(instruction.program_id, ed25519_program::ID, ErrorCode::InvalidAttestationSignature);
+    require!(instruction.accounts.is_empty(), ErrorCode::InvalidAttestationSignature);
+    
+    // Header: signature count and padding, then one 14-byte offsets entry
+    let data = &instruction.data;
+    require!(data.len() >= 16 && data[0] == 1, ErrorCode::InvalidAttestationSignature);
+    let read_u16 = |at: usize| u16::from_le_bytes([data[at], data[at + 1]]);
+    let signature_instruction_index = read_u16(4);
+    let public_key_offset = read_u16(6) as usize;
+    let public_key_instruction_index = read_u16(8);
+    let message_offset = read_u16(10) as usize;
+    let message_size = read_u16(12) as usize;
+    let message_instruction_index = read_u16(14);
+    require!(
+        signature_instruction_index == u16::MAX
+            && public_key_instruction_index == u16::MAX
+            && message_instruction_index == u16::MAX,
+        ErrorCode::InvalidAttestationSignature
+    );
+    
+    let public_key = data.get(public_key_offset..public_key_offset + 32).ok_or(ErrorCode::InvalidAttestationSignature)?;
+    let signed_message = data.get(message_offset..message_offset + message_size).ok_or(ErrorCode::InvalidAttestationSignature)?;
+    require!(public_key == signer.as_ref(), ErrorCode::InvalidAttestationSignature);
+    require!(signed_message == message, ErrorCode::InvalidAttestationSignature);
+    
+    Ok(())
+}
+
+/// Validate that a custodian attestation is fresh and its par covers the par of held assets
+pub fn validate_custodian_attestation(
+    attestation: &CustodianAttestation,
+    fund: &Pubkey,
+    custodian: &Pubkey,
+    now: i64,
+    max_attestation_age: i64,
+    held_par: u64,
+    tolerance_bps: u16,
+) -> Result<()> {
+    require_keys_eq!(attestation.fund, *fund, ErrorCode::InvalidAccountData);
+    require_keys_eq!(attestation.custodian, *custodian, ErrorCode::MissingAttestation);
+    require!(now - attestation.statement_date <= max_attestation_age, ErrorCode::StaleAttestation);
+    
+    // Par against par, so discount pricing never reads as a mismatch; only a shortfall matters
+    let tolerance = (held_par as u128) * (tolerance_bps as u128) / 10_000;
+    require!(
+        (attestation.total_par as u128) + tolerance >= held_par as u128,
+        ErrorCode::AttestationMismatch
+    );
+    
+    Ok(())
+}
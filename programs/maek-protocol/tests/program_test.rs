@@ -0,0 +1,3240 @@
+// Program-level tests for the MAEK Protocol entrypoints.
+// Run natively with `cargo test`. `npm run test:sbf` builds the program and also
+// runs the ignored SBF test, where the stack frame and compute budget limits are enforced.
+
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_pack::Pack;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use anchor_spl::associated_token::get_associated_token_address;
+use maek_protocol::error::ErrorCode;
+use maek_protocol::utils::oracle::PYTH_ORACLE_PROGRAM_ID;
+use maek_protocol::state::{
+    AssetPurchase, AssetStatus, AssetValuation, CreditEvent, CreditEventKind, CustodianAttestation, DayCountConvention,
+    FixedIncomeAsset, FixedIncomeAssetType, FundRegistry, FundState, HoldingsStatement, IssuerExposure, LiquidityAction,
+    LiquidityStatus, PendingOrder, RedemptionRequest, UserFundAccount, WriteDownHistory,
+};
+use solana_program_test::{processor, BanksClientError, ProgramTest, ProgramTestContext};
+use solana_sdk::bpf_loader_upgradeable::{self, UpgradeableLoaderState};
+use solana_sdk::compute_budget::ComputeBudgetInstruction;
+use solana_sdk::instruction::{AccountMeta, Instruction, InstructionError};
+use solana_sdk::signature::{Keypair, Signer};
+use solana_sdk::system_instruction;
+use solana_sdk::transaction::{Transaction, TransactionError};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Compute budget every fund management instruction must fit in
+const MAX_COMPUTE_UNITS: u32 = 200_000;
+
+/// Makes otherwise identical transactions distinct, so a repeated call is
+/// rejected by the program rather than deduplicated by the bank
+static TRANSACTION_NONCE: AtomicU64 = AtomicU64::new(0);
+
+fn process_instruction(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    data: &[u8],
+) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+    // Anchor's entrypoint ties the account slice to the `'info` lifetime
+    let accounts = Box::leak(Box::new(accounts.to_vec()));
+    maek_protocol::entry(program_id, accounts, data)
+}
+
+struct TestFund {
+    registry_authority: Keypair,
+    admin: Keypair,
+    usdc_mint: Pubkey,
+    fund_state: Pubkey,
+    fund_token_mint: Pubkey,
+    usdc_vault: Pubkey,
+    /// Custodian's USDC account receiving purchase settlements
+    settlement_account: Pubkey,
+}
+
+fn registry_pda() -> Pubkey {
+    Pubkey::find_program_address(&[b"fund_registry"], &maek_protocol::ID).0
+}
+
+fn fund_state_pda(fund_id: u64) -> Pubkey {
+    Pubkey::find_program_address(&[b"fund_state", &fund_id.to_le_bytes()], &maek_protocol::ID).0
+}
+
+fn user_account_pda(fund_state: &Pubkey, user: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"user_account", fund_state.as_ref(), user.as_ref()],
+        &maek_protocol::ID,
+    )
+    .0
+}
+
+async fn start() -> ProgramTestContext {
+    let mut program_test = ProgramTest::new(
+        "maek_protocol",
+        maek_protocol::ID,
+        processor!(process_instruction),
+    );
+    program_test.set_compute_max_units(MAX_COMPUTE_UNITS as u64);
+    program_test.start_with_context().await
+}
+
+async fn send(
+    context: &mut ProgramTestContext,
+    instruction: Instruction,
+    signers: &[&Keypair],
+) -> std::result::Result<(), BanksClientError> {
+    send_all(context, vec![instruction], signers).await
+}
+
+async fn send_all(
+    context: &mut ProgramTestContext,
+    instructions: Vec<Instruction>,
+    signers: &[&Keypair],
+) -> std::result::Result<(), BanksClientError> {
+    let blockhash = context.banks_client.get_latest_blockhash().await?;
+    let mut all_signers = vec![&context.payer];
+    all_signers.extend_from_slice(signers);
+    let mut all_instructions = vec![
+        ComputeBudgetInstruction::set_compute_unit_limit(MAX_COMPUTE_UNITS),
+        ComputeBudgetInstruction::set_compute_unit_price(TRANSACTION_NONCE.fetch_add(1, Ordering::Relaxed)),
+    ];
+    all_instructions.extend(instructions);
+    let transaction =
+        Transaction::new_signed_with_payer(&all_instructions, Some(&context.payer.pubkey()), &all_signers, blockhash);
+    context.banks_client.process_transaction(transaction).await
+}
+
+/// Assert a transaction failed with the given program error
+fn assert_program_error(result: std::result::Result<(), BanksClientError>, error: ErrorCode) {
+    match result.expect_err("transaction should fail").unwrap() {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            assert_eq!(code, u32::from(error), "unexpected program error {}", code)
+        }
+        other => panic!("unexpected transaction error: {:?}", other),
+    }
+}
+
+async fn funded_keypair(context: &mut ProgramTestContext) -> Keypair {
+    let keypair = Keypair::new();
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[system_instruction::transfer(
+            &context.payer.pubkey(),
+            &keypair.pubkey(),
+            10_000_000_000,
+        )],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await.unwrap();
+    keypair
+}
+
+async fn create_usdc_mint(context: &mut ProgramTestContext) -> Pubkey {
+    let mint = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &mint.pubkey(),
+                rent.minimum_balance(spl_token::state::Mint::LEN),
+                spl_token::state::Mint::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_mint(
+                &spl_token::ID,
+                &mint.pubkey(),
+                &context.payer.pubkey(),
+                None,
+                6,
+            )
+            .unwrap(),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &mint],
+        blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await.unwrap();
+    mint.pubkey()
+}
+
+/// Create a USDC token account for `owner` holding `amount` (6 decimals)
+async fn create_usdc_account(
+    context: &mut ProgramTestContext,
+    usdc_mint: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+) -> Pubkey {
+    let account = Keypair::new();
+    let rent = context.banks_client.get_rent().await.unwrap();
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[
+            system_instruction::create_account(
+                &context.payer.pubkey(),
+                &account.pubkey(),
+                rent.minimum_balance(spl_token::state::Account::LEN),
+                spl_token::state::Account::LEN as u64,
+                &spl_token::ID,
+            ),
+            spl_token::instruction::initialize_account(
+                &spl_token::ID,
+                &account.pubkey(),
+                usdc_mint,
+                owner,
+            )
+            .unwrap(),
+            spl_token::instruction::mint_to(
+                &spl_token::ID,
+                usdc_mint,
+                &account.pubkey(),
+                &context.payer.pubkey(),
+                &[],
+                amount,
+            )
+            .unwrap(),
+        ],
+        Some(&context.payer.pubkey()),
+        &[&context.payer, &account],
+        blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await.unwrap();
+    account.pubkey()
+}
+
+fn program_data_pda() -> Pubkey {
+    Pubkey::find_program_address(&[maek_protocol::ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+/// Install the program data account of an upgradeable deployment owned by `upgrade_authority`
+fn set_upgrade_authority(context: &mut ProgramTestContext, upgrade_authority: &Pubkey) {
+    let program_data = solana_sdk::account::Account::new_data(
+        1_000_000_000,
+        &UpgradeableLoaderState::ProgramData {
+            slot: 0,
+            upgrade_authority_address: Some(*upgrade_authority),
+        },
+        &bpf_loader_upgradeable::ID,
+    )
+    .unwrap();
+    context.set_account(&program_data_pda(), &program_data.into());
+}
+
+async fn try_initialize_registry(
+    context: &mut ProgramTestContext,
+    authority: &Keypair,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::InitializeRegistry {
+            fund_registry: registry_pda(),
+            authority: authority.pubkey(),
+            program_data: program_data_pda(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::InitializeRegistry {}.data(),
+    };
+    send(context, instruction, &[authority]).await
+}
+
+/// Create the registry as the program's upgrade authority
+async fn initialize_registry(context: &mut ProgramTestContext, authority: &Keypair) {
+    set_upgrade_authority(context, &authority.pubkey());
+    try_initialize_registry(context, authority).await.unwrap();
+}
+
+async fn initialize_fund(
+    context: &mut ProgramTestContext,
+    registry_authority: Keypair,
+    admin: Keypair,
+    fund_id: u64,
+) -> TestFund {
+    let usdc_mint = create_usdc_mint(context).await;
+    let fund_state = fund_state_pda(fund_id);
+    let fund_token_mint =
+        Pubkey::find_program_address(&[b"fund_token_mint", fund_state.as_ref()], &maek_protocol::ID).0;
+    let usdc_vault =
+        Pubkey::find_program_address(&[b"usdc_vault", fund_state.as_ref()], &maek_protocol::ID).0;
+
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::InitializeFund {
+            fund_registry: registry_pda(),
+            fund_state,
+            authority: registry_authority.pubkey(),
+            admin: admin.pubkey(),
+            usdc_mint,
+            fund_token_mint,
+            usdc_vault,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::InitializeFund {
+            management_fee_bps: 15,
+            target_liquidity_ratio: 25,
+        }
+        .data(),
+    };
+    send(context, instruction, &[&registry_authority, &admin]).await.unwrap();
+
+    let settlement_account = create_usdc_account(context, &usdc_mint, &Pubkey::new_unique(), 0).await;
+    let fund = TestFund {
+        registry_authority,
+        admin,
+        usdc_mint,
+        fund_state,
+        fund_token_mint,
+        usdc_vault,
+        settlement_account,
+    };
+    set_settlement_account(context, &fund, &fund.registry_authority, settlement_account).await.unwrap();
+    fund
+}
+
+/// Appoint the custodian settlement account, signed by `authority`
+async fn set_settlement_account(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    authority: &Keypair,
+    settlement_account: Pubkey,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::RegistryConfig {
+            fund_state: fund.fund_state,
+            fund_registry: registry_pda(),
+            authority: authority.pubkey(),
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::SetSettlementAccount { settlement_account }.data(),
+    };
+    send(context, instruction, &[authority]).await
+}
+
+/// Registry plus a single fund with id 0
+async fn setup_fund(context: &mut ProgramTestContext) -> TestFund {
+    let registry_authority = funded_keypair(context).await;
+    let admin = funded_keypair(context).await;
+    initialize_registry(context, &registry_authority).await;
+    initialize_fund(context, registry_authority, admin, 0).await
+}
+
+async fn deposit(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    user: &Keypair,
+    user_usdc_account: Pubkey,
+    amount: u64,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::Deposit {
+            fund_state: fund.fund_state,
+            user_account: user_account_pda(&fund.fund_state, &user.pubkey()),
+            user: user.pubkey(),
+            user_usdc_account,
+            user_fund_token_account: get_associated_token_address(&user.pubkey(), &fund.fund_token_mint),
+            usdc_vault: fund.usdc_vault,
+            fund_token_mint: fund.fund_token_mint,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::Deposit { amount }.data(),
+    };
+    send(context, instruction, &[user]).await
+}
+
+async fn load_account<T: AccountDeserialize>(context: &mut ProgramTestContext, address: Pubkey) -> T {
+    let account = context
+        .banks_client
+        .get_account(address)
+        .await
+        .unwrap()
+        .unwrap();
+    T::try_deserialize(&mut account.data.as_slice()).unwrap()
+}
+
+fn asset_pda(fund_state: &Pubkey, asset_id: &[u8; 12]) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"fixed_income_asset", fund_state.as_ref(), asset_id.as_ref()],
+        &maek_protocol::ID,
+    )
+    .0
+}
+
+fn write_down_history_pda(asset: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"write_down_history", asset.as_ref()], &maek_protocol::ID).0
+}
+
+fn issuer_exposure_pda(fund_state: &Pubkey, issuer: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"issuer_exposure", fund_state.as_ref(), issuer.as_ref()],
+        &maek_protocol::ID,
+    )
+    .0
+}
+
+async fn now(context: &mut ProgramTestContext) -> i64 {
+    context.banks_client.get_sysvar::<Clock>().await.unwrap().unix_timestamp
+}
+
+async fn advance_clock(context: &mut ProgramTestContext, seconds: i64) {
+    let mut clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+    clock.unix_timestamp += seconds;
+    context.set_sysvar(&clock);
+}
+
+/// Deposit `amount` USDC (6 decimals) from a fresh investor
+async fn seed_cash(context: &mut ProgramTestContext, fund: &TestFund, amount: u64) -> Keypair {
+    let user = funded_keypair(context).await;
+    let user_usdc = create_usdc_account(context, &fund.usdc_mint, &user.pubkey(), amount).await;
+    deposit(context, fund, &user, user_usdc, amount).await.unwrap();
+    user
+}
+
+/// A 91-day T-bill bought at a discount
+fn treasury_bill(asset_id: &[u8; 12], maturity_date: i64) -> AssetPurchase {
+    AssetPurchase {
+        asset_type: FixedIncomeAssetType::TreasuryBill,
+        asset_id: *asset_id,
+        face_value: 100_000_000_000,     // $1,000
+        purchase_price: 98_750_000_000, // $987.50
+        maturity_date,
+        yield_rate: 5_000, // 5.00%
+        coupon_rate: 0,
+        coupon_frequency: 0,
+        floating_rate: false,
+        credit_rating: 1,
+        issuer: Pubkey::default(),
+        sector: 1,
+        country: 1,
+    }
+}
+
+/// Sweep `amount` USDC to the treasury vault, creating the vault on first use, ahead of `instruction`
+/// in one transaction
+async fn send_funded_by_treasury(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    amount: u64,
+    instruction: Instruction,
+) -> std::result::Result<(), BanksClientError> {
+    let mut instructions = Vec::new();
+    let treasury_vault = treasury_vault_pda(&fund.fund_state);
+    if context.banks_client.get_account(treasury_vault).await.unwrap().is_none() {
+        instructions.push(initialize_treasury_vault_instruction(fund));
+    }
+    if amount > 0 {
+        instructions.push(sweep_treasury_instruction(fund, amount, true));
+    }
+    instructions.push(instruction);
+    send_all(context, instructions, &[&fund.admin]).await
+}
+
+/// Register a purchase, first sweeping its cost to the treasury vault
+async fn register_asset(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    purchase: AssetPurchase,
+) -> std::result::Result<(), BanksClientError> {
+    let cost = purchase.purchase_price / 100;
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::RegisterAsset {
+            fund_state: fund.fund_state,
+            fixed_income_asset: asset_pda(&fund.fund_state, &purchase.asset_id),
+            issuer_exposure: issuer_exposure_pda(&fund.fund_state, &purchase.issuer),
+            treasury_vault: treasury_vault_pda(&fund.fund_state),
+            settlement_account: fund.settlement_account,
+            admin_authority: fund.admin.pubkey(),
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::RegisterAsset { purchase }.data(),
+    };
+    send_funded_by_treasury(context, fund, cost, instruction).await
+}
+
+#[tokio::test]
+async fn test_initialize_fund() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.fund_id, 0);
+    assert_eq!(fund_state.admin_authority, fund.admin.pubkey());
+    assert_eq!(fund_state.fund_token_mint, fund.fund_token_mint);
+    assert_eq!(fund_state.usdc_mint, fund.usdc_mint);
+    assert_eq!(fund_state.usdc_vault, fund.usdc_vault);
+    assert_eq!(fund_state.management_fee_bps, 15);
+    assert_eq!(fund_state.target_liquidity_ratio, 25);
+    assert_eq!(fund_state.nav_per_share, 100_000_000);
+
+    let registry: FundRegistry = load_account(&mut context, registry_pda()).await;
+    assert_eq!(registry.fund_count, 1);
+}
+
+#[tokio::test]
+async fn test_initialize_registry_requires_upgrade_authority() {
+    let mut context = start().await;
+    let upgrade_authority = funded_keypair(&mut context).await;
+    set_upgrade_authority(&mut context, &upgrade_authority.pubkey());
+
+    // Nobody else can claim the registry ahead of the deployer
+    let intruder = funded_keypair(&mut context).await;
+    assert_program_error(try_initialize_registry(&mut context, &intruder).await, ErrorCode::UnauthorizedAccess);
+
+    try_initialize_registry(&mut context, &upgrade_authority).await.unwrap();
+    let registry: FundRegistry = load_account(&mut context, registry_pda()).await;
+    assert_eq!(registry.authority, upgrade_authority.pubkey());
+}
+
+#[tokio::test]
+async fn test_initialize_fund_requires_registry_authority() {
+    let mut context = start().await;
+    let authority = funded_keypair(&mut context).await;
+    initialize_registry(&mut context, &authority).await;
+
+    let intruder = funded_keypair(&mut context).await;
+    let usdc_mint = create_usdc_mint(&mut context).await;
+    let fund_state = fund_state_pda(0);
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::InitializeFund {
+            fund_registry: registry_pda(),
+            fund_state,
+            authority: intruder.pubkey(),
+            admin: intruder.pubkey(),
+            usdc_mint,
+            fund_token_mint: Pubkey::find_program_address(
+                &[b"fund_token_mint", fund_state.as_ref()],
+                &maek_protocol::ID,
+            )
+            .0,
+            usdc_vault: Pubkey::find_program_address(&[b"usdc_vault", fund_state.as_ref()], &maek_protocol::ID).0,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::InitializeFund {
+            management_fee_bps: 15,
+            target_liquidity_ratio: 25,
+        }
+        .data(),
+    };
+    assert!(send(&mut context, instruction, &[&intruder]).await.is_err());
+}
+
+#[tokio::test]
+async fn test_multiple_funds_are_isolated() {
+    let mut context = start().await;
+    let authority = funded_keypair(&mut context).await;
+    let admin = funded_keypair(&mut context).await;
+    initialize_registry(&mut context, &authority).await;
+    let tbill_fund = initialize_fund(&mut context, authority.insecure_clone(), admin.insecure_clone(), 0).await;
+    let corporate_fund = initialize_fund(&mut context, authority, admin, 1).await;
+
+    let registry: FundRegistry = load_account(&mut context, registry_pda()).await;
+    assert_eq!(registry.fund_count, 2);
+    assert_ne!(tbill_fund.fund_token_mint, corporate_fund.fund_token_mint);
+    assert_ne!(tbill_fund.usdc_vault, corporate_fund.usdc_vault);
+
+    let user = funded_keypair(&mut context).await;
+    let tbill_usdc = create_usdc_account(&mut context, &tbill_fund.usdc_mint, &user.pubkey(), 1_000_000_000).await;
+    let corporate_usdc =
+        create_usdc_account(&mut context, &corporate_fund.usdc_mint, &user.pubkey(), 1_000_000_000).await;
+
+    deposit(&mut context, &tbill_fund, &user, tbill_usdc, 1_000_000_000).await.unwrap();
+    deposit(&mut context, &corporate_fund, &user, corporate_usdc, 250_000_000).await.unwrap();
+
+    let tbill_state: FundState = load_account(&mut context, tbill_fund.fund_state).await;
+    let corporate_state: FundState = load_account(&mut context, corporate_fund.fund_state).await;
+    assert_eq!(tbill_state.cash_reserves, 1_000_000_000);
+    assert_eq!(tbill_state.total_shares, 100_000_000_000); // 1000 tokens
+    assert_eq!(corporate_state.cash_reserves, 250_000_000);
+    assert_eq!(corporate_state.total_shares, 25_000_000_000); // 250 tokens
+
+    let tbill_user: UserFundAccount =
+        load_account(&mut context, user_account_pda(&tbill_fund.fund_state, &user.pubkey())).await;
+    assert_eq!(tbill_user.fund, tbill_fund.fund_state);
+    assert_eq!(tbill_user.fund_tokens, 100_000_000_000);
+
+    // A USDC account for another fund's mint is rejected
+    assert!(deposit(&mut context, &tbill_fund, &user, corporate_usdc, 10_000_000).await.is_err());
+}
+
+#[tokio::test]
+async fn test_pause_and_unpause_fund() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+
+    let accounts = maek_protocol::accounts::AdminPause {
+        fund_state: fund.fund_state,
+        admin_authority: fund.admin.pubkey(),
+    }
+    .to_account_metas(None);
+
+    let pause = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: accounts.clone(),
+        data: maek_protocol::instruction::PauseFund {
+            reason: "Custodian reconciliation".to_string(),
+        }
+        .data(),
+    };
+    send(&mut context, pause, &[&fund.admin]).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert!(fund_state.is_paused);
+
+    let unpause = Instruction {
+        program_id: maek_protocol::ID,
+        accounts,
+        data: maek_protocol::instruction::UnpauseFund {}.data(),
+    };
+    send(&mut context, unpause, &[&fund.admin]).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert!(!fund_state.is_paused);
+}
+
+#[tokio::test]
+async fn test_pause_fund_requires_admin() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+
+    let intruder = funded_keypair(&mut context).await;
+    let pause = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::AdminPause {
+            fund_state: fund.fund_state,
+            admin_authority: intruder.pubkey(),
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::PauseFund {
+            reason: "Unauthorized".to_string(),
+        }
+        .data(),
+    };
+    assert!(send(&mut context, pause, &[&intruder]).await.is_err());
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert!(!fund_state.is_paused);
+}
+
+#[tokio::test]
+#[ignore = "needs the SBF build; run with `npm run test:sbf`"]
+async fn test_entrypoints_run_on_sbf_build() {
+    // Load target/deploy/maek_protocol.so with no native fallback, so the stack
+    // frame and compute limits are enforced by the SBF VM
+    let mut program_test = ProgramTest::new("maek_protocol", maek_protocol::ID, None);
+    program_test.prefer_bpf(true);
+    program_test.set_compute_max_units(MAX_COMPUTE_UNITS as u64);
+    let mut context = program_test.start_with_context().await;
+    let program = context.banks_client.get_account(maek_protocol::ID).await.unwrap().unwrap();
+    assert_ne!(program.owner, solana_sdk::native_loader::ID);
+    assert!(program.executable);
+
+    let fund = setup_fund(&mut context).await;
+    let investor = seed_cash(&mut context, &fund, 10_000_000_000).await; // $10,000
+    let investor_usdc = create_usdc_account(&mut context, &fund.usdc_mint, &investor.pubkey(), 0).await;
+    advance_clock(&mut context, 86400).await;
+    update_nav(&mut context, &fund, vec![], &[]).await.unwrap();
+    withdraw(&mut context, &fund, &investor, investor_usdc, 10_000_000_000).await.unwrap();
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    register_asset(&mut context, &fund, treasury_bill(b"912797GK0000", maturity_date)).await.unwrap();
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.active_asset_count, 1);
+}
+
+/// Invest in a batch of purchases, first sweeping their cost to the treasury vault
+async fn invest_in_fixed_income(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    assets: Vec<AssetPurchase>,
+) -> std::result::Result<(), BanksClientError> {
+    let cost = assets.iter().map(|purchase| purchase.purchase_price / 100).sum();
+    let mut accounts = maek_protocol::accounts::InvestFixedIncome {
+        fund_state: fund.fund_state,
+        treasury_vault: treasury_vault_pda(&fund.fund_state),
+        settlement_account: fund.settlement_account,
+        admin_authority: fund.admin.pubkey(),
+        token_program: spl_token::ID,
+        system_program: anchor_lang::system_program::ID,
+    }
+    .to_account_metas(None);
+    for purchase in &assets {
+        accounts.push(AccountMeta::new(asset_pda(&fund.fund_state, &purchase.asset_id), false));
+        accounts.push(AccountMeta::new(issuer_exposure_pda(&fund.fund_state, &purchase.issuer), false));
+    }
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts,
+        data: maek_protocol::instruction::InvestInFixedIncome { assets }.data(),
+    };
+    send_funded_by_treasury(context, fund, cost, instruction).await
+}
+
+#[tokio::test]
+async fn test_invest_in_fixed_income_without_cash_fails() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    let result = invest_in_fixed_income(&mut context, &fund, vec![treasury_bill(b"912797GK0000", maturity_date)]).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_invest_in_fixed_income_empty_batch() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+
+    invest_in_fixed_income(&mut context, &fund, vec![]).await.unwrap();
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.cash_reserves, 0);
+    assert_eq!(fund_state.fixed_income_value, 0);
+}
+
+#[tokio::test]
+async fn test_invest_in_fixed_income_validates_purchases() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+    let maturity_date = now(&mut context).await + 91 * 86400;
+
+    // The same checks as register_asset run on every purchase in the batch
+    let mut downgraded = treasury_bill(b"912797GK0001", maturity_date);
+    downgraded.credit_rating = 2;
+    let rejected = vec![treasury_bill(b"912797GK0000", maturity_date), downgraded];
+    assert!(invest_in_fixed_income(&mut context, &fund, rejected).await.is_err());
+
+    let accepted = vec![
+        treasury_bill(b"912797GK0000", maturity_date),
+        treasury_bill(b"912797GK0001", maturity_date),
+    ];
+    invest_in_fixed_income(&mut context, &fund, accepted).await.unwrap();
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.cash_reserves, 20_000_000_000 - 2 * 987_500_000);
+    assert_eq!(fund_state.fixed_income_value, 2 * 98_750_000_000);
+
+    // Each purchase is recorded as a holding, like register_asset's
+    let holding: FixedIncomeAsset = load_account(&mut context, asset_pda(&fund.fund_state, b"912797GK0001")).await;
+    assert_eq!(holding.fund, fund.fund_state);
+    assert!(holding.status == AssetStatus::Active);
+    assert_eq!(holding.current_value, 98_750_000_000);
+    assert_eq!(fund_state.active_asset_count, 2);
+
+    // A holding cannot be bought twice
+    assert!(invest_in_fixed_income(&mut context, &fund, vec![treasury_bill(b"912797GK0000", maturity_date)]).await.is_err());
+
+    // Issuer limits count holdings bought earlier, not only those in the batch
+    let issuer = Pubkey::new_unique();
+    let corporate = |asset_id: &[u8; 12]| AssetPurchase {
+        asset_type: FixedIncomeAssetType::CorporateBond,
+        coupon_rate: 4_500,
+        coupon_frequency: 2,
+        credit_rating: 3,
+        issuer,
+        sector: 2,
+        ..treasury_bill(asset_id, maturity_date)
+    };
+    register_asset(&mut context, &fund, corporate(b"459200HU8000")).await.unwrap();
+    assert_program_error(
+        invest_in_fixed_income(&mut context, &fund, vec![corporate(b"459200HU8001")]).await,
+        ErrorCode::PortfolioConcentrationExceeded,
+    );
+}
+
+#[tokio::test]
+async fn test_invest_in_fixed_income_holdings_carry_through_update_nav() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    let first = asset_pda(&fund.fund_state, b"912797GK0000");
+    let second = asset_pda(&fund.fund_state, b"912797GK0001");
+    invest_in_fixed_income(
+        &mut context,
+        &fund,
+        vec![treasury_bill(b"912797GK0000", maturity_date), treasury_bill(b"912797GK0001", maturity_date)],
+    )
+    .await
+    .unwrap();
+
+    let exposure: IssuerExposure =
+        load_account(&mut context, issuer_exposure_pda(&fund.fund_state, &Pubkey::default())).await;
+    assert_eq!(exposure.total_value, 2 * 98_750_000_000);
+    assert_eq!(exposure.asset_count, 2);
+
+    // Valuing both holdings at cost leaves gross assets where the investment left them
+    advance_clock(&mut context, 86400).await;
+    let valuations = vec![valuation(first, 98_750_000_000), valuation(second, 98_750_000_000)];
+    update_nav(&mut context, &fund, valuations, &[first, second]).await.unwrap();
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.fixed_income_value, 2 * 98_750_000_000);
+    assert_eq!(fund_state.active_asset_count, 2);
+
+    // NAV moves only by the day's management fee
+    assert_eq!(fund_state.total_assets + fund_state.accrued_fees_payable, 2_000_000_000_000);
+    assert_eq!(fund_state.nav_per_share, fund_state.total_assets / 20_000);
+    assert!(fund_state.nav_per_share > 99_999_000);
+}
+
+async fn update_nav(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    new_asset_valuations: Vec<AssetValuation>,
+    asset_accounts: &[Pubkey],
+) -> std::result::Result<(), BanksClientError> {
+    update_nav_with(context, fund, new_asset_valuations, asset_accounts, None, None).await
+}
+
+/// Update NAV passing the custodian attestation and price override co-signer when given
+async fn update_nav_with(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    new_asset_valuations: Vec<AssetValuation>,
+    asset_accounts: &[Pubkey],
+    custodian_attestation: Option<Pubkey>,
+    price_override: Option<&Keypair>,
+) -> std::result::Result<(), BanksClientError> {
+    let mut accounts = maek_protocol::accounts::UpdateNAV {
+        fund_state: fund.fund_state,
+        admin_authority: fund.admin.pubkey(),
+        custodian_attestation,
+        price_override_authority: price_override.map(|authority| authority.pubkey()),
+    }
+    .to_account_metas(None);
+    accounts.extend(asset_accounts.iter().map(|asset| AccountMeta::new(*asset, false)));
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts,
+        data: maek_protocol::instruction::UpdateNav { new_asset_valuations }.data(),
+    };
+    let mut signers = vec![&fund.admin];
+    signers.extend(price_override);
+    send(context, instruction, &signers).await
+}
+
+fn valuation(asset_id: Pubkey, current_value: u64) -> AssetValuation {
+    AssetValuation { asset_id, current_value }
+}
+
+#[tokio::test]
+async fn test_update_nav_validates_valuations() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    let asset_id = *b"912797GK0000";
+    let asset = asset_pda(&fund.fund_state, &asset_id);
+    register_asset(&mut context, &fund, treasury_bill(&asset_id, maturity_date)).await.unwrap();
+    advance_clock(&mut context, 86400).await;
+
+    // A zero valuation is rejected
+    assert_program_error(
+        update_nav(&mut context, &fund, vec![valuation(asset, 0)], &[asset]).await,
+        ErrorCode::InvalidFixedIncomeAssetData,
+    );
+
+    // One day of interest booked by the crank on the T-bill lifts NAV above par
+    accrue_interest(&mut context, &fund, &asset_id).await.unwrap(); // $0.13715277 accrued
+    let valuations = vec![valuation(asset, 98_750_000_000)];
+    update_nav(&mut context, &fund, valuations, &[asset]).await.unwrap();
+
+    // The 0.15% management fee for the elapsed time is booked as payable before pricing NAV
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    let elapsed = (fund_state.last_nav_update - fund_state.inception_date) as u128;
+    assert!(elapsed >= 86400);
+    let fee = (2_000_013_715_277u128 * 15 * elapsed / (10_000 * 365 * 86400)) as u64;
+    assert_eq!(fund_state.accrued_fees_payable, fee);
+    assert_eq!(fund_state.total_assets, 2_000_013_715_277 - fee);
+    assert_eq!(fund_state.fixed_income_value, 98_763_715_277);
+    assert_eq!(fund_state.nav_per_share, fund_state.total_assets / 20_000);
+    assert!(fund_state.nav_per_share > 100_000_000 && fund_state.nav_per_share < 100_000_686); // Below the gross $1.00000686
+
+    // The valuation is stored on the asset account next to the crank's accrual
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert_eq!(holding.current_value, 98_750_000_000);
+    assert_eq!(holding.accrued_interest, 13_715_277);
+
+    // Fees keep accruing on net assets until they are paid
+    advance_clock(&mut context, 86400).await;
+    accrue_interest(&mut context, &fund, &asset_id).await.unwrap();
+    let valuations = vec![valuation(asset, 98_750_000_000)];
+    update_nav(&mut context, &fund, valuations, &[asset]).await.unwrap();
+
+    let previous = fund_state;
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    let elapsed = (fund_state.last_nav_update - previous.last_nav_update) as u128;
+    let net_assets = 2_000_027_430_554 - previous.accrued_fees_payable as u128;
+    let fee = (net_assets * 15 * elapsed / (10_000 * 365 * 86400)) as u64;
+    assert_eq!(fund_state.accrued_fees_payable, previous.accrued_fees_payable + fee);
+    assert_eq!(fund_state.total_assets + fund_state.accrued_fees_payable, 2_000_027_430_554);
+}
+
+#[tokio::test]
+async fn test_update_nav_requires_every_asset_once() {
+    let mut context = start().await;
+    let authority = funded_keypair(&mut context).await;
+    let admin = funded_keypair(&mut context).await;
+    initialize_registry(&mut context, &authority).await;
+    let fund = initialize_fund(&mut context, authority.insecure_clone(), admin.insecure_clone(), 0).await;
+    let other_fund = initialize_fund(&mut context, authority, admin, 1).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+    seed_cash(&mut context, &other_fund, 20_000_000_000).await;
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    let first = asset_pda(&fund.fund_state, b"912797GK0000");
+    let second = asset_pda(&fund.fund_state, b"912797GK0001");
+    let foreign = asset_pda(&other_fund.fund_state, b"912797GK0002");
+    register_asset(&mut context, &fund, treasury_bill(b"912797GK0000", maturity_date)).await.unwrap();
+    register_asset(&mut context, &fund, treasury_bill(b"912797GK0001", maturity_date)).await.unwrap();
+    register_asset(&mut context, &other_fund, treasury_bill(b"912797GK0002", maturity_date)).await.unwrap();
+    advance_clock(&mut context, 86400).await;
+
+    // Omitting a holding is rejected
+    assert_program_error(
+        update_nav(&mut context, &fund, vec![valuation(first, 98_800_000_000)], &[first]).await,
+        ErrorCode::IncompleteAssetValuations,
+    );
+
+    // Valuing the same holding twice is rejected
+    let doubled = vec![valuation(first, 98_800_000_000), valuation(first, 98_800_000_000)];
+    assert_program_error(
+        update_nav(&mut context, &fund, doubled, &[first, first]).await,
+        ErrorCode::DuplicateAssetValuation,
+    );
+
+    // Another fund's holding cannot stand in for one of ours
+    let substituted = vec![valuation(first, 98_800_000_000), valuation(foreign, 98_800_000_000)];
+    assert_program_error(
+        update_nav(&mut context, &fund, substituted, &[first, foreign]).await,
+        ErrorCode::AssetValuationMismatch,
+    );
+
+    // Valuations must line up with the supplied accounts
+    let misaligned = vec![valuation(second, 98_800_000_000), valuation(first, 98_800_000_000)];
+    assert_program_error(
+        update_nav(&mut context, &fund, misaligned, &[first, second]).await,
+        ErrorCode::AssetValuationMismatch,
+    );
+
+    let valuations = vec![valuation(second, 98_800_000_000), valuation(first, 98_800_000_000)];
+    update_nav(&mut context, &fund, valuations, &[second, first]).await.unwrap();
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.fixed_income_value, 2 * 98_800_000_000);
+    assert_eq!(fund_state.total_assets + fund_state.accrued_fees_payable, 2_000_000_000_000 + 2 * 50_000_000);
+}
+
+async fn handle_asset_maturity(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    asset_id: &[u8; 12],
+    issuer: &Pubkey,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::HandleMaturity {
+            fund_state: fund.fund_state,
+            fixed_income_asset: asset_pda(&fund.fund_state, asset_id),
+            issuer_exposure: issuer_exposure_pda(&fund.fund_state, issuer),
+            usdc_vault: fund.usdc_vault,
+            treasury_vault: treasury_vault_pda(&fund.fund_state),
+            admin_authority: fund.admin.pubkey(),
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::HandleAssetMaturity {}.data(),
+    };
+    send(context, instruction, &[&fund.admin]).await
+}
+
+#[tokio::test]
+async fn test_handle_asset_maturity() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+    let asset_id = *b"912797GK0000";
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    register_asset(&mut context, &fund, treasury_bill(&asset_id, maturity_date)).await.unwrap();
+
+    // Cannot settle before the maturity date
+    assert_program_error(
+        handle_asset_maturity(&mut context, &fund, &asset_id, &Pubkey::default()).await,
+        ErrorCode::AssetNotMatured,
+    );
+
+    // Nor before the custodian has paid the proceeds into the treasury vault
+    advance_clock(&mut context, 91 * 86400).await;
+    custodian_pays_in(&mut context, &fund, 999_999_999).await;
+    assert_program_error(
+        handle_asset_maturity(&mut context, &fund, &asset_id, &Pubkey::default()).await,
+        ErrorCode::ProceedsNotReceived,
+    );
+    custodian_pays_in(&mut context, &fund, 1).await;
+    handle_asset_maturity(&mut context, &fund, &asset_id, &Pubkey::default()).await.unwrap();
+
+    let asset: FixedIncomeAsset = load_account(&mut context, asset_pda(&fund.fund_state, &asset_id)).await;
+    assert!(asset.status == AssetStatus::Matured);
+
+    // The T-bill redeems at par: $987.50 of holdings become $1,000 of cash
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.cash_reserves, 20_000_000_000 - 987_500_000 + 1_000_000_000);
+    assert_eq!(fund_state.treasury_cash, 0);
+    assert_eq!(fund_state.fixed_income_value, 0);
+    assert_eq!(fund_state.total_assets, 2_000_000_000_000 + 1_250_000_000);
+    assert_eq!(fund_state.active_asset_count, 0);
+
+    let exposure: IssuerExposure =
+        load_account(&mut context, issuer_exposure_pda(&fund.fund_state, &Pubkey::default())).await;
+    assert_eq!(exposure.total_value, 0);
+    assert_eq!(exposure.asset_count, 0);
+
+    // A matured asset cannot be settled twice
+    assert_program_error(
+        handle_asset_maturity(&mut context, &fund, &asset_id, &Pubkey::default()).await,
+        ErrorCode::AssetNotActive,
+    );
+}
+
+#[tokio::test]
+async fn test_treasury_settlement_cycle() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+    let asset_id = *b"912797GK0000";
+    let maturity_date = now(&mut context).await + 91 * 86400;
+
+    // A purchase must be paid for out of treasury cash
+    initialize_treasury_vault(&mut context, &fund).await.unwrap();
+    let mut register = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::RegisterAsset {
+            fund_state: fund.fund_state,
+            fixed_income_asset: asset_pda(&fund.fund_state, &asset_id),
+            issuer_exposure: issuer_exposure_pda(&fund.fund_state, &Pubkey::default()),
+            treasury_vault: treasury_vault_pda(&fund.fund_state),
+            settlement_account: fund.settlement_account,
+            admin_authority: fund.admin.pubkey(),
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::RegisterAsset { purchase: treasury_bill(&asset_id, maturity_date) }.data(),
+    };
+    assert_program_error(send(&mut context, register.clone(), &[&fund.admin]).await, ErrorCode::InsufficientLiquidity);
+
+    // Purchases settle only into the custodian account appointed by the registry authority
+    let admin_usdc = create_usdc_account(&mut context, &fund.usdc_mint, &fund.admin.pubkey(), 0).await;
+    register.accounts[4].pubkey = admin_usdc;
+    sweep_treasury(&mut context, &fund, 987_500_000, true).await.unwrap();
+    assert_program_error(send(&mut context, register, &[&fund.admin]).await, ErrorCode::InvalidAccountData);
+    assert_program_error(
+        set_settlement_account(&mut context, &fund, &fund.admin, admin_usdc).await,
+        ErrorCode::UnauthorizedAccess,
+    );
+    assert_program_error(
+        set_settlement_account(&mut context, &fund, &fund.registry_authority, Pubkey::default()).await,
+        ErrorCode::InvalidAccountData,
+    );
+
+    // Sweep and register: the treasury vault pays the $987.50 cost to the custodian
+    sweep_treasury(&mut context, &fund, 987_500_000, false).await.unwrap();
+    register_asset(&mut context, &fund, treasury_bill(&asset_id, maturity_date)).await.unwrap();
+    assert_eq!(token_balance(&mut context, fund.settlement_account).await, 987_500_000);
+    assert_eq!(token_balance(&mut context, treasury_vault_pda(&fund.fund_state)).await, 0);
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.cash_reserves, 20_000_000_000 - 987_500_000);
+    assert_eq!(fund_state.treasury_cash, 0);
+    assert_eq!(fund_state.fixed_income_value, 98_750_000_000);
+
+    // Mature once the custodian has paid the $1,000 redemption into the treasury vault;
+    // it is swept back to the USDC vault as cash reserves
+    advance_clock(&mut context, 91 * 86400).await;
+    custodian_pays_in(&mut context, &fund, 1_000_000_000).await;
+    handle_asset_maturity(&mut context, &fund, &asset_id, &Pubkey::default()).await.unwrap();
+    assert_eq!(token_balance(&mut context, treasury_vault_pda(&fund.fund_state)).await, 0);
+    assert_eq!(token_balance(&mut context, fund.usdc_vault).await, 20_000_000_000 - 987_500_000 + 1_000_000_000);
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.cash_reserves, 20_000_000_000 - 987_500_000 + 1_000_000_000);
+    assert_eq!(fund_state.treasury_cash, 0);
+    assert_eq!(fund_state.fixed_income_value, 0);
+    assert_eq!(fund_state.total_assets, 2_000_000_000_000 + 1_250_000_000);
+}
+
+async fn sell_asset(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    asset_id: &[u8; 12],
+    issuer: &Pubkey,
+    sale_price: u64,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::SellAsset {
+            fund_state: fund.fund_state,
+            fixed_income_asset: asset_pda(&fund.fund_state, asset_id),
+            issuer_exposure: issuer_exposure_pda(&fund.fund_state, issuer),
+            usdc_vault: fund.usdc_vault,
+            treasury_vault: treasury_vault_pda(&fund.fund_state),
+            admin_authority: fund.admin.pubkey(),
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::SellAsset { sale_price }.data(),
+    };
+    send(context, instruction, &[&fund.admin]).await
+}
+
+#[tokio::test]
+async fn test_sell_asset() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+    let asset_id = *b"912797GK0000";
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    register_asset(&mut context, &fund, treasury_bill(&asset_id, maturity_date)).await.unwrap();
+
+    // Sale proceeds must settle in whole USDC units
+    assert_program_error(
+        sell_asset(&mut context, &fund, &asset_id, &Pubkey::default(), 99_000_000_001).await,
+        ErrorCode::AssetSaleFailed,
+    );
+
+    // The buyer's settlement must have reached the treasury vault
+    assert_program_error(
+        sell_asset(&mut context, &fund, &asset_id, &Pubkey::default(), 99_000_000_000).await,
+        ErrorCode::ProceedsNotReceived,
+    );
+
+    // Sell the $987.50 T-bill for $990.00
+    custodian_pays_in(&mut context, &fund, 990_000_000).await;
+    sell_asset(&mut context, &fund, &asset_id, &Pubkey::default(), 99_000_000_000).await.unwrap();
+
+    let asset: FixedIncomeAsset = load_account(&mut context, asset_pda(&fund.fund_state, &asset_id)).await;
+    assert!(asset.status == AssetStatus::Sold);
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.cash_reserves, 20_000_000_000 - 987_500_000 + 990_000_000);
+    assert_eq!(fund_state.treasury_cash, 0);
+    assert_eq!(fund_state.fixed_income_value, 0);
+    assert_eq!(fund_state.total_assets, 2_000_000_000_000 + 250_000_000);
+    assert_eq!(fund_state.active_asset_count, 0);
+    assert_eq!(fund_state.realized_pnl, 250_000_000);
+
+    let exposure: IssuerExposure =
+        load_account(&mut context, issuer_exposure_pda(&fund.fund_state, &Pubkey::default())).await;
+    assert_eq!(exposure.total_value, 0);
+    assert_eq!(exposure.asset_count, 0);
+
+    // A sold asset cannot be sold again
+    assert_program_error(
+        sell_asset(&mut context, &fund, &asset_id, &Pubkey::default(), 99_000_000_000).await,
+        ErrorCode::AssetNotActive,
+    );
+}
+
+async fn mark_asset_default(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    asset_id: &[u8; 12],
+    write_down_bps: u16,
+) -> std::result::Result<(), BanksClientError> {
+    let asset = asset_pda(&fund.fund_state, asset_id);
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::MarkAssetDefault {
+            fund_state: fund.fund_state,
+            fixed_income_asset: asset,
+            write_down_history: write_down_history_pda(&asset),
+            admin_authority: fund.admin.pubkey(),
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::MarkAssetDefault { write_down_bps }.data(),
+    };
+    send(context, instruction, &[&fund.admin]).await
+}
+
+async fn record_default_recovery(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    asset_id: &[u8; 12],
+    recovery_amount: u64,
+    close_position: bool,
+) -> std::result::Result<(), BanksClientError> {
+    let asset = asset_pda(&fund.fund_state, asset_id);
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::RecordDefaultRecovery {
+            fund_state: fund.fund_state,
+            fixed_income_asset: asset,
+            write_down_history: write_down_history_pda(&asset),
+            issuer_exposure: issuer_exposure_pda(&fund.fund_state, &Pubkey::default()),
+            usdc_vault: fund.usdc_vault,
+            treasury_vault: treasury_vault_pda(&fund.fund_state),
+            admin_authority: fund.admin.pubkey(),
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::RecordDefaultRecovery { recovery_amount, close_position }.data(),
+    };
+    send(context, instruction, &[&fund.admin]).await
+}
+
+#[tokio::test]
+async fn test_default_write_down_and_recovery() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+    let asset_id = *b"912797GK0000";
+    let asset = asset_pda(&fund.fund_state, &asset_id);
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    register_asset(&mut context, &fund, treasury_bill(&asset_id, maturity_date)).await.unwrap();
+
+    // Write the $987.50 holding down by 40%; NAV reprices in the same transaction
+    mark_asset_default(&mut context, &fund, &asset_id, 4_000).await.unwrap();
+
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert!(holding.status == AssetStatus::DefaultRisk);
+    assert_eq!(holding.current_value, 59_250_000_000);
+
+    // A defaulted holding cannot be handed to a price feed
+    assert_program_error(
+        set_asset_price_feed(&mut context, &fund, asset, Pubkey::new_unique()).await,
+        ErrorCode::AssetNotActive,
+    );
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.fixed_income_value, 59_250_000_000);
+    assert_eq!(fund_state.total_assets, 2_000_000_000_000 - 39_500_000_000);
+    assert_eq!(fund_state.nav_per_share, 98_025_000);
+    assert_eq!(fund_state.active_asset_count, 1);
+
+    assert_program_error(
+        mark_asset_default(&mut context, &fund, &asset_id, 10_001).await,
+        ErrorCode::InvalidWriteDown,
+    );
+
+    // Only recoveries that have reached the treasury vault can be booked
+    assert_program_error(
+        record_default_recovery(&mut context, &fund, &asset_id, 50_000_000_000, false).await,
+        ErrorCode::ProceedsNotReceived,
+    );
+
+    // A $500 partial recovery converts written-down value into cash
+    custodian_pays_in(&mut context, &fund, 500_000_000).await;
+    record_default_recovery(&mut context, &fund, &asset_id, 50_000_000_000, false).await.unwrap();
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.cash_reserves, 20_000_000_000 - 987_500_000 + 500_000_000);
+    assert_eq!(fund_state.treasury_cash, 0);
+    assert_eq!(fund_state.fixed_income_value, 9_250_000_000);
+    assert_eq!(fund_state.total_assets, 2_000_000_000_000 - 39_500_000_000);
+
+    // Recoveries are capped at the $1,000 par claim
+    assert_program_error(
+        record_default_recovery(&mut context, &fund, &asset_id, 50_100_000_000, false).await,
+        ErrorCode::RecoveryExceedsPar,
+    );
+
+    // A final $100 recovery exceeds the remaining $92.50 and closes the workout
+    custodian_pays_in(&mut context, &fund, 100_000_000).await;
+    record_default_recovery(&mut context, &fund, &asset_id, 10_000_000_000, true).await.unwrap();
+
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert!(holding.status == AssetStatus::WrittenOff);
+    assert_eq!(holding.current_value, 0);
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.cash_reserves, 20_000_000_000 - 987_500_000 + 600_000_000);
+    assert_eq!(fund_state.treasury_cash, 0);
+    assert_eq!(fund_state.fixed_income_value, 0);
+    assert_eq!(fund_state.total_assets, 2_000_000_000_000 - 38_750_000_000);
+    assert_eq!(fund_state.active_asset_count, 0);
+    assert_eq!(fund_state.realized_pnl, -38_750_000_000); // $600 recovered on $987.50
+
+    let exposure: IssuerExposure =
+        load_account(&mut context, issuer_exposure_pda(&fund.fund_state, &Pubkey::default())).await;
+    assert_eq!(exposure.total_value, 0);
+    assert_eq!(exposure.asset_count, 0);
+
+    // Every step is kept for auditors
+    let history: WriteDownHistory = load_account(&mut context, write_down_history_pda(&asset)).await;
+    assert_eq!(history.asset, asset);
+    assert_eq!(history.total_written_down, 39_500_000_000);
+    assert_eq!(history.total_recovered, 60_000_000_000);
+    let steps: Vec<(CreditEventKind, u64, u64)> =
+        history.events.iter().map(|event| (event.kind, event.amount, event.value_after)).collect();
+    assert!(
+        steps
+            == vec![
+                (CreditEventKind::WriteDown, 39_500_000_000, 59_250_000_000),
+                (CreditEventKind::Recovery, 50_000_000_000, 9_250_000_000),
+                (CreditEventKind::Recovery, 10_000_000_000, 0),
+                (CreditEventKind::WriteOff, 0, 0),
+            ]
+    );
+
+    // A written-off asset takes no further recoveries
+    assert_program_error(
+        record_default_recovery(&mut context, &fund, &asset_id, 10_000_000_000, false).await,
+        ErrorCode::AssetNotInDefault,
+    );
+}
+
+#[tokio::test]
+async fn test_update_nav_keeps_written_down_value() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+    let asset_id = *b"912797GK0000";
+    let asset = asset_pda(&fund.fund_state, &asset_id);
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    register_asset(&mut context, &fund, treasury_bill(&asset_id, maturity_date)).await.unwrap();
+    mark_asset_default(&mut context, &fund, &asset_id, 4_000).await.unwrap();
+    advance_clock(&mut context, 86400).await;
+
+    // A NAV update cannot quietly reverse the write-down, with or without a price override
+    let overrider = funded_keypair(&mut context).await;
+    set_price_override_authority(&mut context, &fund, &fund.registry_authority, overrider.pubkey()).await.unwrap();
+    for restored in [98_750_000_000, 59_250_000_001] {
+        assert_program_error(
+            update_nav_with(&mut context, &fund, vec![valuation(asset, restored)], &[asset], None, Some(&overrider))
+                .await,
+            ErrorCode::DefaultedAssetRevalued,
+        );
+    }
+    assert_program_error(
+        update_nav(&mut context, &fund, vec![valuation(asset, 0)], &[asset]).await,
+        ErrorCode::DefaultedAssetRevalued,
+    );
+    update_nav(&mut context, &fund, vec![valuation(asset, 59_250_000_000)], &[asset]).await.unwrap();
+
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert_eq!(holding.current_value, 59_250_000_000);
+    let history: WriteDownHistory = load_account(&mut context, write_down_history_pda(&asset)).await;
+    assert_eq!(history.events.len(), 1);
+}
+
+async fn accrue_interest(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    asset_id: &[u8; 12],
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::AccrueInterest {
+            fund_state: fund.fund_state,
+            fixed_income_asset: asset_pda(&fund.fund_state, asset_id),
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::AccrueInterest {}.data(),
+    };
+    // Permissionless: only the fee payer signs
+    send(context, instruction, &[]).await
+}
+
+#[tokio::test]
+async fn test_accrue_interest() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+    let asset_id = *b"912797GK0000";
+    let asset = asset_pda(&fund.fund_state, &asset_id);
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    register_asset(&mut context, &fund, treasury_bill(&asset_id, maturity_date)).await.unwrap();
+
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert!(holding.day_count_convention == DayCountConvention::Actual360);
+
+    // 30 days on $987.50 at 5.00% ACT/360
+    advance_clock(&mut context, 30 * 86400).await;
+    accrue_interest(&mut context, &fund, &asset_id).await.unwrap();
+
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert_eq!(holding.accrued_interest, 411_458_333);
+    assert_eq!(holding.days_to_maturity, 61);
+
+    // Cranking again within the same day accrues nothing
+    accrue_interest(&mut context, &fund, &asset_id).await.unwrap();
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert_eq!(holding.accrued_interest, 411_458_333);
+
+    // Accrual stops at maturity
+    advance_clock(&mut context, 90 * 86400).await;
+    accrue_interest(&mut context, &fund, &asset_id).await.unwrap();
+
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert_eq!(holding.accrued_interest, 411_458_333 + 836_631_944);
+    assert_eq!(holding.last_interest_calculation, maturity_date);
+    assert_eq!(holding.days_to_maturity, 0);
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.fixed_income_value, 98_750_000_000 + 1_248_090_277);
+    assert_eq!(fund_state.total_assets, 2_000_000_000_000 + 1_248_090_277);
+}
+
+#[tokio::test]
+async fn test_accrue_coupon_interest() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+
+    // Two-year note paying 4.50% semiannually, bought mid-period at $995.00 dirty
+    let asset_id = *b"91282CJK8000";
+    let asset = asset_pda(&fund.fund_state, &asset_id);
+    let purchased_at = now(&mut context).await;
+    let maturity_date = purchased_at + 730 * 86400;
+    let note = AssetPurchase {
+        asset_type: FixedIncomeAssetType::TreasuryNote,
+        purchase_price: 99_500_000_000,
+        coupon_rate: 4_500,
+        coupon_frequency: 2,
+        ..treasury_bill(&asset_id, maturity_date)
+    };
+    register_asset(&mut context, &fund, note).await.unwrap();
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    let first_coupon = holding.next_coupon_date;
+    let purchased_accrued = maek_protocol::utils::calculations::calculate_accrued_coupon(
+        100_000_000_000, 4_500, 2, DayCountConvention::Actual365, purchased_at, maturity_date,
+    ).unwrap();
+
+    // Crank ten days past the first coupon date
+    advance_clock(&mut context, first_coupon - purchased_at + 10 * 86400).await;
+    accrue_interest(&mut context, &fund, &asset_id).await.unwrap();
+
+    // The coupon is paid, the schedule rolls forward and accrual restarts from the coupon date
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    let period_end = holding.last_interest_calculation;
+    assert!(period_end > first_coupon);
+    assert_eq!(holding.next_coupon_date, maek_protocol::utils::calculations::coupon_period(maturity_date, 2, first_coupon).unwrap().1);
+    let accrued = maek_protocol::utils::calculations::calculate_accrued_coupon(
+        100_000_000_000, 4_500, 2, DayCountConvention::Actual365, period_end, maturity_date,
+    ).unwrap();
+    assert_eq!(holding.accrued_interest, accrued);
+
+    // Accrued interest bought with the note is recovered from the coupon, not booked as income
+    assert_eq!(holding.current_value, 99_500_000_000 - purchased_accrued);
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.treasury_cash, 22_500_000); // $22.50 coupon
+    assert_eq!(
+        fund_state.fixed_income_value,
+        holding.current_value + holding.accrued_interest + fund_state.treasury_cash * 100,
+    );
+    assert_eq!(fund_state.total_assets, 2_000_000_000_000 + 2_250_000_000 - purchased_accrued + accrued);
+
+    // A later crank within the same period keeps accruing without paying again
+    advance_clock(&mut context, 30 * 86400).await;
+    accrue_interest(&mut context, &fund, &asset_id).await.unwrap();
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert!(holding.accrued_interest > accrued);
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.treasury_cash, 22_500_000);
+}
+
+#[tokio::test]
+async fn test_register_asset() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+
+    let asset_id = *b"912797GK0000";
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    register_asset(&mut context, &fund, treasury_bill(&asset_id, maturity_date)).await.unwrap();
+
+    let asset: FixedIncomeAsset = load_account(&mut context, asset_pda(&fund.fund_state, &asset_id)).await;
+    assert_eq!(asset.fund, fund.fund_state);
+    assert_eq!(asset.asset_id, asset_id);
+    assert!(asset.asset_type == FixedIncomeAssetType::TreasuryBill);
+    assert!(asset.status == AssetStatus::Active);
+    assert_eq!(asset.face_value, 100_000_000_000);
+    assert_eq!(asset.current_value, 98_750_000_000);
+    assert_eq!(asset.maturity_date, maturity_date);
+    assert_eq!(asset.days_to_maturity, 91);
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.cash_reserves, 20_000_000_000 - 987_500_000);
+    assert_eq!(fund_state.fixed_income_value, 98_750_000_000);
+    assert_eq!(fund_state.total_assets, 2_000_000_000_000);
+    assert_eq!(fund_state.active_asset_count, 1);
+
+    let exposure: IssuerExposure =
+        load_account(&mut context, issuer_exposure_pda(&fund.fund_state, &Pubkey::default())).await;
+    assert_eq!(exposure.total_value, 98_750_000_000);
+    assert_eq!(exposure.asset_count, 1);
+
+    // The same asset id cannot be registered twice
+    assert!(register_asset(&mut context, &fund, treasury_bill(&asset_id, maturity_date)).await.is_err());
+}
+
+#[tokio::test]
+async fn test_register_coupon_asset() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+
+    // Two-year note paying 4.50% semiannually
+    let asset_id = *b"91282CJK8000";
+    let maturity_date = now(&mut context).await + 730 * 86400;
+    let note = AssetPurchase {
+        asset_type: FixedIncomeAssetType::TreasuryNote,
+        purchase_price: 99_500_000_000, // $995.00
+        coupon_rate: 4_500,
+        coupon_frequency: 2,
+        ..treasury_bill(&asset_id, maturity_date)
+    };
+    register_asset(&mut context, &fund, note).await.unwrap();
+
+    let holding: FixedIncomeAsset = load_account(&mut context, asset_pda(&fund.fund_state, &asset_id)).await;
+    assert_eq!(holding.coupon_rate, 4_500);
+    assert_eq!(holding.coupon_frequency, 2);
+    assert!(holding.day_count_convention == DayCountConvention::Actual365);
+
+    // The next coupon falls within six months, on the schedule rolling back from maturity
+    let (_, next_coupon, remaining) =
+        maek_protocol::utils::calculations::coupon_period(maturity_date, 2, now(&mut context).await).unwrap();
+    assert_eq!(holding.next_coupon_date, next_coupon);
+    assert_eq!(remaining, 4);
+    assert!(holding.next_coupon_date <= now(&mut context).await + 184 * 86400);
+}
+
+#[tokio::test]
+async fn test_register_asset_enforces_validation() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 5_000_000_000).await; // $5,000
+    let maturity_date = now(&mut context).await + 91 * 86400;
+
+    // A $987.50 purchase is more than 10% of a $5,000 fund
+    assert_program_error(
+        register_asset(&mut context, &fund, treasury_bill(b"912797GK0001", maturity_date)).await,
+        ErrorCode::InvestmentAmountExceedsLimit,
+    );
+
+    seed_cash(&mut context, &fund, 15_000_000_000).await;
+
+    // T-bills must be AAA rated
+    let mut downgraded = treasury_bill(b"912797GK0002", maturity_date);
+    downgraded.credit_rating = 2;
+    assert_program_error(
+        register_asset(&mut context, &fund, downgraded).await,
+        ErrorCode::InvalidCreditRating,
+    );
+
+    // T-bills cannot mature beyond one year
+    let long_dated = treasury_bill(b"912797GK0003", maturity_date + 365 * 86400);
+    assert_program_error(
+        register_asset(&mut context, &fund, long_dated).await,
+        ErrorCode::InvalidFixedIncomeAssetData,
+    );
+
+    // T-bills pay no coupon
+    let mut couponed = treasury_bill(b"912797GK0005", maturity_date);
+    couponed.coupon_rate = 5_000;
+    couponed.coupon_frequency = 2;
+    assert_program_error(
+        register_asset(&mut context, &fund, couponed).await,
+        ErrorCode::InvalidCouponTerms,
+    );
+
+    // A $950 price implies a 20% money-market yield, not the quoted 5.00%
+    let mut mispriced = treasury_bill(b"912797GK0004", maturity_date);
+    mispriced.purchase_price = 95_000_000_000;
+    assert_program_error(
+        register_asset(&mut context, &fund, mispriced).await,
+        ErrorCode::PriceYieldMismatch,
+    );
+
+    // Corporate issuers are capped at 5% of the fund
+    let issuer = Pubkey::new_unique();
+    let corporate = |asset_id: &[u8; 12]| AssetPurchase {
+        asset_type: FixedIncomeAssetType::CorporateBond,
+        coupon_rate: 4_500,
+        coupon_frequency: 2,
+        credit_rating: 3,
+        issuer,
+        sector: 2,
+        ..treasury_bill(asset_id, maturity_date)
+    };
+    register_asset(&mut context, &fund, corporate(b"459200HU8000")).await.unwrap();
+    assert_program_error(
+        register_asset(&mut context, &fund, corporate(b"459200HU8001")).await,
+        ErrorCode::PortfolioConcentrationExceeded,
+    );
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.active_asset_count, 1);
+}
+
+async fn set_max_portfolio_duration(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    max_portfolio_duration: u32,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::AdminConfig {
+            fund_state: fund.fund_state,
+            admin_authority: fund.admin.pubkey(),
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::SetMaxPortfolioDuration { max_portfolio_duration }.data(),
+    };
+    send(context, instruction, &[&fund.admin]).await
+}
+
+async fn set_maturity_limits(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    max_wam_days: u16,
+    max_wal_days: u16,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::AdminConfig {
+            fund_state: fund.fund_state,
+            admin_authority: fund.admin.pubkey(),
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::SetMaturityLimits { max_wam_days, max_wal_days }.data(),
+    };
+    send(context, instruction, &[&fund.admin]).await
+}
+
+#[tokio::test]
+async fn test_portfolio_duration_limit() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+    let bill_id = *b"912797GK0000";
+    let note_id = *b"91282CJK8000";
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    let note = AssetPurchase {
+        asset_type: FixedIncomeAssetType::TreasuryNote,
+        purchase_price: 99_500_000_000, // $995.00
+        coupon_rate: 4_500,
+        coupon_frequency: 2,
+        maturity_date: maturity_date + 639 * 86400, // Two years out
+        ..treasury_bill(&note_id, maturity_date)
+    };
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.max_portfolio_duration, 10_000); // One year by default
+
+    // Cap portfolio duration at 0.06 years
+    set_max_portfolio_duration(&mut context, &fund, 600).await.unwrap();
+    register_asset(&mut context, &fund, treasury_bill(&bill_id, maturity_date)).await.unwrap();
+
+    let bill: FixedIncomeAsset = load_account(&mut context, asset_pda(&fund.fund_state, &bill_id)).await;
+    assert_eq!(bill.macaulay_duration, 2_493); // 91 days
+    assert_eq!(bill.modified_duration, 2_462);
+    let bill_weight = bill.modified_duration as u128 * bill.purchase_price as u128;
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.weighted_duration_sum, bill_weight);
+    assert_eq!(fund_state.portfolio_duration, (bill_weight / 2_000_000_000_000) as u32);
+
+    // A two-year note would push portfolio duration past the cap
+    assert_program_error(
+        register_asset(&mut context, &fund, note.clone()).await,
+        ErrorCode::DurationRiskExceeded,
+    );
+
+    set_max_portfolio_duration(&mut context, &fund, 2_000).await.unwrap();
+    register_asset(&mut context, &fund, note).await.unwrap();
+
+    let note: FixedIncomeAsset = load_account(&mut context, asset_pda(&fund.fund_state, &note_id)).await;
+    assert!(note.modified_duration > 18_000 && note.modified_duration < note.macaulay_duration);
+    assert!(note.convexity > 0);
+    let note_weight = note.modified_duration as u128 * note.purchase_price as u128;
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.weighted_duration_sum, bill_weight + note_weight);
+    assert_eq!(fund_state.portfolio_duration, ((bill_weight + note_weight) / 2_000_000_000_000) as u32);
+
+    // Selling the note removes it from the aggregate
+    custodian_pays_in(&mut context, &fund, 995_000_000).await;
+    sell_asset(&mut context, &fund, &note_id, &Pubkey::default(), 99_500_000_000).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.weighted_duration_sum, bill_weight);
+}
+
+#[tokio::test]
+async fn test_weighted_average_maturity_limit() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+    let first_id = *b"912797GK0000";
+    let second_id = *b"912797GL0000";
+    let maturity_date = now(&mut context).await + 91 * 86400;
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.max_wam_days, 60);
+    assert_eq!(fund_state.max_wal_days, 120);
+
+    register_asset(&mut context, &fund, treasury_bill(&first_id, maturity_date)).await.unwrap();
+
+    // $987.50 at 91 days over $20,000 of assets is about 4.5 days
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.held_book_value, 98_750_000_000);
+    assert_eq!(fund_state.maturity_weighted_sum, 98_750_000_000u128 * maturity_date as u128);
+    assert_eq!(fund_state.weighted_average_maturity, 4);
+    assert_eq!(fund_state.weighted_average_life, 4);
+
+    // Limits must satisfy 1 <= WAM <= WAL <= 397 days
+    for (wam, wal) in [(0, 60), (61, 60), (60, 398)] {
+        assert_program_error(set_maturity_limits(&mut context, &fund, wam, wal).await, ErrorCode::InvalidAmount);
+    }
+
+    // A second bill would lift WAM to about 9 days
+    set_maturity_limits(&mut context, &fund, 6, 12).await.unwrap();
+    assert_program_error(
+        register_asset(&mut context, &fund, treasury_bill(&second_id, maturity_date)).await,
+        ErrorCode::WeightedAverageMaturityExceeded,
+    );
+
+    set_maturity_limits(&mut context, &fund, 10, 20).await.unwrap();
+    register_asset(&mut context, &fund, treasury_bill(&second_id, maturity_date)).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.held_book_value, 197_500_000_000);
+    assert_eq!(fund_state.weighted_average_maturity, 8);
+
+    // Selling a bill takes it back out of the aggregates
+    custodian_pays_in(&mut context, &fund, 987_500_000).await;
+    sell_asset(&mut context, &fund, &second_id, &Pubkey::default(), 98_750_000_000).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.held_book_value, 98_750_000_000);
+    assert_eq!(fund_state.maturity_weighted_sum, 98_750_000_000u128 * maturity_date as u128);
+    assert_eq!(fund_state.weighted_average_maturity, 4);
+}
+
+#[tokio::test]
+async fn test_floating_rate_weighted_average_maturity() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+    let fixed_id = *b"91282CJK8000";
+    let floating_id = *b"91282CJL6000";
+    let maturity_date = now(&mut context).await + 730 * 86400;
+    let note = |asset_id: &[u8; 12], floating_rate: bool| AssetPurchase {
+        asset_type: FixedIncomeAssetType::TreasuryNote,
+        purchase_price: 99_500_000_000, // $995.00
+        coupon_rate: 4_500,
+        coupon_frequency: 4,
+        floating_rate,
+        ..treasury_bill(asset_id, maturity_date)
+    };
+
+    // Floating rates reset on coupon dates, so a discount bill cannot float
+    let mut floating_bill = treasury_bill(b"912797GK0000", now(&mut context).await + 91 * 86400);
+    floating_bill.floating_rate = true;
+    assert_program_error(register_asset(&mut context, &fund, floating_bill).await, ErrorCode::InvalidCouponTerms);
+
+    // A fixed-rate two-year note counts to maturity for WAM, about 36 days over $20,000
+    set_maturity_limits(&mut context, &fund, 10, 60).await.unwrap();
+    assert_program_error(
+        register_asset(&mut context, &fund, note(&fixed_id, false)).await,
+        ErrorCode::WeightedAverageMaturityExceeded,
+    );
+
+    // A floater's WAM runs only to its next quarterly reset, while WAL still runs to maturity
+    register_asset(&mut context, &fund, note(&floating_id, true)).await.unwrap();
+    let floater: FixedIncomeAsset = load_account(&mut context, asset_pda(&fund.fund_state, &floating_id)).await;
+    assert!(floater.floating_rate);
+    let first_reset = floater.next_coupon_date;
+    assert_eq!(floater.interest_reset_date(), first_reset);
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.maturity_weighted_sum, 99_500_000_000u128 * maturity_date as u128);
+    assert_eq!(fund_state.reset_weighted_sum, 99_500_000_000u128 * first_reset as u128);
+    assert_eq!(fund_state.weighted_average_life, 36);
+    assert!(fund_state.weighted_average_maturity <= 4);
+
+    // Paying the coupon resets the rate and moves the WAM date to the following reset
+    let elapsed = first_reset - now(&mut context).await + 86400;
+    advance_clock(&mut context, elapsed).await;
+    accrue_interest(&mut context, &fund, &floating_id).await.unwrap();
+    let floater: FixedIncomeAsset = load_account(&mut context, asset_pda(&fund.fund_state, &floating_id)).await;
+    assert!(floater.next_coupon_date > first_reset);
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.reset_weighted_sum, 99_500_000_000u128 * floater.next_coupon_date as u128);
+
+    // Selling the floater takes it back out of both aggregates; the custodian pays in the coupon too
+    custodian_pays_in(&mut context, &fund, fund_state.treasury_cash + 995_000_000).await;
+    sell_asset(&mut context, &fund, &floating_id, &Pubkey::default(), 99_500_000_000).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.maturity_weighted_sum, 0);
+    assert_eq!(fund_state.reset_weighted_sum, 0);
+}
+
+async fn claim_fees(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    fee_recipient_usdc_account: Pubkey,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::ClaimFees {
+            fund_state: fund.fund_state,
+            admin_authority: fund.admin.pubkey(),
+            usdc_vault: fund.usdc_vault,
+            fee_recipient_usdc_account,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::ClaimFees {}.data(),
+    };
+    send(context, instruction, &[&fund.admin]).await
+}
+
+async fn set_fee_recipient(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    fee_recipient: Pubkey,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::AdminConfig {
+            fund_state: fund.fund_state,
+            admin_authority: fund.admin.pubkey(),
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::SetFeeRecipient { fee_recipient }.data(),
+    };
+    send(context, instruction, &[&fund.admin]).await
+}
+
+async fn token_balance(context: &mut ProgramTestContext, address: Pubkey) -> u64 {
+    let account = context.banks_client.get_account(address).await.unwrap().unwrap();
+    spl_token::state::Account::unpack(&account.data).unwrap().amount
+}
+
+#[tokio::test]
+async fn test_claim_fees() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    let holder = seed_cash(&mut context, &fund, 10_000_000_000).await; // $10,000
+    let holder_usdc = create_usdc_account(&mut context, &fund.usdc_mint, &holder.pubkey(), 0).await;
+    let admin_usdc = create_usdc_account(&mut context, &fund.usdc_mint, &fund.admin.pubkey(), 0).await;
+    let outsider_usdc = create_usdc_account(&mut context, &fund.usdc_mint, &Pubkey::new_unique(), 0).await;
+
+    // Fees go to the admin until reassigned, and nothing is payable before a NAV update
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.fee_recipient, fund.admin.pubkey());
+    assert_program_error(claim_fees(&mut context, &fund, admin_usdc).await, ErrorCode::NoFeesToClaim);
+
+    advance_clock(&mut context, 86400).await;
+    update_nav(&mut context, &fund, vec![], &[]).await.unwrap();
+    let before: FundState = load_account(&mut context, fund.fund_state).await;
+    assert!(before.accrued_fees_payable > 4_000_000); // About $0.041 for a day at 0.15%
+
+    // Only the configured recipient can be paid
+    assert_program_error(claim_fees(&mut context, &fund, outsider_usdc).await, ErrorCode::UnauthorizedAccess);
+
+    claim_fees(&mut context, &fund, admin_usdc).await.unwrap();
+    let paid = before.accrued_fees_payable / 100;
+    assert_eq!(token_balance(&mut context, admin_usdc).await, paid);
+    assert_eq!(token_balance(&mut context, fund.usdc_vault).await, 10_000_000_000 - paid);
+
+    // Paying the liability moves cash but leaves total assets and NAV unchanged
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.cash_reserves, 10_000_000_000 - paid);
+    assert_eq!(fund_state.accrued_fees_payable, before.accrued_fees_payable % 100);
+    assert_eq!(fund_state.total_fees_paid, paid * 100);
+    assert_eq!(fund_state.total_assets, before.total_assets);
+    assert_eq!(fund_state.nav_per_share, before.nav_per_share);
+
+    // Reassign the recipient, invest down to the 25% liquidity target and then redeem below it
+    let recipient = Keypair::new();
+    let recipient_usdc = create_usdc_account(&mut context, &fund.usdc_mint, &recipient.pubkey(), 0).await;
+    set_fee_recipient(&mut context, &fund, recipient.pubkey()).await.unwrap();
+    set_maturity_limits(&mut context, &fund, 120, 120).await.unwrap();
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    let mut assets = Vec::new();
+    for index in 0..7u8 {
+        let asset_id = [b'0' + index; 12];
+        register_asset(&mut context, &fund, treasury_bill(&asset_id, maturity_date)).await.unwrap();
+        assets.push(asset_pda(&fund.fund_state, &asset_id));
+    }
+    withdraw(&mut context, &fund, &holder, holder_usdc, 100_000_000_000).await.unwrap();
+
+    advance_clock(&mut context, 86400).await;
+    let valuations = assets.iter().map(|asset| valuation(*asset, 98_750_000_000)).collect();
+    update_nav(&mut context, &fund, valuations, &assets).await.unwrap();
+    assert_program_error(
+        claim_fees(&mut context, &fund, recipient_usdc).await,
+        ErrorCode::LiquidityRatioBelowMinimum,
+    );
+    assert_eq!(token_balance(&mut context, recipient_usdc).await, 0);
+}
+
+async fn set_performance_fee(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    performance_fee_bps: u16,
+    hurdle_rate_bps: u16,
+    crystallization_period: i64,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::AdminConfig {
+            fund_state: fund.fund_state,
+            admin_authority: fund.admin.pubkey(),
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::SetPerformanceFee {
+            performance_fee_bps,
+            hurdle_rate_bps,
+            crystallization_period,
+        }
+        .data(),
+    };
+    send(context, instruction, &[&fund.admin]).await
+}
+
+/// Performance fee the program should have accrued at the latest NAV update
+fn expected_performance_fee(fund_state: &FundState) -> u64 {
+    let assets_before_fee = (fund_state.total_assets + fund_state.accrued_performance_fee) as u128;
+    let nav_before_fee = assets_before_fee * 100_000_000 / fund_state.total_shares as u128;
+    let elapsed = (fund_state.last_nav_update - fund_state.performance_period_start) as u128;
+    let high_water_mark = fund_state.high_water_mark as u128;
+    let hurdle_nav = high_water_mark + high_water_mark * fund_state.hurdle_rate_bps as u128 * elapsed / (10_000 * 365 * 86400);
+    let excess_gain = nav_before_fee.saturating_sub(hurdle_nav) * fund_state.total_shares as u128 / 100_000_000;
+    (excess_gain * fund_state.performance_fee_bps as u128 / 10_000) as u64
+}
+
+#[tokio::test]
+async fn test_performance_fee_high_water_mark() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 20_000_000_000).await; // $20,000
+    let asset_id = *b"912797GK0000";
+    let asset = asset_pda(&fund.fund_state, &asset_id);
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    register_asset(&mut context, &fund, treasury_bill(&asset_id, maturity_date)).await.unwrap();
+
+    assert_program_error(set_performance_fee(&mut context, &fund, 3001, 500, 7 * 86400).await, ErrorCode::FeeTooHigh);
+    assert_program_error(set_performance_fee(&mut context, &fund, 2000, 500, 0).await, ErrorCode::InvalidAmount);
+
+    // 20% of gains above a 5% hurdle, crystallizing weekly
+    set_performance_fee(&mut context, &fund, 2000, 500, 7 * 86400).await.unwrap();
+    set_price_deviation_limit(&mut context, &fund, &fund.registry_authority, FixedIncomeAssetType::TreasuryBill, 500)
+        .await
+        .unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.high_water_mark, 100_000_000);
+    let period_start = fund_state.performance_period_start;
+
+    // A $20 gain clears the hurdle and accrues a provisional fee
+    advance_clock(&mut context, 86400).await;
+    update_nav(&mut context, &fund, vec![valuation(asset, 100_750_000_000)], &[asset]).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    let fee = expected_performance_fee(&fund_state);
+    assert!(fee > 300_000_000); // Roughly 20% of ($20 - $0.27 hurdle - $0.08 management fee)
+    assert_eq!(fund_state.accrued_performance_fee, fee);
+    assert_eq!(fund_state.total_assets + fund_state.accrued_fees_payable + fee, 2_002_000_000_000);
+
+    // Giving the gain back releases the provisional fee
+    advance_clock(&mut context, 86400).await;
+    update_nav(&mut context, &fund, vec![valuation(asset, 98_750_000_000)], &[asset]).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.accrued_performance_fee, 0);
+    assert!(fund_state.nav_per_share < fund_state.high_water_mark);
+
+    // A $40 gain clears a week of hurdle; at the end of the period the fee becomes payable
+    // and the high-water mark resets
+    advance_clock(&mut context, 5 * 86400).await;
+    update_nav(&mut context, &fund, vec![valuation(asset, 102_750_000_000)], &[asset]).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert!(fund_state.last_nav_update >= period_start + 7 * 86400);
+    assert_eq!(fund_state.accrued_performance_fee, 0);
+    assert_eq!(fund_state.high_water_mark, fund_state.nav_per_share);
+    assert_eq!(fund_state.performance_period_start, fund_state.last_nav_update);
+    assert!(fund_state.accrued_fees_payable > 400_000_000); // Roughly 20% of ($40 - $19.18 hurdle)
+    let crystallized = fund_state;
+
+    // No fee accrues again until NAV beats the new high-water mark plus hurdle
+    advance_clock(&mut context, 86400).await;
+    update_nav(&mut context, &fund, vec![valuation(asset, 102_750_000_000)], &[asset]).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.accrued_performance_fee, 0);
+    assert_eq!(fund_state.high_water_mark, crystallized.high_water_mark);
+    assert!(fund_state.nav_per_share < crystallized.nav_per_share);
+}
+
+async fn withdraw(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    user: &Keypair,
+    user_usdc_account: Pubkey,
+    fund_tokens: u64,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::Withdraw {
+            fund_state: fund.fund_state,
+            user_account: user_account_pda(&fund.fund_state, &user.pubkey()),
+            user: user.pubkey(),
+            user_usdc_account,
+            user_fund_token_account: get_associated_token_address(&user.pubkey(), &fund.fund_token_mint),
+            usdc_vault: fund.usdc_vault,
+            fund_token_mint: fund.fund_token_mint,
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::Withdraw { fund_tokens }.data(),
+    };
+    send(context, instruction, &[user]).await
+}
+
+async fn set_swing_pricing(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    thresholds_bps: (u16, u16),
+    factors_bps: (u16, u16),
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::AdminConfig {
+            fund_state: fund.fund_state,
+            admin_authority: fund.admin.pubkey(),
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::SetSwingPricing {
+            inflow_swing_threshold_bps: thresholds_bps.0,
+            outflow_swing_threshold_bps: thresholds_bps.1,
+            inflow_swing_factor_bps: factors_bps.0,
+            outflow_swing_factor_bps: factors_bps.1,
+        }
+        .data(),
+    };
+    send(context, instruction, &[&fund.admin]).await
+}
+
+#[tokio::test]
+async fn test_swing_pricing() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    let investor = seed_cash(&mut context, &fund, 10_000_000_000).await; // $10,000
+
+    assert_program_error(set_swing_pricing(&mut context, &fund, (500, 500), (201, 100)).await, ErrorCode::InvalidAmount);
+
+    // Swing 1% once net flows since the last NAV update pass 5% of total assets
+    set_swing_pricing(&mut context, &fund, (500, 500), (100, 100)).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.net_flows, 1_000_000_000_000);
+
+    // A NAV update starts a new pricing period
+    advance_clock(&mut context, 86400).await;
+    update_nav(&mut context, &fund, vec![], &[]).await.unwrap();
+    let before: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(before.net_flows, 0);
+    let nav = before.nav_per_share as u128;
+
+    // $400 of inflows stays under the threshold and prices at NAV
+    let first = seed_cash(&mut context, &fund, 400_000_000).await;
+    let account: UserFundAccount = load_account(&mut context, user_account_pda(&fund.fund_state, &first.pubkey())).await;
+    assert_eq!(account.fund_tokens as u128, 400_000_000u128 * 10_000_000_000 / nav);
+
+    // Another $200 takes net inflows to $600, so it is priced at NAV plus 1%
+    let second = seed_cash(&mut context, &fund, 200_000_000).await;
+    let account: UserFundAccount = load_account(&mut context, user_account_pda(&fund.fund_state, &second.pubkey())).await;
+    let swung_tokens = 200_000_000u128 * 10_000_000_000 / (nav * 10_100 / 10_000);
+    assert_eq!(account.fund_tokens as u128, swung_tokens);
+
+    // The levy stays in the fund, accreting to remaining holders at the next NAV update
+    let levy = 200_000_000 - swung_tokens * nav / 100_000_000 / 100;
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.net_flows, 60_000_000_000);
+    assert_eq!(fund_state.total_swing_levies as u128, levy * 100);
+    assert!(levy > 1_900_000); // About $1.98
+    assert_eq!(fund_state.total_assets, before.total_assets + 60_000_000_000);
+
+    advance_clock(&mut context, 86400).await;
+    update_nav(&mut context, &fund, vec![], &[]).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert!(fund_state.nav_per_share > before.nav_per_share);
+    let nav = fund_state.nav_per_share;
+
+    // A $700 redemption is more than 5% of assets and is paid at NAV less 1%
+    let investor_usdc = create_usdc_account(&mut context, &fund.usdc_mint, &investor.pubkey(), 0).await;
+    withdraw(&mut context, &fund, &investor, investor_usdc, 70_000_000_000).await.unwrap();
+    let at_nav = 70_000_000_000u128 * nav as u128 / 100_000_000 / 100;
+    let paid = 70_000_000_000u128 * (nav as u128 * 9_900 / 10_000) / 100_000_000 / 100;
+    assert_eq!(token_balance(&mut context, investor_usdc).await, paid as u64);
+
+    let after: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(after.net_flows, -(at_nav as i64 * 100));
+    assert_eq!(after.total_swing_levies, fund_state.total_swing_levies + (at_nav - paid) as u64 * 100);
+    assert_eq!(after.total_assets, fund_state.total_assets - paid as u64 * 100);
+
+    // Queued redemptions add to the same net outflows and pay the same levy
+    request_redemption(&mut context, &fund, &investor, 10_000_000_000).await.unwrap();
+    process_redemptions(&mut context, &fund, &[(0, investor.pubkey(), investor_usdc)]).await.unwrap();
+    let queued_at_nav = 10_000_000_000u128 * nav as u128 / 100_000_000 / 100;
+    let queued_paid = 10_000_000_000u128 * (nav as u128 * 9_900 / 10_000) / 100_000_000 / 100;
+    assert_eq!(token_balance(&mut context, investor_usdc).await, (paid + queued_paid) as u64);
+
+    let queued: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(queued.net_flows, after.net_flows - queued_at_nav as i64 * 100);
+    assert_eq!(queued.period_redemptions, after.period_redemptions + queued_at_nav as u64 * 100);
+    assert_eq!(queued.total_swing_levies, after.total_swing_levies + (queued_at_nav - queued_paid) as u64 * 100);
+    assert_eq!(queued.total_assets, after.total_assets - queued_paid as u64 * 100);
+}
+
+fn redemption_request_pda(fund_state: &Pubkey, sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"redemption_request", fund_state.as_ref(), sequence.to_le_bytes().as_ref()],
+        &maek_protocol::ID,
+    )
+    .0
+}
+
+fn redemption_escrow_pda(fund_state: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"redemption_escrow", fund_state.as_ref()], &maek_protocol::ID).0
+}
+
+async fn request_redemption(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    user: &Keypair,
+    fund_tokens: u64,
+) -> std::result::Result<(), BanksClientError> {
+    let fund_state: FundState = load_account(context, fund.fund_state).await;
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::RequestRedemption {
+            fund_state: fund.fund_state,
+            user_account: user_account_pda(&fund.fund_state, &user.pubkey()),
+            redemption_request: redemption_request_pda(&fund.fund_state, fund_state.redemption_queue_tail),
+            user: user.pubkey(),
+            user_fund_token_account: get_associated_token_address(&user.pubkey(), &fund.fund_token_mint),
+            redemption_escrow: redemption_escrow_pda(&fund.fund_state),
+            fund_token_mint: fund.fund_token_mint,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::RequestRedemption { fund_tokens }.data(),
+    };
+    send(context, instruction, &[user]).await
+}
+
+/// Process queued requests; each entry is (sequence, owner, owner's USDC account)
+async fn process_redemptions(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    requests: &[(u64, Pubkey, Pubkey)],
+) -> std::result::Result<(), BanksClientError> {
+    let mut accounts = maek_protocol::accounts::ProcessRedemptions {
+        fund_state: fund.fund_state,
+        usdc_vault: fund.usdc_vault,
+        fund_token_mint: fund.fund_token_mint,
+        redemption_escrow: redemption_escrow_pda(&fund.fund_state),
+        token_program: spl_token::ID,
+    }
+    .to_account_metas(None);
+    for (sequence, owner, owner_usdc) in requests {
+        accounts.push(AccountMeta::new(redemption_request_pda(&fund.fund_state, *sequence), false));
+        accounts.push(AccountMeta::new(*owner_usdc, false));
+        accounts.push(AccountMeta::new(*owner, false));
+    }
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts,
+        data: maek_protocol::instruction::ProcessRedemptions {}.data(),
+    };
+    send(context, instruction, &[]).await
+}
+
+#[tokio::test]
+async fn test_redemption_queue() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    let first = seed_cash(&mut context, &fund, 10_000_000_000).await; // $10,000
+    let second = seed_cash(&mut context, &fund, 1_000_000_000).await; // $1,000
+    let first_usdc = create_usdc_account(&mut context, &fund.usdc_mint, &first.pubkey(), 0).await;
+    let second_usdc = create_usdc_account(&mut context, &fund.usdc_mint, &second.pubkey(), 0).await;
+
+    // Put all but $3,100 of cash into T-bills
+    set_maturity_limits(&mut context, &fund, 120, 120).await.unwrap();
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    for index in 0..8u8 {
+        register_asset(&mut context, &fund, treasury_bill(&[b'0' + index; 12], maturity_date)).await.unwrap();
+    }
+    assert_program_error(
+        withdraw(&mut context, &fund, &first, first_usdc, 400_000_000_000).await,
+        ErrorCode::InsufficientLiquidity,
+    );
+
+    // Queue $2,500 then $1,000 of redemptions; the tokens move into escrow
+    request_redemption(&mut context, &fund, &first, 250_000_000_000).await.unwrap();
+    request_redemption(&mut context, &fund, &second, 100_000_000_000).await.unwrap();
+    assert_eq!(token_balance(&mut context, redemption_escrow_pda(&fund.fund_state)).await, 350_000_000_000);
+    let account: UserFundAccount = load_account(&mut context, user_account_pda(&fund.fund_state, &first.pubkey())).await;
+    assert_eq!(account.fund_tokens, 750_000_000_000);
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.redemption_queue_tail, 2);
+    assert_eq!(fund_state.queued_redemption_tokens, 350_000_000_000);
+
+    // Withdrawals cannot jump the queue
+    assert_program_error(
+        withdraw(&mut context, &fund, &first, first_usdc, 1_000_000).await,
+        ErrorCode::RedemptionsQueued,
+    );
+
+    // The queue is paid in order
+    assert_program_error(
+        process_redemptions(&mut context, &fund, &[(1, second.pubkey(), second_usdc)]).await,
+        ErrorCode::RedemptionQueueOrder,
+    );
+
+    // The first request is paid in full and the second with the remaining $600 of cash
+    let queue = [(0, first.pubkey(), first_usdc), (1, second.pubkey(), second_usdc)];
+    process_redemptions(&mut context, &fund, &queue).await.unwrap();
+    assert_eq!(token_balance(&mut context, first_usdc).await, 2_500_000_000);
+    assert_eq!(token_balance(&mut context, second_usdc).await, 600_000_000);
+    assert!(context.banks_client.get_account(redemption_request_pda(&fund.fund_state, 0)).await.unwrap().is_none());
+
+    let request: RedemptionRequest = load_account(&mut context, redemption_request_pda(&fund.fund_state, 1)).await;
+    assert_eq!(request.fund_tokens, 40_000_000_000);
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.cash_reserves, 0);
+    assert_eq!(fund_state.redemption_queue_head, 1);
+    assert_eq!(fund_state.queued_redemption_tokens, 40_000_000_000);
+    assert_eq!(fund_state.total_shares, 1_100_000_000_000 - 310_000_000_000);
+    assert_eq!(fund_state.total_assets, 1_100_000_000_000 - 310_000_000_000);
+
+    // Cash from a maturing bill settles the rest of the queue
+    advance_clock(&mut context, 91 * 86400).await;
+    custodian_pays_in(&mut context, &fund, 1_000_000_000).await;
+    handle_asset_maturity(&mut context, &fund, &[b'0'; 12], &Pubkey::default()).await.unwrap();
+
+    // The queue waits while forward pricing is on
+    set_forward_pricing(&mut context, &fund, true).await.unwrap();
+    assert_program_error(
+        process_redemptions(&mut context, &fund, &[(1, second.pubkey(), second_usdc)]).await,
+        ErrorCode::ForwardPricingActive,
+    );
+    set_forward_pricing(&mut context, &fund, false).await.unwrap();
+    process_redemptions(&mut context, &fund, &[(1, second.pubkey(), second_usdc)]).await.unwrap();
+    assert_eq!(token_balance(&mut context, second_usdc).await, 1_000_000_000);
+    assert!(context.banks_client.get_account(redemption_request_pda(&fund.fund_state, 1)).await.unwrap().is_none());
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.redemption_queue_head, 2);
+    assert_eq!(fund_state.queued_redemption_tokens, 0);
+    assert_eq!(token_balance(&mut context, redemption_escrow_pda(&fund.fund_state)).await, 0);
+}
+
+fn pending_order_pda(fund_state: &Pubkey, sequence: u64) -> Pubkey {
+    Pubkey::find_program_address(
+        &[b"pending_order", fund_state.as_ref(), sequence.to_le_bytes().as_ref()],
+        &maek_protocol::ID,
+    )
+    .0
+}
+
+fn order_escrow_pda(fund_state: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"order_escrow", fund_state.as_ref()], &maek_protocol::ID).0
+}
+
+async fn set_forward_pricing(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    enabled: bool,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::AdminConfig {
+            fund_state: fund.fund_state,
+            admin_authority: fund.admin.pubkey(),
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::SetForwardPricing { enabled }.data(),
+    };
+    send(context, instruction, &[&fund.admin]).await
+}
+
+async fn place_subscription_order(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    user: &Keypair,
+    user_usdc_account: Pubkey,
+    amount: u64,
+) -> std::result::Result<(), BanksClientError> {
+    let fund_state: FundState = load_account(context, fund.fund_state).await;
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::PlaceSubscriptionOrder {
+            fund_state: fund.fund_state,
+            user_account: user_account_pda(&fund.fund_state, &user.pubkey()),
+            pending_order: pending_order_pda(&fund.fund_state, fund_state.order_sequence),
+            user: user.pubkey(),
+            user_usdc_account,
+            user_fund_token_account: get_associated_token_address(&user.pubkey(), &fund.fund_token_mint),
+            usdc_vault: fund.usdc_vault,
+            fund_token_mint: fund.fund_token_mint,
+            token_program: spl_token::ID,
+            associated_token_program: anchor_spl::associated_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::PlaceSubscriptionOrder { amount }.data(),
+    };
+    send(context, instruction, &[user]).await
+}
+
+async fn place_redemption_order(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    user: &Keypair,
+    fund_tokens: u64,
+) -> std::result::Result<(), BanksClientError> {
+    let fund_state: FundState = load_account(context, fund.fund_state).await;
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::PlaceRedemptionOrder {
+            fund_state: fund.fund_state,
+            user_account: user_account_pda(&fund.fund_state, &user.pubkey()),
+            pending_order: pending_order_pda(&fund.fund_state, fund_state.order_sequence),
+            user: user.pubkey(),
+            user_fund_token_account: get_associated_token_address(&user.pubkey(), &fund.fund_token_mint),
+            order_escrow: order_escrow_pda(&fund.fund_state),
+            fund_token_mint: fund.fund_token_mint,
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::PlaceRedemptionOrder { fund_tokens }.data(),
+    };
+    send(context, instruction, &[user]).await
+}
+
+/// Settle orders; each entry is (sequence, owner, owner's fund token or USDC account)
+async fn settle_orders(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    orders: &[(u64, Pubkey, Pubkey)],
+) -> std::result::Result<(), BanksClientError> {
+    let mut accounts = maek_protocol::accounts::SettleOrders {
+        fund_state: fund.fund_state,
+        usdc_vault: fund.usdc_vault,
+        fund_token_mint: fund.fund_token_mint,
+        order_escrow: order_escrow_pda(&fund.fund_state),
+        token_program: spl_token::ID,
+    }
+    .to_account_metas(None);
+    for (sequence, owner, token_account) in orders {
+        accounts.push(AccountMeta::new(pending_order_pda(&fund.fund_state, *sequence), false));
+        accounts.push(AccountMeta::new(*token_account, false));
+        accounts.push(AccountMeta::new(user_account_pda(&fund.fund_state, owner), false));
+        accounts.push(AccountMeta::new(*owner, false));
+    }
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts,
+        data: maek_protocol::instruction::SettleOrders {}.data(),
+    };
+    send(context, instruction, &[]).await
+}
+
+#[tokio::test]
+async fn test_forward_pricing() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    let holder = seed_cash(&mut context, &fund, 10_000_000_000).await; // $10,000
+    let holder_usdc = create_usdc_account(&mut context, &fund.usdc_mint, &holder.pubkey(), 0).await;
+    let subscriber = funded_keypair(&mut context).await;
+    let subscriber_usdc = create_usdc_account(&mut context, &fund.usdc_mint, &subscriber.pubkey(), 1_000_000_000).await;
+
+    assert_program_error(
+        place_subscription_order(&mut context, &fund, &subscriber, subscriber_usdc, 1_000_000_000).await,
+        ErrorCode::ForwardPricingInactive,
+    );
+    set_forward_pricing(&mut context, &fund, true).await.unwrap();
+
+    // Direct deposits, withdrawals and the redemption queue are closed while orders strike at the next NAV
+    assert_program_error(
+        deposit(&mut context, &fund, &subscriber, subscriber_usdc, 1_000_000_000).await,
+        ErrorCode::ForwardPricingActive,
+    );
+    assert_program_error(
+        withdraw(&mut context, &fund, &holder, holder_usdc, 50_000_000_000).await,
+        ErrorCode::ForwardPricingActive,
+    );
+    assert_program_error(
+        request_redemption(&mut context, &fund, &holder, 50_000_000_000).await,
+        ErrorCode::ForwardPricingActive,
+    );
+
+    place_subscription_order(&mut context, &fund, &subscriber, subscriber_usdc, 1_000_000_000).await.unwrap();
+    place_redemption_order(&mut context, &fund, &holder, 50_000_000_000).await.unwrap();
+
+    // Subscription cash sits in the vault outside the fund; redeemed tokens sit in escrow
+    let order: PendingOrder = load_account(&mut context, pending_order_pda(&fund.fund_state, 0)).await;
+    assert_eq!(order.owner, subscriber.pubkey());
+    assert_eq!(order.amount, 1_000_000_000);
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.order_sequence, 2);
+    assert_eq!(fund_state.pending_subscriptions, 1_000_000_000);
+    assert_eq!(fund_state.pending_redemption_tokens, 50_000_000_000);
+    assert_eq!(fund_state.cash_reserves, 10_000_000_000);
+    assert_eq!(token_balance(&mut context, fund.usdc_vault).await, 11_000_000_000);
+    assert_eq!(token_balance(&mut context, order_escrow_pda(&fund.fund_state)).await, 50_000_000_000);
+
+    // Orders cannot settle at the NAV they were placed against
+    let subscriber_tokens = get_associated_token_address(&subscriber.pubkey(), &fund.fund_token_mint);
+    let orders = [(0, subscriber.pubkey(), subscriber_tokens), (1, holder.pubkey(), holder_usdc)];
+    assert_program_error(settle_orders(&mut context, &fund, &orders).await, ErrorCode::OrderAwaitingNAV);
+
+    // The next NAV update, net of a day's management fee, sets the strike price
+    advance_clock(&mut context, 86400).await;
+    update_nav(&mut context, &fund, vec![], &[]).await.unwrap();
+    let struck: FundState = load_account(&mut context, fund.fund_state).await;
+    let nav = struck.nav_per_share;
+    assert!(nav < 100_000_000);
+
+    // An order placed after the strike waits for the following NAV, which waits for the struck orders to settle
+    place_redemption_order(&mut context, &fund, &holder, 10_000_000_000).await.unwrap();
+    let late_order = [(2, holder.pubkey(), holder_usdc)];
+    assert_program_error(settle_orders(&mut context, &fund, &late_order).await, ErrorCode::OrderAwaitingNAV);
+    advance_clock(&mut context, 86400).await;
+    assert_program_error(update_nav(&mut context, &fund, vec![], &[]).await, ErrorCode::OrdersAwaitingSettlement);
+    settle_orders(&mut context, &fund, &orders).await.unwrap();
+
+    let minted = (1_000_000_000u128 * 10_000_000_000 / nav as u128) as u64;
+    let paid = (50_000_000_000u128 * nav as u128 / 100_000_000 / 100) as u64;
+    assert_eq!(token_balance(&mut context, subscriber_tokens).await, minted);
+    assert_eq!(token_balance(&mut context, holder_usdc).await, paid);
+    assert!(context.banks_client.get_account(pending_order_pda(&fund.fund_state, 0)).await.unwrap().is_none());
+    assert!(context.banks_client.get_account(pending_order_pda(&fund.fund_state, 1)).await.unwrap().is_none());
+
+    let account: UserFundAccount = load_account(&mut context, user_account_pda(&fund.fund_state, &subscriber.pubkey())).await;
+    assert_eq!(account.fund_tokens, minted);
+    assert_eq!(account.last_deposit_nav, nav);
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.pending_subscriptions, 0);
+    assert_eq!(fund_state.pending_redemption_tokens, 10_000_000_000);
+    assert_eq!(fund_state.cash_reserves, 10_000_000_000 + 1_000_000_000 - paid);
+    assert_eq!(fund_state.total_shares, struck.total_shares + minted - 50_000_000_000);
+    assert_eq!(fund_state.total_assets, struck.total_assets + 100_000_000_000 - paid * 100);
+    assert_eq!(fund_state.net_flows, 100_000_000_000 - paid as i64 * 100);
+    assert_eq!(fund_state.orders_awaiting_settlement, 0);
+    assert_eq!(fund_state.orders_awaiting_nav, 1);
+
+    // The late order strikes at the following NAV, swung down 1% once its outflow passes 0.5% of assets
+    set_swing_pricing(&mut context, &fund, (50, 50), (100, 100)).await.unwrap();
+    update_nav(&mut context, &fund, vec![], &[]).await.unwrap();
+    let struck: FundState = load_account(&mut context, fund.fund_state).await;
+    settle_orders(&mut context, &fund, &late_order).await.unwrap();
+    let at_nav = (10_000_000_000u128 * struck.nav_per_share as u128 / 100_000_000 / 100) as u64;
+    let swung = (10_000_000_000u128 * (struck.nav_per_share as u128 * 9_900 / 10_000) / 100_000_000 / 100) as u64;
+    assert_eq!(token_balance(&mut context, holder_usdc).await, paid + swung);
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.net_flows, -(at_nav as i64 * 100));
+    assert_eq!(fund_state.total_swing_levies, struck.total_swing_levies + (at_nav - swung) * 100);
+    assert_eq!(fund_state.pending_redemption_tokens, 0);
+    assert_eq!(fund_state.nav_sequence, 2);
+    assert!(context.banks_client.get_account(pending_order_pda(&fund.fund_state, 2)).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_forward_redemption_orders_carry_over_the_gate() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    let holder = seed_cash(&mut context, &fund, 10_000_000_000).await; // $10,000
+    let holder_usdc = create_usdc_account(&mut context, &fund.usdc_mint, &holder.pubkey(), 0).await;
+    set_redemption_gate(&mut context, &fund, 1_500).await.unwrap(); // 15% per period
+    set_forward_pricing(&mut context, &fund, true).await.unwrap();
+
+    // A $2,000 order is larger than one period's gate
+    place_redemption_order(&mut context, &fund, &holder, 200_000_000_000).await.unwrap();
+    advance_clock(&mut context, 86400).await;
+    update_nav(&mut context, &fund, vec![], &[]).await.unwrap();
+    let order = [(0, holder.pubkey(), holder_usdc)];
+    settle_orders(&mut context, &fund, &order).await.unwrap();
+
+    // The gate's worth is paid and the remainder waits for the next NAV
+    let first_paid = token_balance(&mut context, holder_usdc).await;
+    let remaining: PendingOrder = load_account(&mut context, pending_order_pda(&fund.fund_state, 0)).await;
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert!(first_paid > 0);
+    assert!(fund_state.remaining_redemption_capacity().unwrap() < 100_000_000);
+    assert_eq!(remaining.nav_sequence, 1);
+    assert!(remaining.amount > 0 && remaining.amount < 200_000_000_000);
+    assert_eq!(fund_state.pending_redemption_tokens, remaining.amount);
+    assert_eq!(fund_state.orders_awaiting_settlement, 0);
+    assert_eq!(fund_state.orders_awaiting_nav, 1);
+    assert_eq!(token_balance(&mut context, order_escrow_pda(&fund.fund_state)).await, remaining.amount);
+
+    // The remainder cannot be paid again this period, but no longer holds up the next NAV
+    assert_program_error(settle_orders(&mut context, &fund, &order).await, ErrorCode::OrderAwaitingNAV);
+    advance_clock(&mut context, 86400).await;
+    update_nav(&mut context, &fund, vec![], &[]).await.unwrap();
+    settle_orders(&mut context, &fund, &order).await.unwrap();
+
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert!(token_balance(&mut context, holder_usdc).await > first_paid);
+    assert!(context.banks_client.get_account(pending_order_pda(&fund.fund_state, 0)).await.unwrap().is_none());
+    assert_eq!(fund_state.pending_redemption_tokens, 0);
+    assert_eq!(fund_state.orders_awaiting_nav, 0);
+    assert_eq!(fund_state.orders_awaiting_settlement, 0);
+    assert_eq!(token_balance(&mut context, order_escrow_pda(&fund.fund_state)).await, 0);
+}
+
+async fn set_redemption_gate(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    redemption_gate_bps: u16,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::AdminConfig {
+            fund_state: fund.fund_state,
+            admin_authority: fund.admin.pubkey(),
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::SetRedemptionGate { redemption_gate_bps }.data(),
+    };
+    send(context, instruction, &[&fund.admin]).await
+}
+
+/// Simulate a read-only fund status instruction and return its return data
+async fn simulate_fund_status(context: &mut ProgramTestContext, fund: &TestFund, data: Vec<u8>) -> Vec<u8> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::FundStatus { fund_state: fund.fund_state }.to_account_metas(None),
+        data,
+    };
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let transaction =
+        Transaction::new_signed_with_payer(&[instruction], Some(&context.payer.pubkey()), &[&context.payer], blockhash);
+    let simulation = context.banks_client.simulate_transaction(transaction).await.unwrap();
+    let return_data = simulation.simulation_details.unwrap().return_data.unwrap();
+    assert_eq!(return_data.program_id, maek_protocol::ID);
+    return_data.data
+}
+
+async fn redemption_capacity(context: &mut ProgramTestContext, fund: &TestFund) -> u64 {
+    let data = simulate_fund_status(context, fund, maek_protocol::instruction::GetRedemptionCapacity {}.data()).await;
+    u64::from_le_bytes(data[..8].try_into().unwrap())
+}
+
+#[tokio::test]
+async fn test_redemption_gate() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    let holder = seed_cash(&mut context, &fund, 10_000_000_000).await; // $10,000
+    let holder_usdc = create_usdc_account(&mut context, &fund.usdc_mint, &holder.pubkey(), 0).await;
+
+    // Without a gate capacity is unlimited
+    assert_eq!(redemption_capacity(&mut context, &fund).await, u64::MAX);
+    assert_program_error(set_redemption_gate(&mut context, &fund, 10_001).await, ErrorCode::InvalidAmount);
+
+    // A 10% gate allows $1,000 of redemptions this period
+    set_redemption_gate(&mut context, &fund, 1_000).await.unwrap();
+    assert_eq!(redemption_capacity(&mut context, &fund).await, 100_000_000_000);
+    assert_program_error(
+        withdraw(&mut context, &fund, &holder, holder_usdc, 150_000_000_000).await,
+        ErrorCode::RedemptionGateExceeded,
+    );
+    withdraw(&mut context, &fund, &holder, holder_usdc, 60_000_000_000).await.unwrap();
+    assert_eq!(redemption_capacity(&mut context, &fund).await, 40_000_000_000);
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.period_redemptions, 60_000_000_000);
+
+    // Queued redemptions are pro-rated to the remaining capacity
+    request_redemption(&mut context, &fund, &holder, 100_000_000_000).await.unwrap();
+    process_redemptions(&mut context, &fund, &[(0, holder.pubkey(), holder_usdc)]).await.unwrap();
+    assert_eq!(token_balance(&mut context, holder_usdc).await, 1_000_000_000);
+    assert_eq!(redemption_capacity(&mut context, &fund).await, 0);
+    let request: RedemptionRequest = load_account(&mut context, redemption_request_pda(&fund.fund_state, 0)).await;
+    assert_eq!(request.fund_tokens, 60_000_000_000);
+
+    // The next NAV update reopens the gate on the period's assets
+    advance_clock(&mut context, 86400).await;
+    update_nav(&mut context, &fund, vec![], &[]).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.period_redemptions, 0);
+    assert_eq!(fund_state.gate_period_assets, fund_state.total_assets);
+    assert_eq!(redemption_capacity(&mut context, &fund).await, fund_state.total_assets / 10);
+
+    process_redemptions(&mut context, &fund, &[(0, holder.pubkey(), holder_usdc)]).await.unwrap();
+    assert!(context.banks_client.get_account(redemption_request_pda(&fund.fund_state, 0)).await.unwrap().is_none());
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.queued_redemption_tokens, 0);
+    let paid = token_balance(&mut context, holder_usdc).await - 1_000_000_000;
+    assert_eq!(fund_state.period_redemptions, paid * 100);
+}
+
+async fn liquidity_status(context: &mut ProgramTestContext, fund: &TestFund) -> LiquidityStatus {
+    let data = simulate_fund_status(context, fund, maek_protocol::instruction::GetLiquidityStatus {}.data()).await;
+    LiquidityStatus::try_from_slice(&data).unwrap()
+}
+
+async fn manage_liquidity(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::ManageLiquidity {
+            fund_state: fund.fund_state,
+            admin_authority: fund.admin.pubkey(),
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::ManageLiquidity {}.data(),
+    };
+    send(context, instruction, &[&fund.admin]).await
+}
+
+#[tokio::test]
+async fn test_manage_liquidity() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    let holder = seed_cash(&mut context, &fund, 10_000_000_000).await; // $10,000
+    let holder_usdc = create_usdc_account(&mut context, &fund.usdc_mint, &holder.pubkey(), 0).await;
+
+    // All cash above the 25% target can be deployed
+    let status = liquidity_status(&mut context, &fund).await;
+    assert_eq!(status.liquidity_ratio, 100);
+    assert_eq!(status.target_liquidity_ratio, 25);
+    assert_eq!(status.action, LiquidityAction::DeployCash);
+    assert_eq!(status.amount, 7_500_000_000);
+    manage_liquidity(&mut context, &fund).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.last_liquidity_action, LiquidityAction::DeployCash);
+    assert_eq!(fund_state.last_liquidity_amount, 7_500_000_000);
+    assert_eq!(fund_state.last_liquidity_review, now(&mut context).await);
+
+    // Investments stop at the target; the eighth bill would leave cash at 21%
+    set_maturity_limits(&mut context, &fund, 120, 120).await.unwrap();
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    for index in 0..7u8 {
+        register_asset(&mut context, &fund, treasury_bill(&[b'0' + index; 12], maturity_date)).await.unwrap();
+    }
+    assert_program_error(
+        register_asset(&mut context, &fund, treasury_bill(&[b'7'; 12], maturity_date)).await,
+        ErrorCode::LiquidityRatioBelowMinimum,
+    );
+    let status = liquidity_status(&mut context, &fund).await;
+    assert_eq!(status.cash_reserves, 3_087_500_000);
+    assert_eq!((status.action, status.amount), (LiquidityAction::DeployCash, 587_500_000));
+
+    // Redemptions draw cash below target and the review calls for raising cash
+    withdraw(&mut context, &fund, &holder, holder_usdc, 100_000_000_000).await.unwrap();
+    advance_clock(&mut context, 60).await;
+    manage_liquidity(&mut context, &fund).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.last_liquidity_action, LiquidityAction::RaiseCash);
+    assert_eq!(fund_state.last_liquidity_amount, 162_500_000); // $2,250 target less $2,087.50 cash
+    let status = liquidity_status(&mut context, &fund).await;
+    assert_eq!(status.liquidity_ratio, 23);
+}
+
+fn treasury_vault_pda(fund_state: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"treasury_vault", fund_state.as_ref()], &maek_protocol::ID).0
+}
+
+fn initialize_treasury_vault_instruction(fund: &TestFund) -> Instruction {
+    Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::InitializeTreasuryVault {
+            fund_state: fund.fund_state,
+            admin_authority: fund.admin.pubkey(),
+            usdc_mint: fund.usdc_mint,
+            treasury_vault: treasury_vault_pda(&fund.fund_state),
+            token_program: spl_token::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::InitializeTreasuryVault {}.data(),
+    }
+}
+
+async fn initialize_treasury_vault(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+) -> std::result::Result<(), BanksClientError> {
+    send(context, initialize_treasury_vault_instruction(fund), &[&fund.admin]).await
+}
+
+fn sweep_treasury_instruction(fund: &TestFund, amount: u64, to_treasury: bool) -> Instruction {
+    let data = if to_treasury {
+        maek_protocol::instruction::SweepToTreasury { amount }.data()
+    } else {
+        maek_protocol::instruction::SweepFromTreasury { amount }.data()
+    };
+    Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::SweepTreasury {
+            fund_state: fund.fund_state,
+            admin_authority: fund.admin.pubkey(),
+            usdc_vault: fund.usdc_vault,
+            treasury_vault: treasury_vault_pda(&fund.fund_state),
+            token_program: spl_token::ID,
+        }
+        .to_account_metas(None),
+        data,
+    }
+}
+
+/// Sweep USDC to the treasury vault, or back to the USDC vault when `to_treasury` is false
+async fn sweep_treasury(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    amount: u64,
+    to_treasury: bool,
+) -> std::result::Result<(), BanksClientError> {
+    send(context, sweep_treasury_instruction(fund, amount, to_treasury), &[&fund.admin]).await
+}
+
+/// The custodian delivers `amount` USDC of maturity, sale or recovery proceeds into the treasury vault
+async fn custodian_pays_in(context: &mut ProgramTestContext, fund: &TestFund, amount: u64) {
+    let blockhash = context.banks_client.get_latest_blockhash().await.unwrap();
+    let transaction = Transaction::new_signed_with_payer(
+        &[spl_token::instruction::mint_to(
+            &spl_token::ID,
+            &fund.usdc_mint,
+            &treasury_vault_pda(&fund.fund_state),
+            &context.payer.pubkey(),
+            &[],
+            amount,
+        )
+        .unwrap()],
+        Some(&context.payer.pubkey()),
+        &[&context.payer],
+        blockhash,
+    );
+    context.banks_client.process_transaction(transaction).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_treasury_vault_sweeps() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 10_000_000_000).await; // $10,000
+    let treasury_vault = treasury_vault_pda(&fund.fund_state);
+
+    // The vault must be created before cash can be swept
+    assert!(sweep_treasury(&mut context, &fund, 1_000_000_000, true).await.is_err());
+    initialize_treasury_vault(&mut context, &fund).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.treasury_vault, treasury_vault);
+    assert!(initialize_treasury_vault(&mut context, &fund).await.is_err());
+
+    // Only cash above the 25% liquidity target can be swept out
+    assert_program_error(
+        sweep_treasury(&mut context, &fund, 8_000_000_000, true).await,
+        ErrorCode::LiquidityRatioBelowMinimum,
+    );
+    sweep_treasury(&mut context, &fund, 5_000_000_000, true).await.unwrap();
+    assert_eq!(token_balance(&mut context, fund.usdc_vault).await, 5_000_000_000);
+    assert_eq!(token_balance(&mut context, treasury_vault).await, 5_000_000_000);
+
+    // The sweep moves value from cash to the investment side
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.cash_reserves, 5_000_000_000);
+    assert_eq!(fund_state.treasury_cash, 5_000_000_000);
+    assert_eq!(fund_state.fixed_income_value, 500_000_000_000);
+    assert_eq!(fund_state.total_assets, 1_000_000_000_000);
+
+    // NAV updates keep carrying the treasury balance
+    advance_clock(&mut context, 86400).await;
+    update_nav(&mut context, &fund, vec![], &[]).await.unwrap();
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.fixed_income_value, 500_000_000_000);
+    assert_eq!(fund_state.total_assets + fund_state.accrued_fees_payable, 1_000_000_000_000);
+
+    // Settlement cash returns to the USDC vault
+    assert_program_error(
+        sweep_treasury(&mut context, &fund, 6_000_000_000, false).await,
+        ErrorCode::InsufficientLiquidity,
+    );
+    sweep_treasury(&mut context, &fund, 5_000_000_000, false).await.unwrap();
+    assert_eq!(token_balance(&mut context, fund.usdc_vault).await, 10_000_000_000);
+    assert_eq!(token_balance(&mut context, treasury_vault).await, 0);
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.cash_reserves, 10_000_000_000);
+    assert_eq!(fund_state.treasury_cash, 0);
+    assert_eq!(fund_state.fixed_income_value, 0);
+}
+
+fn custodian_attestation_pda(fund_state: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"custodian_attestation", fund_state.as_ref()], &maek_protocol::ID).0
+}
+
+async fn set_custodian(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    authority: &Keypair,
+    custodian: Pubkey,
+    max_attestation_age: i64,
+    attestation_tolerance_bps: u16,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::RegistryConfig {
+            fund_state: fund.fund_state,
+            fund_registry: registry_pda(),
+            authority: authority.pubkey(),
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::SetCustodian { custodian, max_attestation_age, attestation_tolerance_bps }
+            .data(),
+    };
+    send(context, instruction, &[authority]).await
+}
+
+/// Ed25519 precompile instruction carrying `signer`'s signature over `message`
+fn ed25519_verify_instruction(signer: &Keypair, message: &[u8]) -> Instruction {
+    let (public_key_offset, signature_offset, message_offset) = (16u16, 48u16, 112u16);
+    let mut data = vec![1, 0]; // One signature, padding
+    for value in [
+        signature_offset,
+        u16::MAX, // Signature in this instruction
+        public_key_offset,
+        u16::MAX,
+        message_offset,
+        message.len() as u16,
+        u16::MAX,
+    ] {
+        data.extend_from_slice(&value.to_le_bytes());
+    }
+    data.extend_from_slice(signer.pubkey().as_ref());
+    data.extend_from_slice(signer.sign_message(message).as_ref());
+    data.extend_from_slice(message);
+    Instruction { program_id: solana_sdk::ed25519_program::ID, accounts: vec![], data }
+}
+
+async fn submit_attestation(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    signer: &Keypair,
+    statement: HoldingsStatement,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::SubmitAttestation {
+            fund_state: fund.fund_state,
+            custodian_attestation: custodian_attestation_pda(&fund.fund_state),
+            payer: context.payer.pubkey(),
+            instructions: solana_sdk::sysvar::instructions::ID,
+            system_program: anchor_lang::system_program::ID,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::SubmitAttestation { statement }.data(),
+    };
+    let verify = ed25519_verify_instruction(signer, &statement.try_to_vec().unwrap());
+    send_all(context, vec![verify, instruction], &[]).await
+}
+
+#[tokio::test]
+async fn test_custodian_attestation_gates_nav() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 10_000_000_000).await; // $10,000
+    set_maturity_limits(&mut context, &fund, 120, 120).await.unwrap();
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    let mut assets = Vec::new();
+    for index in 0..2u8 {
+        let asset_id = [b'0' + index; 12];
+        register_asset(&mut context, &fund, treasury_bill(&asset_id, maturity_date)).await.unwrap();
+        assets.push(asset_pda(&fund.fund_state, &asset_id));
+    }
+    let valuations = || assets.iter().map(|asset| valuation(*asset, 98_800_000_000)).collect::<Vec<_>>();
+    let attestation = custodian_attestation_pda(&fund.fund_state);
+
+    // Statements need a custodian and the custodian needs sane limits
+    let custodian = Keypair::new();
+    let mut statement = HoldingsStatement {
+        fund: fund.fund_state,
+        statement_date: now(&mut context).await,
+        total_par: 200_000_000_000, // $2,000 par of bills
+        positions_hash: [7; 32],
+    };
+    assert_program_error(
+        submit_attestation(&mut context, &fund, &custodian, statement).await,
+        ErrorCode::MissingAttestation,
+    );
+    assert_program_error(
+        set_custodian(&mut context, &fund, &fund.registry_authority, custodian.pubkey(), 8 * 86400, 200).await,
+        ErrorCode::InvalidAmount,
+    );
+
+    // The registry authority appoints the custodian; the fund admin can neither appoint nor be it
+    assert_program_error(
+        set_custodian(&mut context, &fund, &fund.admin, custodian.pubkey(), 2 * 86400, 200).await,
+        ErrorCode::UnauthorizedAccess,
+    );
+    assert_program_error(
+        set_custodian(&mut context, &fund, &fund.registry_authority, fund.admin.pubkey(), 2 * 86400, 200).await,
+        ErrorCode::InvalidAccountData,
+    );
+    set_custodian(&mut context, &fund, &fund.registry_authority, custodian.pubkey(), 2 * 86400, 200).await.unwrap();
+
+    // Nor can the attestation gate be switched off again
+    assert_program_error(
+        set_custodian(&mut context, &fund, &fund.registry_authority, Pubkey::default(), 2 * 86400, 200).await,
+        ErrorCode::InvalidAccountData,
+    );
+
+    // Without an attestation the NAV cannot be published
+    advance_clock(&mut context, 86400).await;
+    assert_program_error(
+        update_nav(&mut context, &fund, valuations(), &assets).await,
+        ErrorCode::MissingAttestation,
+    );
+
+    // Only the custodian's signature over the exact statement is accepted
+    assert_program_error(
+        submit_attestation(&mut context, &fund, &Keypair::new(), statement).await,
+        ErrorCode::InvalidAttestationSignature,
+    );
+
+    // Par far below the held par fails the coverage check
+    statement.statement_date = now(&mut context).await - 3600;
+    statement.total_par = 100_000_000_000;
+    submit_attestation(&mut context, &fund, &custodian, statement).await.unwrap();
+    assert_program_error(
+        update_nav_with(&mut context, &fund, valuations(), &assets, Some(attestation), None).await,
+        ErrorCode::AttestationMismatch,
+    );
+
+    // Statements cannot be rolled back
+    statement.statement_date -= 3600;
+    assert_program_error(
+        submit_attestation(&mut context, &fund, &custodian, statement).await,
+        ErrorCode::AttestationOutdated,
+    );
+
+    // Par is checked against the $2,000 par held, not the $1,976 the bills are marked at;
+    // a shortfall within 2% is tolerated
+    statement.statement_date = now(&mut context).await;
+    statement.total_par = 196_500_000_000;
+    submit_attestation(&mut context, &fund, &custodian, statement).await.unwrap();
+    let recorded: CustodianAttestation = load_account(&mut context, attestation).await;
+    assert_eq!(recorded.custodian, custodian.pubkey());
+    assert_eq!(recorded.statement_date, statement.statement_date);
+    assert_eq!(recorded.total_par, 196_500_000_000);
+    assert_eq!(recorded.positions_hash, [7; 32]);
+    update_nav_with(&mut context, &fund, valuations(), &assets, Some(attestation), None).await.unwrap();
+
+    // Custody above the booked par always covers it
+    advance_clock(&mut context, 86400).await;
+    statement.statement_date = now(&mut context).await;
+    statement.total_par = 250_000_000_000;
+    submit_attestation(&mut context, &fund, &custodian, statement).await.unwrap();
+    update_nav_with(&mut context, &fund, valuations(), &assets, Some(attestation), None).await.unwrap();
+
+    // The statement goes stale after two days
+    advance_clock(&mut context, 2 * 86400 + 1).await;
+    assert_program_error(
+        update_nav_with(&mut context, &fund, valuations(), &assets, Some(attestation), None).await,
+        ErrorCode::StaleAttestation,
+    );
+}
+
+/// Write a mock Pyth-style price account: magic, version 2, price type, exponent,
+/// publish time and a trading aggregate price with its confidence
+fn set_mock_price(context: &mut ProgramTestContext, feed: Pubkey, price: i64, confidence: u64, publish_time: i64) {
+    let mut data = vec![0u8; 240];
+    data[0..4].copy_from_slice(&0xa1b2_c3d4u32.to_le_bytes());
+    data[4..8].copy_from_slice(&2u32.to_le_bytes());
+    data[8..12].copy_from_slice(&3u32.to_le_bytes());
+    data[20..24].copy_from_slice(&(-8i32).to_le_bytes());
+    data[96..104].copy_from_slice(&publish_time.to_le_bytes());
+    data[208..216].copy_from_slice(&price.to_le_bytes());
+    data[216..224].copy_from_slice(&confidence.to_le_bytes());
+    data[224..228].copy_from_slice(&1u32.to_le_bytes()); // Trading
+    let account = solana_sdk::account::Account {
+        lamports: 1_000_000_000,
+        data,
+        owner: PYTH_ORACLE_PROGRAM_ID,
+        executable: false,
+        rent_epoch: 0,
+    };
+    context.set_account(&feed, &account.into());
+}
+
+async fn set_asset_price_feed(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    asset: Pubkey,
+    price_feed: Pubkey,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::SetAssetPriceFeed {
+            fund_state: fund.fund_state,
+            admin_authority: fund.admin.pubkey(),
+            fixed_income_asset: asset,
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::SetAssetPriceFeed { price_feed }.data(),
+    };
+    send(context, instruction, &[&fund.admin]).await
+}
+
+async fn refresh_asset_price(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    asset: Pubkey,
+    price_feed: Pubkey,
+    price_override: Option<&Keypair>,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::RefreshAssetPrice {
+            fund_state: fund.fund_state,
+            fixed_income_asset: asset,
+            price_feed,
+            price_override_authority: price_override.map(|authority| authority.pubkey()),
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::RefreshAssetPrice {}.data(),
+    };
+    let signers: Vec<&Keypair> = price_override.into_iter().collect();
+    send(context, instruction, &signers).await
+}
+
+#[tokio::test]
+async fn test_oracle_asset_pricing() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 10_000_000_000).await; // $10,000
+    set_maturity_limits(&mut context, &fund, 120, 120).await.unwrap();
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    register_asset(&mut context, &fund, treasury_bill(&[b'0'; 12], maturity_date)).await.unwrap();
+    let asset = asset_pda(&fund.fund_state, &[b'0'; 12]);
+    let registered: FixedIncomeAsset = load_account(&mut context, asset).await;
+    advance_clock(&mut context, 10 * 86400).await;
+    let feed = Pubkey::new_unique();
+    let published = now(&mut context).await;
+    set_mock_price(&mut context, feed, 99_000_000, 10_000, published); // 0.99 +/- 0.0001
+
+    // Only the feed assigned by the admin can value the asset
+    assert_program_error(refresh_asset_price(&mut context, &fund, asset, feed, None).await, ErrorCode::InvalidPriceFeed);
+    set_asset_price_feed(&mut context, &fund, asset, feed).await.unwrap();
+
+    // Accretion up to the switch is booked and folded into the carrying value
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert_eq!(holding.last_interest_calculation, registered.last_interest_calculation + 10 * 86400);
+    assert_eq!(holding.accrued_interest, 0);
+    assert!(holding.current_value > registered.current_value);
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.fixed_income_value, holding.current_value);
+    assert_eq!(fund_state.total_assets, 1_000_000_000_000 + holding.current_value - registered.current_value);
+
+    refresh_asset_price(&mut context, &fund, asset, feed, None).await.unwrap();
+
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert_eq!(holding.current_value, 99_000_000_000);
+    assert_eq!(holding.last_valued_at, published);
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.fixed_income_value, 99_000_000_000);
+    assert_eq!(fund_state.total_assets, 1_000_000_000_000 + 250_000_000); // $2.50 gain on the bill
+
+    // Wide confidence, stale and malformed prices are rejected
+    set_mock_price(&mut context, feed, 99_000_000, 1_000_000, published); // +/- 1%
+    assert_program_error(
+        refresh_asset_price(&mut context, &fund, asset, feed, None).await,
+        ErrorCode::OracleConfidenceTooWide,
+    );
+    advance_clock(&mut context, 86400).await;
+    assert_program_error(
+        refresh_asset_price(&mut context, &fund, asset, feed, None).await,
+        ErrorCode::StaleAssetValuation,
+    );
+    context.set_account(&feed, &solana_sdk::account::Account::new(1_000_000_000, 240, &PYTH_ORACLE_PROGRAM_ID).into());
+    assert_program_error(refresh_asset_price(&mut context, &fund, asset, feed, None).await, ErrorCode::InvalidPriceFeed);
+
+    // A well-formed price account is only trusted when the Pyth program owns it
+    let published = now(&mut context).await;
+    set_mock_price(&mut context, feed, 99_000_000, 10_000, published);
+    let mut spoofed = context.banks_client.get_account(feed).await.unwrap().unwrap();
+    spoofed.owner = Pubkey::new_unique();
+    context.set_account(&feed, &spoofed.into());
+    assert_program_error(refresh_asset_price(&mut context, &fund, asset, feed, None).await, ErrorCode::InvalidPriceFeed);
+
+    // A NAV update needs a fresh oracle price and carries the holding at it
+    assert_program_error(
+        update_nav(&mut context, &fund, vec![valuation(asset, 98_000_000_000)], &[asset]).await,
+        ErrorCode::StaleAssetValuation,
+    );
+    let published = now(&mut context).await;
+    set_mock_price(&mut context, feed, 99_100_000, 10_000, published);
+    refresh_asset_price(&mut context, &fund, asset, feed, None).await.unwrap();
+    update_nav(&mut context, &fund, vec![valuation(asset, 98_000_000_000)], &[asset]).await.unwrap();
+
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert_eq!(holding.current_value, 99_100_000_000);
+    assert_eq!(holding.last_valued_at, published);
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.fixed_income_value, 99_100_000_000);
+
+    // The market price already includes the bill's accretion, so the crank books none on top
+    accrue_interest(&mut context, &fund, &[b'0'; 12]).await.unwrap();
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert_eq!(holding.accrued_interest, 0);
+    assert!(holding.last_interest_calculation > holding.purchase_date);
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.fixed_income_value, 99_100_000_000);
+}
+
+async fn set_price_deviation_limit(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    authority: &Keypair,
+    asset_type: FixedIncomeAssetType,
+    max_deviation_bps: u16,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::RegistryConfig {
+            fund_state: fund.fund_state,
+            fund_registry: registry_pda(),
+            authority: authority.pubkey(),
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::SetPriceDeviationLimit { asset_type, max_deviation_bps }.data(),
+    };
+    send(context, instruction, &[authority]).await
+}
+
+async fn set_price_override_authority(
+    context: &mut ProgramTestContext,
+    fund: &TestFund,
+    authority: &Keypair,
+    price_override_authority: Pubkey,
+) -> std::result::Result<(), BanksClientError> {
+    let instruction = Instruction {
+        program_id: maek_protocol::ID,
+        accounts: maek_protocol::accounts::RegistryConfig {
+            fund_state: fund.fund_state,
+            fund_registry: registry_pda(),
+            authority: authority.pubkey(),
+        }
+        .to_account_metas(None),
+        data: maek_protocol::instruction::SetPriceOverrideAuthority { price_override_authority }.data(),
+    };
+    send(context, instruction, &[authority]).await
+}
+
+#[tokio::test]
+async fn test_price_deviation_limits() {
+    let mut context = start().await;
+    let fund = setup_fund(&mut context).await;
+    seed_cash(&mut context, &fund, 10_000_000_000).await; // $10,000
+    set_maturity_limits(&mut context, &fund, 120, 120).await.unwrap();
+    let maturity_date = now(&mut context).await + 91 * 86400;
+    register_asset(&mut context, &fund, treasury_bill(&[b'0'; 12], maturity_date)).await.unwrap();
+    let asset = asset_pda(&fund.fund_state, &[b'0'; 12]);
+
+    // T-bills default to a 2% limit against the previous valuation
+    let fund_state: FundState = load_account(&mut context, fund.fund_state).await;
+    assert_eq!(fund_state.price_deviation_limit(FixedIncomeAssetType::TreasuryBill), 200);
+    assert_eq!(fund_state.price_deviation_limit(FixedIncomeAssetType::MortgageBackedSecurity), 1_000);
+    advance_clock(&mut context, 86400).await;
+    assert_program_error(
+        update_nav(&mut context, &fund, vec![valuation(asset, 49_375_000_000)], &[asset]).await,
+        ErrorCode::PriceDeviationExceeded,
+    );
+
+    // Limits are configurable per asset type by the registry authority, never by the fund admin
+    let bill = FixedIncomeAssetType::TreasuryBill;
+    assert_program_error(
+        set_price_deviation_limit(&mut context, &fund, &fund.admin, bill, 5_000).await,
+        ErrorCode::UnauthorizedAccess,
+    );
+    assert_program_error(
+        set_price_deviation_limit(&mut context, &fund, &fund.registry_authority, bill, 5_001).await,
+        ErrorCode::InvalidAmount,
+    );
+    set_price_deviation_limit(&mut context, &fund, &fund.registry_authority, bill, 50).await.unwrap();
+    assert_program_error(
+        update_nav(&mut context, &fund, vec![valuation(asset, 99_500_000_000)], &[asset]).await,
+        ErrorCode::PriceDeviationExceeded,
+    );
+    update_nav(&mut context, &fund, vec![valuation(asset, 99_000_000_000)], &[asset]).await.unwrap();
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert_eq!(holding.previous_value, 98_750_000_000);
+    assert_eq!(holding.current_value, 99_000_000_000);
+
+    // Only an override authority appointed by the registry, separate from the admin, can co-sign a larger move
+    let overrider = funded_keypair(&mut context).await;
+    assert_program_error(
+        set_price_override_authority(&mut context, &fund, &fund.registry_authority, fund.admin.pubkey()).await,
+        ErrorCode::InvalidAccountData,
+    );
+    assert_program_error(
+        set_price_override_authority(&mut context, &fund, &fund.admin, overrider.pubkey()).await,
+        ErrorCode::UnauthorizedAccess,
+    );
+    set_price_override_authority(&mut context, &fund, &fund.registry_authority, overrider.pubkey()).await.unwrap();
+    advance_clock(&mut context, 86400).await;
+    let impostor = funded_keypair(&mut context).await;
+    assert_program_error(
+        update_nav_with(&mut context, &fund, vec![valuation(asset, 49_500_000_000)], &[asset], None, Some(&impostor))
+            .await,
+        ErrorCode::UnauthorizedAccess,
+    );
+    update_nav_with(&mut context, &fund, vec![valuation(asset, 49_500_000_000)], &[asset], None, Some(&overrider))
+        .await
+        .unwrap();
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert_eq!(holding.previous_value, 99_000_000_000);
+    assert_eq!(holding.current_value, 49_500_000_000);
+
+    // Oracle prices are held to the same limit, measured from the carrying value with accretion to the switch
+    let feed = Pubkey::new_unique();
+    set_asset_price_feed(&mut context, &fund, asset, feed).await.unwrap();
+    let switched: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert!(switched.current_value > 49_500_000_000);
+    let published = now(&mut context).await;
+    set_mock_price(&mut context, feed, 99_000_000, 10_000, published);
+    assert_program_error(
+        refresh_asset_price(&mut context, &fund, asset, feed, None).await,
+        ErrorCode::PriceDeviationExceeded,
+    );
+    refresh_asset_price(&mut context, &fund, asset, feed, Some(&overrider)).await.unwrap();
+    let holding: FixedIncomeAsset = load_account(&mut context, asset).await;
+    assert_eq!(holding.previous_value, switched.current_value);
+    assert_eq!(holding.current_value, 99_000_000_000);
+}
+
+#[test]
+fn test_account_lengths_match_serialized_size() {
+    use anchor_lang::Discriminator;
+    let discriminator = FundState::discriminator().len();
+    assert_eq!(FundRegistry::LEN, discriminator + FundRegistry::default().try_to_vec().unwrap().len());
+    assert_eq!(FundState::LEN, discriminator + FundState::default().try_to_vec().unwrap().len());
+    assert_eq!(UserFundAccount::LEN, discriminator + UserFundAccount::default().try_to_vec().unwrap().len());
+    assert_eq!(FixedIncomeAsset::LEN, discriminator + FixedIncomeAsset::default().try_to_vec().unwrap().len());
+    assert_eq!(IssuerExposure::LEN, discriminator + IssuerExposure::default().try_to_vec().unwrap().len());
+    assert_eq!(RedemptionRequest::LEN, discriminator + RedemptionRequest::default().try_to_vec().unwrap().len());
+    assert_eq!(PendingOrder::LEN, discriminator + PendingOrder::default().try_to_vec().unwrap().len());
+    assert_eq!(
+        CustodianAttestation::LEN,
+        discriminator + CustodianAttestation::default().try_to_vec().unwrap().len()
+    );
+    let full_history = WriteDownHistory {
+        events: vec![CreditEvent::default(); WriteDownHistory::MAX_EVENTS],
+        ..Default::default()
+    };
+    assert_eq!(WriteDownHistory::LEN, discriminator + full_history.try_to_vec().unwrap().len());
+}